@@ -11,4 +11,5 @@ cargo test --features cond_comp_experimental --no-default-features
 cargo test --features cond_comp_graphics --no-default-features
 cargo test --features cond_comp_networking --no-default-features
 cargo test --features cond_comp_audio --no-default-features
+cargo test --features no_std --no-default-features
 cargo test --all-features
\ No newline at end of file
@@ -0,0 +1,186 @@
+use super::*;
+
+mod kw {
+    use super::*;
+    custom_keyword!(ffi_mirror);
+}
+
+/// The settings of `#[delegate_impl(..)]`, currently only `ffi_mirror = Name`.
+#[derive(Parse, ToTokens)]
+pub struct SettingFfiMirror {
+    kw: kw::ffi_mirror,
+    eq_token: Token![=],
+    /// The name of the struct generated by the setting `ffi_mirror` of `#[delegated_enum]`.
+    name: Ident,
+}
+
+/// Returns the name of the FFI mirror whose shims should be generated, if any.
+pub fn parse_settings(settings: TokenStream) -> Result<Option<Ident>> {
+    if settings.is_empty() {
+        return Ok(None);
+    }
+
+    let SettingFfiMirror { kw, name, .. } = syn::parse2(settings)?;
+
+    if !cfg!(feature = "ffi") {
+        bail!(kw => "`ffi_mirror` generates unsafe code, which requires the feature `ffi` of `spire_enum`.\n\
+            Help: Enable it in your `Cargo.toml`: `spire_enum = { version = \"..\", features = [\"ffi\"] }`");
+    }
+
+    Ok(Some(name))
+}
+
+/// Errors if the impl block has generics, since the shims cannot be generic.
+pub fn check_impl_generics(generics: &Optional<InputGenerics>) -> Result<()> {
+    if let _Some(generics) = generics {
+        bail!(generics => "`extern \"C\"` shims cannot be generated for impl blocks with generics");
+    }
+
+    Ok(())
+}
+
+/// Generates the `extern "C"` shim of `method`, named `[mirror_name_in_snake_case]_[method_name]`,
+/// or `[mirror_name_in_snake_case]_[trait_name_in_snake_case]_[method_name]` if the method belongs to a trait impl
+/// (so that the symbols of traits that share a method name, such as `Debug::fmt` and `Display::fmt`, don't collide).
+///
+/// The shim takes a pointer to the FFI mirror (`*const` for `&self`, `*mut` for `&mut self`) followed by the other
+/// arguments of the method, then calls the method on a bitwise copy of the mirrored enum (see `read_enum`).
+/// With `&mut self`, the copy is written back to the mirror after the call.
+///
+/// The shim returns a status code: `0` on success, or `-1` if the tag of the mirror does not belong to any variant.
+/// The status code only reports invalid tags, the method is called directly, so if it panics the process aborts
+/// (a panic cannot unwind out of `extern "C"`).
+/// If the method returns a value, it is written to the trailing out-pointer `__out`.
+///
+/// `trait_path` must be provided if the method belongs to a trait impl.
+pub fn generate_ffi_shim(
+    method: &SaneMethod,
+    self_ty: &Type,
+    trait_path: Option<&Path>,
+    ffi_ident: &Ident,
+) -> Result<TokenStream> {
+    let SaneMethodSignature {
+        asyncness,
+        ident: fn_ident,
+        generics: fn_generics,
+        receiver,
+        other_inputs,
+        output,
+        ..
+    } = &method.sig;
+
+    if let _Some(asyncness) = asyncness {
+        bail!(asyncness => "`extern \"C\"` shims cannot be generated for async methods");
+    }
+
+    if let _Some(generics) = fn_generics {
+        bail!(generics => "`extern \"C\"` shims cannot be generated for generic methods");
+    }
+
+    let mutability = match receiver {
+        ReceiverKind::Std(Receiver { ty, .. }) => {
+            match &**ty {
+                Type::Reference(TypeReference { mutability, .. }) => mutability.is_some(),
+                _ => {
+                    bail!(receiver => "`extern \"C\"` shims can only be generated for methods that take `&self` \
+                        or `&mut self`")
+                }
+            }
+        }
+        ReceiverKind::NonReceiverWithAttr { receiver_attr, .. } => {
+            bail!(receiver_attr => "`extern \"C\"` shims cannot be generated for methods with the attribute `#[receiver]`")
+        }
+    };
+
+    let shim_ident = {
+        let ffi_snake = ffi_ident.to_string().to_case(Case::Snake);
+        match trait_path.and_then(|trait_path| trait_path.segments.last()) {
+            Some(trait_segment) => {
+                let trait_snake = trait_segment.ident.to_string().to_case(Case::Snake);
+                format_ident!("{ffi_snake}_{trait_snake}_{fn_ident}")
+            }
+            None => format_ident!("{ffi_snake}_{fn_ident}"),
+        }
+    };
+
+    let method_path = match trait_path {
+        Some(trait_path) => quote! { <#self_ty as #trait_path>::#fn_ident },
+        None => quote! { <#self_ty>::#fn_ident },
+    };
+
+    let method_name = {
+        let self_name = self_ty.to_token_stream().to_string().replace(' ', "");
+        match trait_path {
+            Some(trait_path) => {
+                let trait_name = trait_path.to_token_stream().to_string().replace(' ', "");
+                format!("<{self_name} as {trait_name}>::{fn_ident}")
+            }
+            None => format!("{self_name}::{fn_ident}"),
+        }
+    };
+
+    let arg_idents = other_inputs.iter().map(|arg| &arg.pat_ident.ident);
+
+    // Writes the output of `call` to `__out`, if the method returns a value.
+    let (out_arg, call_and_write, docs_out): (_, fn(TokenStream) -> TokenStream, _) = match output {
+        syn::ReturnType::Type(_, ty) => {
+            (
+                Some(quote! { __out: *mut #ty }),
+                |call| quote! { unsafe { ::core::ptr::write(__out, #call) }; },
+                "\n- `__out` must be valid for writes, it receives the output of the method on success.",
+            )
+        }
+        syn::ReturnType::Default => (None, |call| quote! { #call; }, ""),
+    };
+
+    let docs = docs_tokens(format!(
+        "`extern \"C\"` shim of `{method_name}`, generated by [`delegate_impl`](spire_enum_macros::delegate_impl).\n\n\
+         Returns `0` on success, or `-1` if the tag of `*this` does not belong to any variant \
+         (in which case the method is not called).\n\n\
+         If the method panics, the process aborts, since the panic cannot unwind out of `extern \"C\"`.\n\n\
+         # Safety\n\
+         - `this` must point to a valid [`{ffi_ident}`], whose active payload field matches the tag \
+         (as is the case when created with `{ffi_ident}::from`).{docs_out}"
+    ));
+
+    let read_this = quote! {
+        match unsafe { #ffi_ident::read_enum(this) } {
+            ::core::result::Result::Ok(__this) => ::core::mem::ManuallyDrop::new(__this),
+            ::core::result::Result::Err(_) => return -1,
+        }
+    };
+
+    let body = if mutability {
+        let write_output = call_and_write(quote! { __output });
+        quote! {
+            let mut __this = #read_this;
+            let __output = #method_path(&mut *__this, #(#arg_idents),*);
+            unsafe {
+                ::core::ptr::write(this, #ffi_ident::from(::core::mem::ManuallyDrop::into_inner(__this)));
+            }
+            #write_output
+            0
+        }
+    } else {
+        let call_and_write = call_and_write(quote! { #method_path(&*__this, #(#arg_idents),*) });
+        quote! {
+            let __this = #read_this;
+            #call_and_write
+            0
+        }
+    };
+
+    let this_ty = if mutability {
+        quote! { *mut #ffi_ident }
+    } else {
+        quote! { *const #ffi_ident }
+    };
+
+    Ok(quote! {
+        #docs
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn #shim_ident(this: #this_ty, #(#other_inputs,)* #out_arg) -> i32 {
+            #body
+        }
+    })
+}
@@ -12,9 +12,9 @@ pub struct InputImplInherent {
     items: Brace<Any<InputImplItem>>,
 }
 
-pub fn run(input: InputImplInherent) -> Result<TokenStream> {
+pub fn run(input: InputImplInherent, ffi_mirror: Option<Ident>) -> Result<TokenStream> {
     let sane = sanitize_input(input)?;
-    generate_output(sane)
+    generate_output(sane, ffi_mirror)
 }
 
 struct SaneImplInherent {
@@ -100,7 +100,7 @@ fn sanitize_fn(input: Box<InputImplItemFn>) -> Result<SaneMethod> {
     })
 }
 
-fn generate_output(sane: SaneImplInherent) -> Result<TokenStream> {
+fn generate_output(sane: SaneImplInherent, ffi_mirror: Option<Ident>) -> Result<TokenStream> {
     let SaneImplInherent {
         attrs,
         defaultness,
@@ -120,8 +120,20 @@ fn generate_output(sane: SaneImplInherent) -> Result<TokenStream> {
         delegate_macro_ident(enum_ident)
     };
 
+    let functions = functions.into_inner();
+
+    let ffi_shims = match &ffi_mirror {
+        Some(ffi_ident) => {
+            ffi_shims::check_impl_generics(&impl_generics)?;
+            functions
+                .iter()
+                .map(|method| ffi_shims::generate_ffi_shim(method, &self_ty, None, ffi_ident))
+                .try_collect::<_, Vec<_>, _>()?
+        }
+        None => Vec::new(),
+    };
+
     let functions_tt = functions
-        .into_inner()
         .into_iter()
         .map(|method| sane_method_output(method, &macro_ident))
         .try_collect::<_, Vec<_>, _>()?;
@@ -131,5 +143,7 @@ fn generate_output(sane: SaneImplInherent) -> Result<TokenStream> {
         #defaultness #impl_unsafety #impl_token #impl_generics #self_ty #impl_where_clause {
             #(#functions_tt)*
         }
+
+        #(#ffi_shims)*
     })
 }
@@ -1,3 +1,4 @@
+mod ffi_shims;
 mod inherent_impl;
 mod shared;
 mod trait_impl;
@@ -14,10 +15,11 @@ pub enum InputImpl {
     Trait(Box<InputImplTrait>),
 }
 
-pub fn run(input: TokenStream1) -> Result<TokenStream> {
+pub fn run(settings: TokenStream1, input: TokenStream1) -> Result<TokenStream> {
+    let ffi_mirror = ffi_shims::parse_settings(settings.into())?;
     let input = syn::parse::<InputImpl>(input)?;
     match input {
-        InputImpl::Inherent(input) => inherent_impl::run(*input),
-        InputImpl::Trait(input) => trait_impl::run(*input),
+        InputImpl::Inherent(input) => inherent_impl::run(*input, ffi_mirror),
+        InputImpl::Trait(input) => trait_impl::run(*input, ffi_mirror),
     }
 }
@@ -15,9 +15,9 @@ pub struct InputImplTrait {
     items: Brace<Any<InputImplItem>>,
 }
 
-pub fn run(input: InputImplTrait) -> Result<TokenStream> {
+pub fn run(input: InputImplTrait, ffi_mirror: Option<Ident>) -> Result<TokenStream> {
     let sane = sanitize_input(input)?;
-    generate_output(sane)
+    generate_output(sane, ffi_mirror)
 }
 
 struct SaneImplTrait {
@@ -186,7 +186,7 @@ fn sanitize_fn(input: Box<InputImplItemFn>) -> Result<SaneItem> {
     }
 }
 
-fn generate_output(sane: SaneImplTrait) -> Result<TokenStream> {
+fn generate_output(sane: SaneImplTrait, ffi_mirror: Option<Ident>) -> Result<TokenStream> {
     let SaneImplTrait {
         attrs,
         defaultness,
@@ -209,8 +209,28 @@ fn generate_output(sane: SaneImplTrait) -> Result<TokenStream> {
         delegate_macro_ident(enum_ident)
     };
 
+    let items = items.into_inner();
+
+    let ffi_shims = match &ffi_mirror {
+        Some(ffi_ident) => {
+            ffi_shims::check_impl_generics(&impl_generics)?;
+            items
+                .iter()
+                .filter_map(|item| {
+                    match item {
+                        SaneItem::Method(method) => Some(method),
+                        _ => None,
+                    }
+                })
+                .map(|method| {
+                    ffi_shims::generate_ffi_shim(method, &self_ty, Some(&trait_path), ffi_ident)
+                })
+                .try_collect::<_, Vec<_>, _>()?
+        }
+        None => Vec::new(),
+    };
+
     let items = items
-        .into_inner()
         .into_iter()
         .map(|item| match item {
             SaneItem::AssocType(ty) => Ok(ty.to_token_stream()),
@@ -226,5 +246,7 @@ fn generate_output(sane: SaneImplTrait) -> Result<TokenStream> {
         #not_token #trait_path #for_token #self_ty #impl_where_clause {
             #(#items)*
         }
+
+        #(#ffi_shims)*
     })
 }
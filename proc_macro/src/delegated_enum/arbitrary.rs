@@ -0,0 +1,155 @@
+use strategies::{ArbitraryCase, ArbitraryFields, ImplGenerics};
+
+use super::*;
+
+/// Generates the `Arbitrary` impls of the settings `arbitrary` and `proptest`, for the enum and its extracted
+/// variant types.
+///
+/// The enum picks one of its variants according to their weights (`#[weight(n)]`, 1 by default), variants with
+/// the attribute `#[arbitrary(skip)]` are never picked, and their extracted types (if any) don't get impls.
+///
+/// # Errors
+/// - If `#[weight(..)]` or `#[arbitrary(skip)]` are used without any of the settings.
+/// - If a weight is zero, or is combined with `#[arbitrary(skip)]`.
+/// - If all variants are skipped.
+pub(super) fn generate_arbitrary_impls(
+    enum_def: &SaneEnum,
+    settings: &Settings,
+) -> Result<TokenStream> {
+    let weights = sanitize_weights(enum_def, settings)?;
+
+    let (arbitrary, proptest) = (settings.arbitrary.is_some(), settings.proptest.is_some());
+    if !arbitrary && !proptest {
+        return Ok(TokenStream::new());
+    }
+
+    let mut stream = TokenStream::new();
+    let mut enum_cases = Vec::new();
+
+    for (var, weight) in enum_def.variants.iter().zip(weights) {
+        let Some(weight) = weight else { continue };
+
+        let var_ident = &var.ident;
+        let enum_path = &enum_def.path;
+        let var_cfgs = &var.attrs.cfg_attrs;
+
+        if settings.extract_variants.is_some() && var.allow_extract() {
+            let var_args = var.generics.stream_args();
+            let var_ty = quote! { #var_ident #var_args };
+
+            let cases = [ArbitraryCase {
+                cfg_attrs: None,
+                weight: 1,
+                path: var_ident.to_token_stream(),
+                fields: case_fields(&var.fields),
+            }];
+
+            if arbitrary {
+                let generics = ImplGenerics::bounded(&var.generics, strategies::arbitrary_bound());
+                let impl_ = strategies::arbitrary_impl(&var_ty, &generics, &cases);
+                stream.extend(quote! { #var_cfgs #impl_ });
+            }
+
+            if proptest {
+                let generics = ImplGenerics::bounded(&var.generics, strategies::proptest_bound());
+                let impl_ = strategies::proptest_impl(&var_ty, &generics, &cases);
+                stream.extend(quote! { #var_cfgs #impl_ });
+            }
+
+            enum_cases.push(ArbitraryCase {
+                cfg_attrs: Some(var_cfgs),
+                weight,
+                path: quote! { #enum_path::#var_ident },
+                fields: ArbitraryFields::Unnamed(vec![var_ty]),
+            });
+        } else {
+            enum_cases.push(ArbitraryCase {
+                cfg_attrs: Some(var_cfgs),
+                weight,
+                path: quote! { #enum_path::#var_ident },
+                fields: case_fields(&var.fields),
+            });
+        }
+    }
+
+    let enum_ty = enum_def.ty.to_token_stream();
+
+    if arbitrary {
+        let generics = ImplGenerics::bounded(&enum_def.generics, strategies::arbitrary_bound());
+        stream.extend(strategies::arbitrary_impl(&enum_ty, &generics, &enum_cases));
+    }
+
+    if proptest {
+        let generics = ImplGenerics::bounded(&enum_def.generics, strategies::proptest_bound());
+        stream.extend(strategies::proptest_impl(&enum_ty, &generics, &enum_cases));
+    }
+
+    Ok(stream)
+}
+
+/// Returns the weight of each variant, or `None` for the variants with `#[arbitrary(skip)]`.
+fn sanitize_weights(enum_def: &SaneEnum, settings: &Settings) -> Result<Vec<Option<u32>>> {
+    let enabled = settings.arbitrary.is_some() || settings.proptest.is_some();
+    let mut weights = Vec::with_capacity(enum_def.variants.len());
+
+    for var in &enum_def.variants {
+        let SaneVariantAttributes {
+            weight,
+            arbitrary_skip,
+            ..
+        } = &var.attrs;
+
+        match (weight, arbitrary_skip) {
+            (_Some((kw, _)), _) if !enabled => {
+                bail!(kw => "`#[weight(..)]` requires one of the settings `arbitrary` or `proptest`")
+            }
+            (_, _Some(kw)) if !enabled => {
+                bail!(kw => "`#[arbitrary(skip)]` requires one of the settings `arbitrary` or `proptest`")
+            }
+            (_Some((kw, _)), _Some(skip)) => {
+                bail!(kw => "`#[weight(..)]` cannot be combined with `#[arbitrary(skip)]`..", skip => "..found here")
+            }
+            (_Some((_, lit)), _None) => {
+                let weight = lit.base10_parse::<u32>()?;
+                if weight == 0 {
+                    bail!(lit => "weights must be greater than zero.\n\
+                        Help: Use `#[arbitrary(skip)]` to never generate this variant");
+                }
+
+                weights.push(Some(weight));
+            }
+            (_None, _Some(_)) => weights.push(None),
+            (_None, _None) => weights.push(Some(1)),
+        }
+    }
+
+    if enabled && weights.iter().all(Option::is_none) {
+        bail!(enum_def.ident => "at least one variant must not have the attribute `#[arbitrary(skip)]`");
+    }
+
+    Ok(weights)
+}
+
+fn case_fields(fields: &SaneVarFields) -> ArbitraryFields<'_> {
+    match fields {
+        SaneVarFields::Named(named) => {
+            ArbitraryFields::Named(
+                named
+                    .fields
+                    .iter()
+                    .map(|field| (&field.ident, field.ty.to_token_stream()))
+                    .collect(),
+            )
+        }
+        SaneVarFields::Unnamed(unnamed) => {
+            ArbitraryFields::Unnamed(
+                unnamed
+                    .fields
+                    .iter()
+                    .map(|field| field.ty.to_token_stream())
+                    .collect(),
+            )
+        }
+        SaneVarFields::Unit => ArbitraryFields::Unit,
+    }
+}
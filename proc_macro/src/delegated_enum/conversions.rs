@@ -20,11 +20,13 @@ pub fn generate_variant_try_from_enum(
         attrs: _,
         vis: _,
         enum_token: _,
-        ident: enum_ident,
+        ident: _,
+        path: enum_path,
         generics: enum_generics,
         _brace,
         variants: _,
         ty: enum_ty,
+        foreign: _,
     } = enum_def;
 
     let SaneVar {
@@ -35,6 +37,11 @@ pub fn generate_variant_try_from_enum(
                 no_var_type: _,
                 no_convert: _,
                 delegate_via: _,
+                variant_id: _,
+                former_names: _,
+                weight: _,
+                arbitrary_skip: _,
+                transitions: _,
             },
         ident: var_ident,
         fields: var_fields,
@@ -89,7 +96,7 @@ pub fn generate_variant_try_from_enum(
         };
 
         let if_var_from_enum = quote! {
-            if let #enum_ident::#var_ident(__var) = #fn_input {
+            if let #enum_path::#var_ident(__var) = #fn_input {
                 ::core::result::Result::Ok(__var)
             }
         };
@@ -110,7 +117,7 @@ pub fn generate_variant_try_from_enum(
                 } = &named.fields[0];
 
                 let if_var_from_enum = quote! {
-                    if let #enum_ident::#var_ident { #field_ident, .. } = #fn_input {
+                    if let #enum_path::#var_ident { #field_ident, .. } = #fn_input {
                         Ok(#field_ident)
                     }
                 };
@@ -128,7 +135,7 @@ pub fn generate_variant_try_from_enum(
                 } = &unnamed.fields[0];
 
                 let if_var_from_enum = quote! {
-                    if let #enum_ident::#var_ident(__var) = #fn_input {
+                    if let #enum_path::#var_ident(__var) = #fn_input {
                         ::core::result::Result::Ok(__var)
                     }
                 };
@@ -183,7 +190,7 @@ pub fn generate_variant_try_from_enum(
         #var_cfg_attrs
         impl #gen_lf_params ::spire_enum::prelude::FromEnumRef<#enum_ty> for #var_ty #where_clause {
             fn from_enum_ref<'__ref>(#fn_input: &'__ref #enum_ty) -> ::core::option::Option<&'__ref Self> {
-                if let #enum_ident::#var_ident(__var) = #fn_input {
+                if let #enum_path::#var_ident(__var) = #fn_input {
                     ::core::option::Option::Some(__var)
                 } else {
                     ::core::option::Option::None
@@ -194,7 +201,7 @@ pub fn generate_variant_try_from_enum(
         #var_cfg_attrs
         impl #gen_lf_params ::spire_enum::prelude::FromEnumMut<#enum_ty> for #var_ty #where_clause {
             fn from_enum_mut<'__ref>(#fn_input: &'__ref mut #enum_ty) -> ::core::option::Option<&'__ref mut Self> {
-                if let #enum_ident::#var_ident(__var) = #fn_input {
+                if let #enum_path::#var_ident(__var) = #fn_input {
                     ::core::option::Option::Some(__var)
                 } else {
                     ::core::option::Option::None
@@ -213,11 +220,13 @@ pub fn generate_enum_from_variant(
         attrs: _,
         vis: _,
         enum_token: _,
-        ident: enum_ident,
+        ident: _,
+        path: enum_path,
         generics: enum_generics,
         _brace,
         variants: _,
         ty: enum_ty,
+        foreign: _,
     } = enum_def;
     let SaneVar {
         attrs:
@@ -227,6 +236,11 @@ pub fn generate_enum_from_variant(
                 no_var_type: _,
                 no_convert: _,
                 delegate_via: _,
+                variant_id: _,
+                former_names: _,
+                weight: _,
+                arbitrary_skip: _,
+                transitions: _,
             },
         ident: var_ident,
         fields: var_fields,
@@ -248,7 +262,7 @@ pub fn generate_enum_from_variant(
         };
 
         let ret_enum_from_var = quote! {
-            #enum_ident::#var_ident(#fn_input)
+            #enum_path::#var_ident(#fn_input)
         };
 
         (var_ty, ret_enum_from_var)
@@ -267,7 +281,7 @@ pub fn generate_enum_from_variant(
                 } = &named.fields[0];
 
                 let ret_enum_from_var = quote! {
-                    #enum_ident::#var_ident { #field_ident: #fn_input }
+                    #enum_path::#var_ident { #field_ident: #fn_input }
                 };
 
                 (quote! { #var_ty }, ret_enum_from_var)
@@ -283,7 +297,7 @@ pub fn generate_enum_from_variant(
                 } = &unnamed.fields[0];
 
                 let ret_enum_from_var = quote! {
-                    #enum_ident::#var_ident(#fn_input)
+                    #enum_path::#var_ident(#fn_input)
                 };
 
                 (quote! { #var_ty }, ret_enum_from_var)
@@ -6,15 +6,32 @@ pub struct SaneEnum {
     pub vis: Visibility,
     pub enum_token: Token![enum],
     pub ident: Ident,
+    /// How the generated code refers to the enum when naming its variants (`#path::Variant`).
+    ///
+    /// Same as `ident`, unless the enum is foreign, in which case it's the path provided to the macro.
+    pub path: TokenStream,
     pub generics: Optional<SaneGenerics>,
     pub _brace: syn::token::Brace,
     pub variants: Vec<SaneVar>,
     pub ty: Box<Type>,
+    /// Whether the enum is defined elsewhere, see [`ForeignEnum`].
+    pub foreign: bool,
 }
 
 pub(super) fn run(enum_stream: TokenStream1, settings: Settings) -> Result<TokenStream> {
     let input_enum = syn::parse::<Enum<Meta<VarMeta>, Meta<kw_delegator>>>(enum_stream)?;
-    let enum_def = sanitize_input(input_enum, &settings)?;
+    tokenize(input_enum, settings, None)
+}
+
+/// `foreign_path` must be provided when the enum is defined elsewhere, in which case:
+/// - The enum definition is not emitted, an exhaustiveness check is emitted instead.
+/// - The impls that the orphan rules forbid (foreign traits for the foreign enum) are not emitted.
+pub(super) fn tokenize(
+    input_enum: Enum<Meta<VarMeta>, Meta<kw_delegator>>,
+    settings: Settings,
+    foreign_path: Option<Path>,
+) -> Result<TokenStream> {
+    let enum_def = sanitize_input(input_enum, &settings, foreign_path)?;
 
     let mut stream = TokenStream::new();
 
@@ -47,7 +64,7 @@ pub(super) fn run(enum_stream: TokenStream1, settings: Settings) -> Result<Token
 
     let should_impl_enum_into_vars = settings.should_impl_enum_try_into_variants();
 
-    if should_impl_enum_into_vars {
+    if should_impl_enum_into_vars && !enum_def.foreign {
         let gen_params = enum_def.generics.stream_params();
         let where_clause = enum_def.generics.as_pair().1;
         let enum_ty = &enum_def.ty;
@@ -96,7 +113,49 @@ pub(super) fn run(enum_stream: TokenStream1, settings: Settings) -> Result<Token
         (false, false) => {}
     }
 
-    stream.extend(generate_enum_type(&enum_def, &settings)?);
+    if enum_def.foreign {
+        let (gen_params, where_clause) = enum_def.generics.as_pair();
+        stream.extend(tokenize_exhaustiveness_check(
+            &enum_def.path,
+            &enum_def.ty,
+            &gen_params,
+            &where_clause,
+            enum_def.variants.iter().map(|var| &var.attrs.cfg_attrs),
+            enum_def.variants.iter().map(|var| &var.ident),
+        ));
+    } else {
+        stream.extend(generate_enum_type(&enum_def, &settings)?);
+    }
+
+    let variant_ids = variant_ids::sanitize_variant_ids(&enum_def)?;
+
+    if let Some(ids) = &variant_ids {
+        if enum_def.foreign {
+            bail!(enum_def.ident => "Variant ids cannot be used with foreign enums, \
+                since the orphan rules forbid adding methods to types of other crates.\n\
+                Help: Apply `#[variant_id = N]` to the variants in the enum's definition instead.");
+        }
+
+        stream.extend(variant_ids::generate_variant_id_impls(&enum_def, ids));
+    }
+
+    if settings.wire.is_some() {
+        stream.extend(wire::generate_wire_impls(&enum_def, &settings, variant_ids.as_deref()));
+    }
+
+    if let _Some(mirror) = &settings.ffi_mirror {
+        stream.extend(ffi_mirror::generate_ffi_mirror(
+            &enum_def,
+            &settings,
+            mirror,
+            variant_ids.as_deref(),
+        )?);
+    }
+
+    stream.extend(arbitrary::generate_arbitrary_impls(&enum_def, &settings)?);
+
+    stream.extend(state_machine::generate_state_machine(&enum_def, &settings)?);
+
     stream.extend(generate_delegate_macro(&enum_def, &settings)?);
 
     Ok(stream)
@@ -105,6 +164,7 @@ pub(super) fn run(enum_stream: TokenStream1, settings: Settings) -> Result<Token
 fn sanitize_input(
     input: Enum<Meta<VarMeta>, Meta<kw_delegator>>,
     settings: &Settings,
+    foreign_path: Option<Path>,
 ) -> Result<SaneEnum> {
     let Enum {
         attrs,
@@ -126,10 +186,15 @@ fn sanitize_input(
         .map(|var| sanitize_variant(var, settings, &generics))
         .try_collect::<_, Vec<_>, _>()?;
 
+    let path = match &foreign_path {
+        Some(path) => path.to_token_stream(),
+        None => ident.to_token_stream(),
+    };
+
     let ty: Box<Type> = {
         let enum_gen_args = generics.stream_args();
 
-        (|| Ok(try_parse_quote!(#ident #enum_gen_args)))().map_err(|mut err: Error| {
+        (|| Ok(try_parse_quote!(#path #enum_gen_args)))().map_err(|mut err: Error| {
             let msg = Error::new(
                 ident.span(),
                 "parse_quote! failed to convert tokens into a syn::Type, we tried to merge this ident..",
@@ -148,10 +213,12 @@ fn sanitize_input(
         vis,
         enum_token,
         ident,
+        path,
         generics,
         _brace: brace,
         variants,
         ty,
+        foreign: foreign_path.is_some(),
     })
 }
 
@@ -161,10 +228,12 @@ fn generate_enum_type(enum_def: &SaneEnum, settings: &Settings) -> Result<TokenS
         vis,
         enum_token,
         ident,
+        path: _,
         generics,
         _brace: _,
         variants,
         ty: _,
+        foreign: _,
     } = enum_def;
 
     let (generics, where_clause) = generics.as_pair();
@@ -217,29 +286,35 @@ fn generate_delegate_macro(enum_def: &SaneEnum, settings: &Settings) -> Result<T
             _Some(ExplicitDelegator::Expr(_, expr)) => {
                 handle_delegator_closure(enum_def, var, var_ty.as_ref(), expr)?
             }
-            _None => match &var.fields {
-                SaneVarFields::Named(SaneVarFieldsNamed {
-                    fields: _,
-                    delegator: Some((_, field_ident, field_ty)),
-                }) => handle_delegator_field_named(
-                    enum_def,
-                    var,
-                    field_ident,
-                    field_ty,
-                    var_ty.is_some(),
-                ),
-                SaneVarFields::Unnamed(SaneVarFieldsUnnamed {
-                    fields: _,
-                    delegator: Some((_, field_idx, field_ty)),
-                }) => handle_delegator_field_unnamed(
-                    enum_def,
-                    var,
-                    *field_idx,
-                    field_ty,
-                    var_ty.is_some(),
-                ),
-                _ => handle_no_explicit_delegator(enum_def, var, var_ty.as_ref())?,
-            },
+            _None => {
+                match &var.fields {
+                    SaneVarFields::Named(SaneVarFieldsNamed {
+                        fields: _,
+                        delegator: Some((_, field_ident, field_ty)),
+                    }) => {
+                        handle_delegator_field_named(
+                            enum_def,
+                            var,
+                            field_ident,
+                            field_ty,
+                            var_ty.is_some(),
+                        )
+                    }
+                    SaneVarFields::Unnamed(SaneVarFieldsUnnamed {
+                        fields: _,
+                        delegator: Some((_, field_idx, field_ty)),
+                    }) => {
+                        handle_delegator_field_unnamed(
+                            enum_def,
+                            var,
+                            *field_idx,
+                            field_ty,
+                            var_ty.is_some(),
+                        )
+                    }
+                    _ => handle_no_explicit_delegator(enum_def, var, var_ty.as_ref())?,
+                }
+            }
         };
 
         cases_closure.push(case_closure);
@@ -313,7 +388,7 @@ fn handle_delegator_closure(
     var_ty: Option<&Type>,
     expr: &Paren<ExprClosure>,
 ) -> Result<(TokenStream, TokenStream, TokenStream)> {
-    let enum_ident = &enum_def.ident;
+    let enum_path = &enum_def.path;
     let var_ident = &variant.ident;
     let var_cfgs = &variant.attrs.cfg_attrs;
     let delegator_ty = match &expr.output {
@@ -357,7 +432,7 @@ fn handle_delegator_closure(
     if var_ty.is_some() {
         let closure = quote! {
             #var_cfgs
-            #enum_ident::#var_ident(__var) => {
+            #enum_path::#var_ident(__var) => {
                 let __f = #expr_without_ret;
                 let $arg = __f(__var);
                 $($Rest)*
@@ -366,7 +441,7 @@ fn handle_delegator_closure(
 
         let tokens = quote! {
             #var_cfgs
-            #enum_ident::#var_ident(__var) => {
+            #enum_path::#var_ident(__var) => {
                 let __f = #expr_without_ret;
                 let __res = __f(__var);
                 __res $($Rest)*
@@ -375,7 +450,7 @@ fn handle_delegator_closure(
 
         let non_receiver = quote! {
             #var_cfgs
-            #enum_ident::#var_ident(__var) => {
+            #enum_path::#var_ident(__var) => {
                 let __f = #expr_without_ret;
                 let $arg = __f(__var);
                 <#delegator_ty>::$($NonReceiverFn)*($($Args),*) $($Rest)*
@@ -397,7 +472,7 @@ fn handle_delegator_closure(
 
                 let closure = quote! {
                     #var_cfgs
-                    #enum_ident::#var_ident { #(#field_idents),* , .. } => {
+                    #enum_path::#var_ident { #(#field_idents),* , .. } => {
                         let __f = #expr_without_ret;
                         let $arg = __f(#(#field_idents),*);
                         $($Rest)*
@@ -406,7 +481,7 @@ fn handle_delegator_closure(
 
                 let tokens = quote! {
                     #var_cfgs
-                    #enum_ident::#var_ident { #(#field_idents),* , .. } => {
+                    #enum_path::#var_ident { #(#field_idents),* , .. } => {
                         let __f = #expr_without_ret;
                         let __res = __f(#(#field_idents),*);
                         __res $($Rest)*
@@ -415,7 +490,7 @@ fn handle_delegator_closure(
 
                 let non_receiver = quote! {
                     #var_cfgs
-                    #enum_ident::#var_ident { #(#field_idents),* , .. } => {
+                    #enum_path::#var_ident { #(#field_idents),* , .. } => {
                         let __f = #expr_without_ret;
                         let $arg = __f(#(#field_idents),*);
                         <#delegator_ty>::$($NonReceiverFn)*($($Args),*) $($Rest)*
@@ -437,7 +512,7 @@ fn handle_delegator_closure(
 
                 let closure = quote! {
                     #var_cfgs
-                    #enum_ident::#var_ident(#(#field_idents),* , ..) => {
+                    #enum_path::#var_ident(#(#field_idents),* , ..) => {
                         let __f = #expr_without_ret;
                         let $arg = __f(#(#field_idents),*);
                         $($Rest)*
@@ -446,7 +521,7 @@ fn handle_delegator_closure(
 
                 let tokens = quote! {
                     #var_cfgs
-                    #enum_ident::#var_ident(#(#field_idents),* , ..) => {
+                    #enum_path::#var_ident(#(#field_idents),* , ..) => {
                         let __f = #expr_without_ret;
                         let __res = __f(#(#field_idents),*);
                         __res $($Rest)*
@@ -455,7 +530,7 @@ fn handle_delegator_closure(
 
                 let non_receiver = quote! {
                     #var_cfgs
-                    #enum_ident::#var_ident(#(#field_idents),* , ..) => {
+                    #enum_path::#var_ident(#(#field_idents),* , ..) => {
                         let __f = #expr_without_ret;
                         let $arg = __f(#(#field_idents),*);
                         <#delegator_ty>::$($NonReceiverFn)*($($Args),*) $($Rest)*
@@ -467,7 +542,7 @@ fn handle_delegator_closure(
             SaneVarFields::Unit => {
                 let closure = quote! {
                     #var_cfgs
-                    #enum_ident::#var_ident => {
+                    #enum_path::#var_ident => {
                         let __f = #expr_without_ret;
                         let $arg = __f();
                         $($Rest)*
@@ -476,7 +551,7 @@ fn handle_delegator_closure(
 
                 let tokens = quote! {
                     #var_cfgs
-                    #enum_ident::#var_ident => {
+                    #enum_path::#var_ident => {
                         let __f = #expr_without_ret;
                         let __res = __f();
                         __res $($Rest)*
@@ -485,7 +560,7 @@ fn handle_delegator_closure(
 
                 let non_receiver = quote! {
                     #var_cfgs
-                    #enum_ident::#var_ident => {
+                    #enum_path::#var_ident => {
                         let __f = #expr_without_ret;
                         let $arg = __f();
                         <#delegator_ty>::$($NonReceiverFn)*($($Args),*) $($Rest)*
@@ -554,25 +629,25 @@ fn handle_no_explicit_delegator(
     var_ty: Option<&Type>,
 ) -> Result<(TokenStream, TokenStream, TokenStream)> {
     if let Some(var_ty) = var_ty {
-        let enum_ident = &enum_def.ident;
+        let enum_path = &enum_def.path;
         let var_ident = &variant.ident;
         let var_cfgs = &variant.attrs.cfg_attrs;
 
         let closure = quote! {
             #var_cfgs
-            #enum_ident::#var_ident($arg) => { $($Rest)* }
+            #enum_path::#var_ident($arg) => { $($Rest)* }
         };
 
         let tokens = quote! {
             #var_cfgs
-            #enum_ident::#var_ident(__var) => {
+            #enum_path::#var_ident(__var) => {
                 __var $($Rest)*
             }
         };
 
         let non_receiver_case = quote! {
             #var_cfgs
-            #enum_ident::#var_ident($arg) => {
+            #enum_path::#var_ident($arg) => {
                 <#var_ty>::$($NonReceiverFn)*($($Args),*) $($Rest)*
             }
         };
@@ -624,7 +699,7 @@ fn handle_delegator_field_unnamed(
     field_ty: &Type,
     will_variant_be_generated: bool,
 ) -> (TokenStream, TokenStream, TokenStream) {
-    let enum_ident = &enum_def.ident;
+    let enum_path = &enum_def.path;
     let var_ident = &variant.ident;
     let var_cfgs = &variant.attrs.cfg_attrs;
 
@@ -641,15 +716,15 @@ fn handle_delegator_field_unnamed(
         (
             quote! {
                 #var_cfgs
-                #enum_ident::#var_ident(#var_ident(#fields $arg, ..), ..) => { $($Rest)* }
+                #enum_path::#var_ident(#var_ident(#fields $arg, ..), ..) => { $($Rest)* }
             },
             quote! {
                 #var_cfgs
-                #enum_ident::#var_ident(#var_ident(#fields __var, ..), ..) => { __var $($Rest)* }
+                #enum_path::#var_ident(#var_ident(#fields __var, ..), ..) => { __var $($Rest)* }
             },
             quote! {
                 #var_cfgs
-                #enum_ident::#var_ident(#var_ident(#fields $arg, ..), ..) => {
+                #enum_path::#var_ident(#var_ident(#fields $arg, ..), ..) => {
                     <#field_ty>::$($NonReceiverFn)*($($Args),*) $($Rest)*
                 }
             },
@@ -658,15 +733,15 @@ fn handle_delegator_field_unnamed(
         (
             quote! {
                 #var_cfgs
-                #enum_ident::#var_ident(#fields $arg, ..) => { $($Rest)* }
+                #enum_path::#var_ident(#fields $arg, ..) => { $($Rest)* }
             },
             quote! {
                 #var_cfgs
-                #enum_ident::#var_ident(#fields __var, ..) => { __var $($Rest)* }
+                #enum_path::#var_ident(#fields __var, ..) => { __var $($Rest)* }
             },
             quote! {
                 #var_cfgs
-                #enum_ident::#var_ident(#fields $arg, ..) => {
+                #enum_path::#var_ident(#fields $arg, ..) => {
                     <#field_ty>::$($NonReceiverFn)*($($Args),*) $($Rest)*
                 }
             },
@@ -681,7 +756,7 @@ fn handle_delegator_field_named(
     field_ty: &Type,
     will_variant_be_generated: bool,
 ) -> (TokenStream, TokenStream, TokenStream) {
-    let enum_ident = &enum_def.ident;
+    let enum_path = &enum_def.path;
     let var_ident = &variant.ident;
     let var_cfgs = &variant.attrs.cfg_attrs;
 
@@ -689,15 +764,15 @@ fn handle_delegator_field_named(
         (
             quote! {
                 #var_cfgs
-                #enum_ident::#var_ident(#var_ident { #field_ident: $arg, .. }) => { $($Rest)* }
+                #enum_path::#var_ident(#var_ident { #field_ident: $arg, .. }) => { $($Rest)* }
             },
             quote! {
                 #var_cfgs
-                #enum_ident::#var_ident(#var_ident { #field_ident, .. }) => { #field_ident $($Rest)* }
+                #enum_path::#var_ident(#var_ident { #field_ident, .. }) => { #field_ident $($Rest)* }
             },
             quote! {
                 #var_cfgs
-                #enum_ident::#var_ident(#var_ident { #field_ident: $arg, .. }) => {
+                #enum_path::#var_ident(#var_ident { #field_ident: $arg, .. }) => {
                     <#field_ty>::$($NonReceiverFn)*($($Args),*) $($Rest)*
                 }
             },
@@ -706,15 +781,15 @@ fn handle_delegator_field_named(
         (
             quote! {
                 #var_cfgs
-                #enum_ident::#var_ident { #field_ident: $arg, .. } => { $($Rest)* }
+                #enum_path::#var_ident { #field_ident: $arg, .. } => { $($Rest)* }
             },
             quote! {
                 #var_cfgs
-                #enum_ident::#var_ident { #field_ident, .. } => { #field_ident $($Rest)* }
+                #enum_path::#var_ident { #field_ident, .. } => { #field_ident $($Rest)* }
             },
             quote! {
                 #var_cfgs
-                #enum_ident::#var_ident { #field_ident: $arg, .. } => {
+                #enum_path::#var_ident { #field_ident: $arg, .. } => {
                     <#field_ty>::$($NonReceiverFn)*($($Args),*) $($Rest)*
                 }
             },
@@ -0,0 +1,258 @@
+use proc_macro2::Literal;
+
+use super::*;
+
+/// Generates the `#[repr(C)]` mirror of the enum (setting `ffi_mirror(name = Name)`):
+/// - `[Name]Tag`: a fieldless `#[repr(C)]` enum with one variant per variant of the enum.
+/// - `[Name]Payload`: a `#[repr(C)]` union with one field per variant that has a payload.
+/// - `Name`: a `#[repr(C)]` struct with the tag (as a `u32`) and the payload.
+///
+/// Along with `From<Enum> for Name`, `Name::into_enum` and `Name::read_enum`, the latter is used by the
+/// `extern "C"` shims generated by `#[delegate_impl(ffi_mirror = Name)]`.
+///
+/// Converting back to the enum is `unsafe`, since the fields of the mirror are public: safe code may pair a valid
+/// tag with a payload of another variant.
+///
+/// The tag of each variant is its id, if the variants have ids (`variant_ids`, see [`sanitize_variant_ids`]),
+/// otherwise it is the variant's position in the declaration order (including the variants disabled by `#[cfg]`
+/// attributes).
+///
+/// # Errors
+/// - If the enum has generics.
+/// - If a variant has fields, but is not extracted (setting `extract_variants`) and doesn't have exactly one
+///   unnamed field.
+///
+/// [`sanitize_variant_ids`]: super::variant_ids::sanitize_variant_ids
+pub(super) fn generate_ffi_mirror(
+    enum_def: &SaneEnum,
+    settings: &Settings,
+    mirror: &SaneSettingFfiMirror,
+    variant_ids: Option<&[u32]>,
+) -> Result<TokenStream> {
+    if enum_def.generics.is_some() {
+        bail!(enum_def.ident => "`ffi_mirror` cannot be used on enums with generics, \
+            since the `extern \"C\"` shims cannot be generic");
+    }
+
+    let enum_path = &enum_def.path;
+    let enum_ident = &enum_def.ident;
+    let enum_name = enum_ident.to_string();
+    let vis = &enum_def.vis;
+
+    let ffi_ident = &mirror.name;
+    let tag_ident = format_ident!("{ffi_ident}Tag");
+    let payload_ident = format_ident!("{ffi_ident}Payload");
+
+    let tags = match variant_ids {
+        Some(ids) => ids.iter().map(|id| Literal::u32_unsuffixed(*id)).collect(),
+        None => {
+            (0..enum_def.variants.len())
+                .map(Literal::usize_unsuffixed)
+                .collect::<Vec<_>>()
+        }
+    };
+
+    let mut tag_variants = Vec::new();
+    let mut payload_fields = Vec::new();
+    let mut from_arms = Vec::new();
+    let mut read_arms = Vec::new();
+
+    for (var, tag) in enum_def.variants.iter().zip(&tags) {
+        let var_ident = &var.ident;
+        let var_cfgs = &var.attrs.cfg_attrs;
+        let extracted = settings.extract_variants.is_some() && var.allow_extract();
+
+        tag_variants.push(quote! {
+            #var_cfgs
+            #var_ident = #tag
+        });
+
+        let payload_ty = match &var.fields {
+            _ if extracted => Some(var_ident.to_token_stream()),
+            SaneVarFields::Unnamed(unnamed) if unnamed.fields.len() == 1 => {
+                unnamed
+                    .fields
+                    .first()
+                    .map(|field| field.ty.to_token_stream())
+            }
+            SaneVarFields::Unit => None,
+            SaneVarFields::Named(_) | SaneVarFields::Unnamed(_) => {
+                bail!(var_ident => "`ffi_mirror` requires each variant to be a unit variant, \
+                    or to have exactly one unnamed field.\n\
+                    Help: Move the fields into a `#[repr(C)]` struct, or extract the variants with the setting \
+                    `extract_variants(attrs(repr(C)))`");
+            }
+        };
+
+        let Some(payload_ty) = payload_ty else {
+            from_arms.push(quote! {
+                #var_cfgs
+                #enum_path::#var_ident => Self {
+                    tag: #tag_ident::#var_ident as u32,
+                    payload: #payload_ident { __unit: () },
+                },
+            });
+
+            read_arms.push(quote! {
+                #var_cfgs
+                ::core::option::Option::Some(#tag_ident::#var_ident) => #enum_path::#var_ident,
+            });
+
+            continue;
+        };
+
+        let field_ident = payload_field_ident(var_ident);
+
+        payload_fields.push(quote! {
+            #var_cfgs
+            pub #field_ident: ::core::mem::ManuallyDrop<#payload_ty>,
+        });
+
+        from_arms.push(quote! {
+            #var_cfgs
+            #enum_path::#var_ident(__payload) => Self {
+                tag: #tag_ident::#var_ident as u32,
+                payload: #payload_ident {
+                    #field_ident: ::core::mem::ManuallyDrop::new(__payload),
+                },
+            },
+        });
+
+        read_arms.push(quote! {
+            #var_cfgs
+            ::core::option::Option::Some(#tag_ident::#var_ident) => {
+                #enum_path::#var_ident(unsafe { ::core::ptr::read(&*this.payload.#field_ident) })
+            }
+        });
+    }
+
+    let tag_checks = enum_def.variants.iter().map(|var| {
+        let var_ident = &var.ident;
+        let var_cfgs = &var.attrs.cfg_attrs;
+
+        quote! {
+            #var_cfgs
+            {
+                if self.tag == #tag_ident::#var_ident as u32 {
+                    return ::core::option::Option::Some(#tag_ident::#var_ident);
+                }
+            }
+        }
+    });
+
+    let docs_tag = docs_tokens(format!(
+        "The tags of [`{ffi_ident}`], one for each variant of [`{enum_ident}`].\n\n\
+         This enum was generated by the setting `ffi_mirror` of [`delegated_enum`](spire_enum_macros::delegated_enum)."
+    ));
+
+    let docs_payload = docs_tokens(format!(
+        "The payloads of [`{ffi_ident}`], the active field is determined by [`{ffi_ident}::tag`].\n\n\
+         Unit variants use the field `__unit`."
+    ));
+
+    let docs_ffi = docs_tokens(format!(
+        "`#[repr(C)]` mirror of [`{enum_ident}`], meant to be passed across FFI boundaries.\n\n\
+         Convert from [`{enum_ident}`] with [`From`], and back with the unsafe [`into_enum`]({ffi_ident}::into_enum), \
+         which validates the tag, but cannot validate the payload.\n\n\
+         The payload is never dropped: dropping a value of this type leaks the payload, \
+         convert it back to [`{enum_ident}`] to drop it.\n\n\
+         This struct was generated by the setting `ffi_mirror` of [`delegated_enum`](spire_enum_macros::delegated_enum)."
+    ));
+
+    let docs_tag_field = docs_tokens(format!(
+        "The discriminant of one of the variants of [`{tag_ident}`], which determines the active field of `payload`."
+    ));
+
+    let docs_read_enum = docs_tokens(format!(
+        "Returns a bitwise copy of the [`{enum_ident}`] that `this` mirrors, without validating or consuming `*this`.\n\n\
+         # Safety\n\
+         - `this` must be valid for reads.\n\
+         - The active field of the payload must match the tag.\n\
+         - The copy shares ownership of the payload with `*this`, at most one of them may be dropped or used mutably \
+           (e.g. wrap the copy in [`ManuallyDrop`](::core::mem::ManuallyDrop)).\n\n\
+         # Errors\n\
+         If the tag does not belong to any variant of [`{enum_ident}`]."
+    ));
+
+    let docs_into_enum = docs_tokens(format!(
+        "Converts the mirror back into the [`{enum_ident}`] it mirrors, moving the payload.\n\n\
+         # Safety\n\
+         The active field of the payload must match the tag, which is always the case when `self` was created with \
+         [`From`].\n\n\
+         # Errors\n\
+         If the tag does not belong to any variant of [`{enum_ident}`]."
+    ));
+
+    Ok(quote! {
+        #docs_tag
+        #[repr(C)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis enum #tag_ident {
+            #( #tag_variants ),*
+        }
+
+        #docs_payload
+        #[repr(C)]
+        #vis union #payload_ident {
+            #( #payload_fields )*
+            pub __unit: (),
+        }
+
+        #docs_ffi
+        #[repr(C)]
+        #vis struct #ffi_ident {
+            #docs_tag_field
+            pub tag: u32,
+            pub payload: #payload_ident,
+        }
+
+        #[allow(unused)]
+        impl #ffi_ident {
+            #[doc = "Returns the variant of the tag, or `None` if the tag does not belong to any variant."]
+            pub const fn variant_tag(&self) -> ::core::option::Option<#tag_ident> {
+                #( #tag_checks )*
+
+                ::core::option::Option::None
+            }
+
+            #docs_read_enum
+            pub unsafe fn read_enum(
+                this: *const Self,
+            ) -> ::core::result::Result<#enum_ident, ::spire_enum::prelude::InvalidRepr<u32>> {
+                let this = unsafe { &*this };
+
+                ::core::result::Result::Ok(match this.variant_tag() {
+                    #( #read_arms )*
+                    ::core::option::Option::None => {
+                        return ::core::result::Result::Err(::spire_enum::prelude::InvalidRepr::new(#enum_name, this.tag));
+                    }
+                })
+            }
+
+            #docs_into_enum
+            pub unsafe fn into_enum(self) -> ::core::result::Result<#enum_ident, ::spire_enum::prelude::InvalidRepr<u32>> {
+                // `self` is not dropped, so the payload is moved into the copy.
+                unsafe { Self::read_enum(&self) }
+            }
+        }
+
+        impl ::core::convert::From<#enum_ident> for #ffi_ident {
+            fn from(value: #enum_ident) -> Self {
+                match value {
+                    #( #from_arms )*
+                }
+            }
+        }
+    })
+}
+
+/// The name of the union field that holds the payload of the variant `var_ident`, in snake case.
+fn payload_field_ident(var_ident: &Ident) -> Ident {
+    let name = var_ident.to_string().to_case(Case::Snake);
+
+    match syn::parse_str::<Ident>(&name) {
+        Ok(ident) => ident,
+        // Keywords, such as `type`.
+        Err(_) => Ident::new_raw(&name, Span::call_site()),
+    }
+}
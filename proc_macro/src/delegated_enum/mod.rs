@@ -1,12 +1,19 @@
+mod arbitrary;
 mod conversions;
 mod enum_;
+mod ffi_mirror;
 mod settings;
+mod state_machine;
+mod union;
 mod variant;
 mod variant_fields;
+mod variant_ids;
+mod wire;
 
 use conversions::*;
 use enum_::SaneEnum;
 use settings::*;
+pub use union::run_union;
 use variant::*;
 use variant_fields::*;
 
@@ -16,3 +23,57 @@ pub fn run(settings_stream: TokenStream1, enum_stream: TokenStream1) -> Result<T
     let settings = parse_settings(settings_stream.into())?;
     enum_::run(enum_stream, settings)
 }
+
+/// Function-like form, for enums defined elsewhere, see [`ForeignEnum`].
+///
+/// The restated variants may contain the same attributes accepted by [`delegated_enum`](crate::delegated_enum)
+/// (e.g. `#[delegator]`, `#[delegate_via(..)]`, `#[dont_impl_conversions]`).
+pub fn run_foreign(input_stream: TokenStream1) -> Result<TokenStream> {
+    let ForeignEnum {
+        path,
+        input_enum,
+        settings,
+    } = syn::parse::<ForeignEnum<Meta<VarMeta>, Meta<kw_delegator>>>(input_stream)?;
+
+    let settings = parse_settings(settings)?;
+
+    if let _Some(extract_variants) = &settings.extract_variants {
+        bail!(extract_variants.kw => "The variants of foreign enums cannot be extracted, \
+            since the enum's definition can't be changed.\n\
+            Help: Apply `extract_variants` to the enum's definition instead.");
+    }
+
+    if let _Some(wire) = &settings.wire {
+        bail!(wire => "The wire format cannot be implemented for foreign enums, \
+            since the orphan rules forbid implementing `WireEncode` and `WireDecode` for types of other crates.\n\
+            Help: Apply `wire` to the enum's definition instead.");
+    }
+
+    if let _Some(ffi_mirror) = &settings.ffi_mirror {
+        bail!(ffi_mirror.kw => "The FFI mirror cannot be generated for foreign enums, \
+            since the orphan rules forbid adding methods to types of other crates.\n\
+            Help: Apply `ffi_mirror` to the enum's definition instead.");
+    }
+
+    match (&settings.arbitrary, &settings.proptest) {
+        (_Some(kw), _) => {
+            bail!(kw => "`Arbitrary` cannot be implemented for foreign enums, \
+                since the orphan rules forbid implementing foreign traits for types of other crates.\n\
+                Help: Apply `arbitrary` to the enum's definition instead.");
+        }
+        (_, _Some(kw)) => {
+            bail!(kw => "`Arbitrary` cannot be implemented for foreign enums, \
+                since the orphan rules forbid implementing foreign traits for types of other crates.\n\
+                Help: Apply `proptest` to the enum's definition instead.");
+        }
+        _ => {}
+    }
+
+    if let _Some(state_machine) = &settings.state_machine {
+        bail!(state_machine.kw => "`StateMachine` cannot be implemented for foreign enums, \
+            since the orphan rules forbid implementing foreign traits for types of other crates.\n\
+            Help: Apply `state_machine` to the enum's definition instead.");
+    }
+
+    enum_::tokenize(input_enum, settings, Some(path))
+}
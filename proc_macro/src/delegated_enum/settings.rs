@@ -9,6 +9,13 @@ mod kw {
     custom_keyword!(impl_enum_try_into_variants);
     custom_keyword!(impl_variants_into_enum);
     custom_keyword!(impl_conversions);
+    custom_keyword!(wire);
+    custom_keyword!(ffi_mirror);
+    custom_keyword!(name);
+    custom_keyword!(arbitrary);
+    custom_keyword!(proptest);
+    custom_keyword!(state_machine);
+    custom_keyword!(context);
 }
 
 #[derive(Default)]
@@ -17,6 +24,11 @@ pub struct Settings {
     enum_try_into_variants: Optional<kw::impl_enum_try_into_variants>,
     variants_into_enum: Optional<kw::impl_variants_into_enum>,
     conversions: Optional<kw::impl_conversions>,
+    pub wire: Optional<kw::wire>,
+    pub ffi_mirror: Optional<SaneSettingFfiMirror>,
+    pub arbitrary: Optional<kw::arbitrary>,
+    pub proptest: Optional<kw::proptest>,
+    pub state_machine: Optional<SaneSettingStateMachine>,
 }
 
 impl Settings {
@@ -48,6 +60,34 @@ pub fn parse_settings(input_stream: TokenStream) -> Result<Settings> {
             Setting::ImplConversions(kw) => {
                 assign_unique_or_panic!(sane_settings.conversions, kw)
             }
+            Setting::Wire(kw) => assign_unique_or_panic!(sane_settings.wire, kw),
+            Setting::FfiMirror(ffi_mirror) => {
+                let sane_ffi_mirror = sanitize_ffi_mirror(ffi_mirror)?;
+                assign_unique_or_panic!(sane_settings.ffi_mirror, sane_ffi_mirror);
+            }
+            Setting::Arbitrary(kw) => {
+                if !cfg!(feature = "arbitrary") {
+                    bail!(kw => "`arbitrary` requires the feature `arbitrary` of `spire_enum`.\n\
+                        Help: Enable it in your `Cargo.toml`: `spire_enum = { version = \"..\", features = [\"arbitrary\"] }`");
+                }
+
+                assign_unique_or_panic!(sane_settings.arbitrary, kw)
+            }
+            Setting::StateMachine(SettingStateMachine { kw, args }) => {
+                let sane_state_machine = SaneSettingStateMachine {
+                    kw,
+                    context: args.into_inner().ty,
+                };
+                assign_unique_or_panic!(sane_settings.state_machine, sane_state_machine);
+            }
+            Setting::Proptest(kw) => {
+                if !cfg!(feature = "proptest") {
+                    bail!(kw => "`proptest` requires the feature `proptest` of `spire_enum`.\n\
+                        Help: Enable it in your `Cargo.toml`: `spire_enum = { version = \"..\", features = [\"proptest\"] }`");
+                }
+
+                assign_unique_or_panic!(sane_settings.proptest, kw)
+            }
         }
     }
 
@@ -71,6 +111,11 @@ enum Setting {
     ImplVariantsTryFromEnum(kw::impl_enum_try_into_variants),
     ImplEnumFromVars(kw::impl_variants_into_enum),
     ImplConversions(kw::impl_conversions),
+    Wire(kw::wire),
+    FfiMirror(SettingFfiMirror),
+    Arbitrary(kw::arbitrary),
+    Proptest(kw::proptest),
+    StateMachine(SettingStateMachine),
 }
 
 impl Parse for Setting {
@@ -91,9 +136,19 @@ impl Parse for Setting {
             Ok(Setting::ImplEnumFromVars(kw))
         } else if let Ok(kw) = input.parse::<kw::impl_conversions>() {
             Ok(Setting::ImplConversions(kw))
+        } else if let Ok(kw) = input.parse::<kw::wire>() {
+            Ok(Setting::Wire(kw))
+        } else if input.peek(kw::ffi_mirror) {
+            Ok(Setting::FfiMirror(input.parse()?))
+        } else if let Ok(kw) = input.parse::<kw::arbitrary>() {
+            Ok(Setting::Arbitrary(kw))
+        } else if let Ok(kw) = input.parse::<kw::proptest>() {
+            Ok(Setting::Proptest(kw))
+        } else if input.peek(kw::state_machine) {
+            Ok(Setting::StateMachine(input.parse()?))
         } else {
             Err(input.error(
-                "Expected one of `extract_variants`, `impl_enum_try_into_variants`, `impl_variants_into_enum`, or `impl_conversions`."
+                "Expected one of `extract_variants`, `impl_enum_try_into_variants`, `impl_variants_into_enum`, `impl_conversions`, `wire`, `ffi_mirror`, `arbitrary`, `proptest`, or `state_machine`."
             ))
         }
     }
@@ -207,3 +262,63 @@ fn sanitize_extract_variants(input: SettingExtractVariants) -> Result<SaneSettin
         enum_derives,
     })
 }
+
+#[derive(Parse, ToTokens)]
+struct SettingFfiMirror {
+    kw:   kw::ffi_mirror,
+    args: Paren<FfiMirrorName>,
+}
+
+#[derive(Parse, ToTokens)]
+struct FfiMirrorName {
+    kw: kw::name,
+    eq_token: Token![=],
+    ident: Ident,
+}
+
+pub struct SaneSettingFfiMirror {
+    pub kw:   kw::ffi_mirror,
+    /// The name of the generated `#[repr(C)]` struct.
+    pub name: Ident,
+}
+
+impl SaneSettingFfiMirror {
+    pub fn span(&self) -> Span { self.kw.span() }
+}
+
+fn sanitize_ffi_mirror(input: SettingFfiMirror) -> Result<SaneSettingFfiMirror> {
+    let SettingFfiMirror { kw, args } = input;
+
+    if !cfg!(feature = "ffi") {
+        bail!(kw => "`ffi_mirror` generates unsafe code, which requires the feature `ffi` of `spire_enum`.\n\
+            Help: Enable it in your `Cargo.toml`: `spire_enum = { version = \"..\", features = [\"ffi\"] }`");
+    }
+
+    Ok(SaneSettingFfiMirror {
+        kw,
+        name: args.into_inner().ident,
+    })
+}
+
+#[derive(Parse, ToTokens)]
+struct SettingStateMachine {
+    kw:   kw::state_machine,
+    args: Paren<StateMachineContext>,
+}
+
+#[derive(Parse, ToTokens)]
+struct StateMachineContext {
+    kw: kw::context,
+    eq_token: Token![=],
+    ty: Box<Type>,
+}
+
+pub struct SaneSettingStateMachine {
+    pub kw: kw::state_machine,
+    /// The type passed to the hooks of `StateHooks`.
+    pub context: Box<Type>,
+}
+
+impl SaneSettingStateMachine {
+    pub fn span(&self) -> Span { self.kw.span() }
+}
@@ -0,0 +1,125 @@
+use super::*;
+
+/// Generates the items of the setting `state_machine(context = Type)`:
+/// - `[EnumName]StateKind`: a fieldless enum with one variant per variant of the enum.
+/// - The implementation of `StateMachine`, whose transition graph is declared by the variant attributes
+///   `#[transitions(..)]`.
+///
+/// Variants without `#[transitions(..)]` may transition into any variant, `#[transitions()]` declares a final state.
+///
+/// # Errors
+/// - If `#[transitions(..)]` is used without the setting.
+/// - If `#[transitions(..)]` lists a variant that does not exist, or lists the same variant twice.
+pub(super) fn generate_state_machine(
+    enum_def: &SaneEnum,
+    settings: &Settings,
+) -> Result<TokenStream> {
+    let _Some(SaneSettingStateMachine { context, .. }) = &settings.state_machine else {
+        let first_kw = enum_def
+            .variants
+            .iter()
+            .find_map(|var| var.attrs.transitions.as_ref().map(|(kw, _)| kw));
+
+        if let Some(kw) = first_kw {
+            bail!(kw => "`#[transitions(..)]` requires the setting `state_machine(context = Type)`");
+        }
+
+        return Ok(TokenStream::new());
+    };
+
+    let enum_ident = &enum_def.ident;
+    let enum_path = &enum_def.path;
+    let enum_ty = &enum_def.ty;
+    let vis = &enum_def.vis;
+    let kind_ident = format_ident!("{enum_ident}StateKind");
+
+    let (gen_params, where_clause) =
+        (enum_def.generics.stream_params(), enum_def.generics.as_pair().1);
+
+    let var_idents = enum_def
+        .variants
+        .iter()
+        .map(|var| &var.ident)
+        .collect::<Vec<_>>();
+    let var_cfgs = enum_def
+        .variants
+        .iter()
+        .map(|var| &var.attrs.cfg_attrs)
+        .collect::<Vec<_>>();
+
+    let mut transition_arms = Vec::new();
+
+    for var in &enum_def.variants {
+        let var_ident = &var.ident;
+        let var_cfgs = &var.attrs.cfg_attrs;
+
+        let _Some((_, targets)) = &var.attrs.transitions else {
+            transition_arms.push(quote! {
+                #var_cfgs
+                (#kind_ident::#var_ident, _) => true,
+            });
+
+            continue;
+        };
+
+        for (idx, target) in targets.iter().enumerate() {
+            if let Some(first) = targets[..idx].iter().find(|other| *other == target) {
+                bail!(first => "duplicate transition..", target => "..found here");
+            }
+
+            let Some(target_var) = enum_def
+                .variants
+                .iter()
+                .find(|other| other.ident == *target)
+            else {
+                bail!(target => format!("`{enum_ident}` has no variant named `{target}`"));
+            };
+
+            let target_cfgs = &target_var.attrs.cfg_attrs;
+
+            transition_arms.push(quote! {
+                #var_cfgs
+                #target_cfgs
+                (#kind_ident::#var_ident, #kind_ident::#target) => true,
+            });
+        }
+    }
+
+    let docs_kind = docs_tokens(format!(
+        "The kinds of the states of [`{enum_ident}`], used to describe its transitions.\n\n\
+         This enum was generated by the setting `state_machine` of [`delegated_enum`](spire_enum_macros::delegated_enum)."
+    ));
+
+    Ok(quote! {
+        #docs_kind
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis enum #kind_ident {
+            #(
+                #var_cfgs
+                #var_idents
+            ),*
+        }
+
+        impl #gen_params ::spire_enum::prelude::StateMachine for #enum_ty #where_clause {
+            type Context = #context;
+            type Kind = #kind_ident;
+
+            fn kind(&self) -> Self::Kind {
+                match self {
+                    #(
+                        #var_cfgs
+                        #enum_path::#var_idents { .. } => #kind_ident::#var_idents,
+                    )*
+                }
+            }
+
+            fn can_transition(from: Self::Kind, to: Self::Kind) -> bool {
+                match (from, to) {
+                    #(#transition_arms)*
+                    #[allow(unreachable_patterns)]
+                    _ => false,
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,125 @@
+use super::*;
+
+/// Function-like shorthand of [`delegated_enum`](crate::delegated_enum), see [`UnionEnum`].
+pub fn run_union(input_stream: TokenStream1) -> Result<TokenStream> {
+    let UnionEnum {
+        attrs,
+        vis,
+        enum_token,
+        ident,
+        generics,
+        variants,
+        settings,
+    } = syn::parse::<UnionEnum>(input_stream)?;
+
+    let mut var_idents = Vec::<&Ident>::with_capacity(variants.len());
+
+    for UnionVariant { ty, .. } in &variants {
+        let var_ident = match ty {
+            Type::Path(TypePath { qself: None, path }) => {
+                path.segments.last().map(|seg| &seg.ident)
+            }
+            _ => None,
+        };
+
+        let Some(var_ident) = var_ident else {
+            bail!(ty => "expected the path of a type, the variant's name is derived from its last segment");
+        };
+
+        if let Some(first) = var_idents.iter().find(|other| **other == var_ident) {
+            bail!(first => format!("duplicate variant name `{var_ident}`.."), var_ident => "..found here");
+        }
+
+        var_idents.push(var_ident);
+    }
+
+    let var_attrs = variants.iter().map(|var| &var.attrs);
+    let var_tys = variants.iter().map(|var| &var.ty);
+    let where_clause = &generics.where_clause;
+
+    let input_enum = syn::parse2(quote! {
+        #(#attrs)*
+        #vis #enum_token #ident #generics #where_clause {
+            #(
+                #(#var_attrs)*
+                #var_idents(#var_tys)
+            ),*
+        }
+    })?;
+
+    let settings = parse_settings(settings)?;
+    enum_::tokenize(input_enum, settings, None)
+}
+
+/// The input of `union!`, which declares a delegated enum with one single-field variant per type.
+///
+/// Syntax: `#[attrs] vis enum Name<Generics> = TypeA | TypeB<T>; settings(..)`,
+/// each type may be preceded by the variant attributes accepted by [`delegated_enum`](crate::delegated_enum).
+struct UnionEnum {
+    attrs: Vec<SynAttribute>,
+    vis: Visibility,
+    enum_token: Token![enum],
+    ident: Ident,
+    generics: syn::Generics,
+    variants: Vec<UnionVariant>,
+    settings: TokenStream,
+}
+
+struct UnionVariant {
+    attrs: Vec<SynAttribute>,
+    ty: Type,
+}
+
+mod kw {
+    use super::*;
+    custom_keyword!(settings);
+}
+
+impl Parse for UnionEnum {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(SynAttribute::parse_outer)?;
+        let vis = input.parse()?;
+        let enum_token = input.parse()?;
+        let ident = input.parse()?;
+        let mut generics = input.parse::<syn::Generics>()?;
+        generics.where_clause = input.parse()?;
+        input.parse::<Token![=]>()?;
+
+        let mut variants = Vec::new();
+
+        loop {
+            variants.push(UnionVariant {
+                attrs: input.call(SynAttribute::parse_outer)?,
+                ty: input.parse()?,
+            });
+
+            if input.parse::<Option<Token![|]>>()?.is_none() {
+                break;
+            }
+        }
+
+        let mut settings = TokenStream::new();
+
+        if input.parse::<Option<Token![;]>>()?.is_some() && input.peek(kw::settings) {
+            input.parse::<kw::settings>()?;
+            let content;
+            syn::parenthesized!(content in input);
+            settings = content.parse()?;
+            input.parse::<Option<Token![;]>>()?;
+        }
+
+        if !input.is_empty() {
+            return Err(input.error("expected `|`, `;` or `settings(..)`"));
+        }
+
+        Ok(UnionEnum {
+            attrs,
+            vis,
+            enum_token,
+            ident,
+            generics,
+            variants,
+            settings,
+        })
+    }
+}
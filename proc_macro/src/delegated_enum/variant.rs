@@ -3,12 +3,19 @@ mod var_kw {
     custom_keyword!(dont_extract);
     custom_keyword!(dont_impl_conversions);
     custom_keyword!(delegate_via);
+    custom_keyword!(variant_id);
+    custom_keyword!(former_names);
+    custom_keyword!(weight);
+    custom_keyword!(arbitrary);
+    custom_keyword!(skip);
+    custom_keyword!(transitions);
 }
 
 use syn::parse2;
 use var_kw::{
-    delegate_via as kw_delegate_via, dont_extract as kw_dont_extract,
-    dont_impl_conversions as kw_dont_impl_conversions,
+    arbitrary as kw_arbitrary, delegate_via as kw_delegate_via, dont_extract as kw_dont_extract,
+    dont_impl_conversions as kw_dont_impl_conversions, former_names as kw_former_names,
+    skip as kw_skip, transitions as kw_transitions, variant_id as kw_variant_id, weight as kw_weight,
 };
 
 use super::*;
@@ -18,6 +25,11 @@ pub enum VarMeta {
     NoVarType(kw_dont_extract),
     NoConversions(kw_dont_impl_conversions),
     DelegateVia(kw_delegate_via, Paren<TokenStream>),
+    VariantId(kw_variant_id, Token![=], syn::LitInt),
+    FormerNames(kw_former_names, Paren<InputPunctuated<syn::LitStr, Token![,]>>),
+    Weight(kw_weight, Paren<syn::LitInt>),
+    Arbitrary(kw_arbitrary, Paren<kw_skip>),
+    Transitions(kw_transitions, Paren<InputPunctuated<Ident, Token![,]>>),
 }
 
 pub(super) fn generate_variant_type_definition(
@@ -34,6 +46,11 @@ pub(super) fn generate_variant_type_definition(
                 no_var_type: _,
                 no_convert: _,
                 delegate_via: _,
+                variant_id: _,
+                former_names: _,
+                weight: _,
+                arbitrary_skip: _,
+                transitions: _,
             },
         ident: var_ident,
         fields,
@@ -44,6 +61,11 @@ pub(super) fn generate_variant_type_definition(
 
     let vis = &enum_def.vis;
 
+    // `#[table_skip]` is a helper attribute of the table macros, it is only meaningful on the enum's variant.
+    let var_syn_attrs = var_syn_attrs
+        .iter()
+        .filter(|attr| !tables::is_table_skip_attr(attr));
+
     let (generics, where_clause) = generics.as_pair();
 
     let fields_and_where_clause = match fields {
@@ -74,7 +96,7 @@ pub(super) fn generate_variant_type_definition(
     quote! {
         #var_cfg_attrs
         #(#enum_derives)*
-        #var_syn_attrs
+        #(#var_syn_attrs)*
         #( #[#extra_attrs] )*
         #vis struct #var_ident #generics #fields_and_where_clause
     }
@@ -92,6 +114,11 @@ pub(super) fn generate_enum_variant_definition(
                 no_var_type: _,
                 no_convert: _,
                 delegate_via: _,
+                variant_id: _,
+                former_names: _,
+                weight: _,
+                arbitrary_skip: _,
+                transitions: _,
             },
         ident: var_ident,
         fields,
@@ -203,6 +230,14 @@ pub struct SaneVariantAttributes {
     pub no_var_type: Optional<kw_dont_extract>,
     pub no_convert: Optional<kw_dont_impl_conversions>,
     pub delegate_via: Optional<(kw_delegate_via, Box<Paren<ExprClosure>>)>,
+    pub variant_id: Optional<(kw_variant_id, syn::LitInt)>,
+    /// The names listed in the variant's `#[former_names(..)]` attributes, along with the keyword of the first one.
+    pub former_names: Optional<(kw_former_names, Vec<syn::LitStr>)>,
+    /// The relative probability of generating the variant with the settings `arbitrary` and `proptest`.
+    pub weight: Optional<(kw_weight, syn::LitInt)>,
+    pub arbitrary_skip: Optional<kw_arbitrary>,
+    /// The variants listed in `#[transitions(..)]`, which this variant may transition into (setting `state_machine`).
+    pub transitions: Optional<(kw_transitions, Vec<Ident>)>,
 }
 
 fn sanitize_attributes(attrs: Any<Attribute<Meta<VarMeta>>>) -> Result<SaneVariantAttributes> {
@@ -230,6 +265,35 @@ fn sanitize_attributes(attrs: Any<Attribute<Meta<VarMeta>>>) -> Result<SaneVaria
                     sane.delegate_via = _Some((kw, Box::new(expr_paren)));
                 }
             }
+            VarMeta::VariantId(kw, _, id) => {
+                if let _Some((first_kw, _)) = sane.variant_id {
+                    err_expected_only_one!(first_kw, kw);
+                } else {
+                    sane.variant_id = _Some((kw, id));
+                }
+            }
+            VarMeta::FormerNames(kw, names) => {
+                let names = names.into_inner().inner;
+                match &mut sane.former_names {
+                    _Some((_, former_names)) => former_names.extend(names),
+                    _None => sane.former_names = _Some((kw, names.into_iter().collect())),
+                }
+            }
+            VarMeta::Weight(kw, weight) => {
+                if let _Some((first_kw, _)) = sane.weight {
+                    err_expected_only_one!(first_kw, kw);
+                } else {
+                    sane.weight = _Some((kw, weight.into_inner()));
+                }
+            }
+            VarMeta::Arbitrary(kw, _) => assign_unique_or_panic!(sane.arbitrary_skip, kw),
+            VarMeta::Transitions(kw, targets) => {
+                if let _Some((first_kw, _)) = sane.transitions {
+                    err_expected_only_one!(first_kw, kw);
+                } else {
+                    sane.transitions = _Some((kw, targets.into_inner().inner.into_iter().collect()));
+                }
+            }
         }
     }
 
@@ -0,0 +1,240 @@
+use proc_macro2::Literal;
+use quote::quote_spanned;
+
+use super::*;
+
+/// Returns the id of each variant, declared with the attribute `#[variant_id = N]`,
+/// or `None` if none of the variants declares one.
+///
+/// Two variants may share an id (or a name) if their `#[cfg]` attributes differ, since they might never be enabled
+/// together, [`generate_variant_id_impls`] then emits an error for the configurations that enable both.
+///
+/// # Errors
+/// - If some variants declare an id but others don't.
+/// - If two variants with the same `#[cfg]` attributes have the same id, or if a name (current or former) refers to
+///   two of them.
+/// - If a variant has `#[former_names(..)]`, but the enum has no ids.
+pub(super) fn sanitize_variant_ids(enum_def: &SaneEnum) -> Result<Option<Vec<u32>>> {
+    let Some(first_with_id) = enum_def
+        .variants
+        .iter()
+        .find(|var| var.attrs.variant_id.is_some())
+    else {
+        for var in &enum_def.variants {
+            if let _Some((kw, _)) = &var.attrs.former_names {
+                bail!(kw => "`#[former_names(..)]` requires the variants to have stable ids.\n\
+                    Help: Add `#[variant_id = N]` to each variant");
+            }
+        }
+
+        return Ok(None);
+    };
+
+    let mut ids = Vec::<(u32, &syn::LitInt, &SaneVar)>::new();
+    let mut names = Vec::<(String, Span, &SaneVar)>::new();
+
+    for var in &enum_def.variants {
+        let _Some((_, lit)) = &var.attrs.variant_id else {
+            bail!(var.ident => format!(
+                "variant `{}` has no id, but variant `{}` does.\n\
+                 Help: When any variant has `#[variant_id = N]`, all variants must have one",
+                var.ident, first_with_id.ident
+            ));
+        };
+
+        let id = lit.base10_parse::<u32>()?;
+
+        if let Some((_, prev_lit, prev_var)) = ids
+            .iter()
+            .find(|(prev_id, _, prev_var)| *prev_id == id && same_cfgs(prev_var, var))
+        {
+            let prev_ident = &prev_var.ident;
+            bail!(
+                lit => format!("variants `{prev_ident}` and `{}` have the same id `{id}`, variant ids must be unique", var.ident),
+                prev_lit => format!("id of `{prev_ident}` declared here"),
+            );
+        }
+
+        ids.push((id, lit, var));
+
+        for (name, span) in var_names(var) {
+            if let Some((_, prev_span, prev_var)) = names
+                .iter()
+                .find(|(prev_name, _, prev_var)| *prev_name == name && same_cfgs(prev_var, var))
+            {
+                let mut err = Error::new(
+                    span,
+                    format!(
+                        "the name `{name}` refers to both variants `{}` and `{}`, \
+                         names (current or former) must be unique",
+                        prev_var.ident, var.ident
+                    ),
+                );
+                err.combine(Error::new(*prev_span, "first used here"));
+                return Err(err);
+            }
+
+            names.push((name, span, var));
+        }
+    }
+
+    Ok(Some(ids.into_iter().map(|(id, ..)| id).collect()))
+}
+
+/// Returns the predicates of the `#[cfg(..)]` attributes of `var`.
+fn cfg_predicates(var: &SaneVar) -> impl Iterator<Item = &TokenStream> {
+    var.attrs.cfg_attrs.iter().map(|attr| &*attr.inner.tokens)
+}
+
+/// Whether both variants have the same `#[cfg(..)]` attributes, regardless of their order.
+fn same_cfgs(a: &SaneVar, b: &SaneVar) -> bool {
+    let sorted = |var| {
+        let mut predicates = cfg_predicates(var)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        predicates.sort();
+        predicates
+    };
+
+    sorted(a) == sorted(b)
+}
+
+/// Generates a `compile_error!` for the configurations that enable both `a` and `b`, which share an id or a name.
+///
+/// [`sanitize_variant_ids`] only lets such pairs through if their `#[cfg(..)]` attributes differ.
+fn tokenize_conflict_guard(a: &SaneVar, b: &SaneVar, span: Span, msg: String) -> TokenStream {
+    let predicates = cfg_predicates(a).chain(cfg_predicates(b));
+
+    quote_spanned! {span=>
+        #[cfg(all(#(#predicates),*))]
+        ::core::compile_error! { #msg }
+    }
+}
+
+/// Returns the current name of `var`, followed by its former names, along with their spans.
+fn var_names(var: &SaneVar) -> impl Iterator<Item = (String, Span)> + '_ {
+    let former_names = match &var.attrs.former_names {
+        _Some((_, names)) => names.as_slice(),
+        _None => &[],
+    };
+
+    std::iter::once((var.ident.to_string(), var.ident.span()))
+        .chain(former_names.iter().map(|lit| (lit.value(), lit.span())))
+}
+
+/// Generates `variant_id`, `variant_id_from_name` and `variant_name_from_id`.
+///
+/// Also generates the errors for the variants that share an id or a name, when the enabled features include both.
+pub(super) fn generate_variant_id_impls(enum_def: &SaneEnum, ids: &[u32]) -> TokenStream {
+    let enum_path = &enum_def.path;
+    let enum_ty = &enum_def.ty;
+    let gen_params = enum_def.generics.stream_params();
+    let where_clause = enum_def.generics.as_pair().1;
+
+    let var_idents = enum_def
+        .variants
+        .iter()
+        .map(|var| &var.ident)
+        .collect::<Vec<_>>();
+    let var_cfgs = enum_def
+        .variants
+        .iter()
+        .map(|var| &var.attrs.cfg_attrs)
+        .collect::<Vec<_>>();
+
+    let current_names = enum_def
+        .variants
+        .iter()
+        .map(|var| var.ident.to_string())
+        .collect::<Vec<_>>();
+
+    let all_names = enum_def.variants.iter().map(|var| {
+        let names = var_names(var).map(|(name, _)| name);
+        quote! { #(#names)|* }
+    });
+
+    let mut conflict_guards = Vec::new();
+
+    for (idx, (var, id)) in enum_def.variants.iter().zip(ids).enumerate() {
+        for (prev_var, prev_id) in enum_def.variants[..idx].iter().zip(ids) {
+            if prev_id == id {
+                let span = match &var.attrs.variant_id {
+                    _Some((_, lit)) => lit.span(),
+                    _None => var.ident.span(),
+                };
+
+                conflict_guards.push(tokenize_conflict_guard(
+                    prev_var,
+                    var,
+                    span,
+                    format!(
+                        "variants `{}` and `{}` have the same id `{id}`, variant ids must be unique",
+                        prev_var.ident, var.ident
+                    ),
+                ));
+            }
+
+            for (name, span) in var_names(var) {
+                if var_names(prev_var).any(|(prev_name, _)| prev_name == name) {
+                    conflict_guards.push(tokenize_conflict_guard(
+                        prev_var,
+                        var,
+                        span,
+                        format!(
+                            "the name `{name}` refers to both variants `{}` and `{}`, \
+                             names (current or former) must be unique",
+                            prev_var.ident, var.ident
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    let ids = ids
+        .iter()
+        .map(|id| Literal::u32_suffixed(*id))
+        .collect::<Vec<_>>();
+
+    quote! {
+        #( #conflict_guards )*
+
+        #[allow(unused)]
+        impl #gen_params #enum_ty #where_clause {
+            #[doc = "Returns the stable id of this variant, declared with the attribute `#[variant_id = N]`.\n\n\
+                     Unlike the variant's position, the id does not change when variants are reordered or renamed."]
+            pub const fn variant_id(&self) -> u32 {
+                match self {
+                    #(
+                        #var_cfgs
+                        #enum_path::#var_idents { .. } => #ids,
+                    )*
+                }
+            }
+
+            #[doc = "Returns the id of the variant named `name`, which may be its current name or any of the names \
+                     listed in its attribute `#[former_names(..)]`.\n\n\
+                     Returns `None` if no variant has (or had) the name."]
+            pub fn variant_id_from_name(name: &str) -> ::core::option::Option<u32> {
+                match name {
+                    #(
+                        #var_cfgs
+                        #all_names => ::core::option::Option::Some(#ids),
+                    )*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            #[doc = "Returns the current name of the variant whose id is `id`, or `None` if no variant has the id."]
+            pub const fn variant_name_from_id(id: u32) -> ::core::option::Option<&'static str> {
+                match id {
+                    #(
+                        #var_cfgs
+                        #ids => ::core::option::Option::Some(#current_names),
+                    )*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use proc_macro2::Literal;
+
+use super::*;
+
+/// Generates the implementations of `WireEncode` and `WireDecode` for the enum (setting `wire`).
+///
+/// Each variant is encoded as its tag, followed by each of its fields in order. The tag is:
+/// - The variant's id as a `u32`, if the variants have ids (`variant_ids`, see [`sanitize_variant_ids`]).
+/// - Otherwise, the variant's position in the declaration order (including the variants disabled by `#[cfg]`
+///   attributes), as a `u8`, or as a `u16` if the enum has more than 256 variants.
+///
+/// Variants that were extracted (setting `extract_variants`) are encoded as the fields of their generated type.
+///
+/// If the variants have ids, also generates `from_variant_id`, which decodes the fields of the variant whose id is
+/// already known, `decode` reads the id and then delegates to it.
+///
+/// [`sanitize_variant_ids`]: super::variant_ids::sanitize_variant_ids
+pub(super) fn generate_wire_impls(
+    enum_def: &SaneEnum,
+    settings: &Settings,
+    variant_ids: Option<&[u32]>,
+) -> TokenStream {
+    let enum_path = &enum_def.path;
+    let enum_ty = &enum_def.ty;
+    let enum_name = enum_def.ident.to_string();
+    let gen_params = enum_def.generics.stream_params();
+
+    let (tag_ty, tags) = match variant_ids {
+        Some(ids) => (quote! { u32 }, ids.iter().map(|id| Literal::u32_suffixed(*id)).collect()),
+        None if enum_def.variants.len() > 256 => {
+            (
+                quote! { u16 },
+                (0..enum_def.variants.len())
+                    .map(|idx| Literal::u16_suffixed(idx as u16))
+                    .collect::<Vec<_>>(),
+            )
+        }
+        None => {
+            (
+                quote! { u8 },
+                (0..enum_def.variants.len())
+                    .map(|idx| Literal::u8_suffixed(idx as u8))
+                    .collect(),
+            )
+        }
+    };
+
+    let mut encode_arms = Vec::new();
+    let mut decode_branches = Vec::new();
+
+    for (var, tag) in enum_def.variants.iter().zip(&tags) {
+        let var_ident = &var.ident;
+        let var_cfgs = &var.attrs.cfg_attrs;
+        let extracted = settings.extract_variants.is_some() && var.allow_extract();
+
+        let (pattern, field_idents, construct) = match &var.fields {
+            SaneVarFields::Named(named) => {
+                let field_idents = named
+                    .fields
+                    .iter()
+                    .map(|field| field.ident.clone())
+                    .collect::<Vec<_>>();
+
+                let pattern = quote! { { #(#field_idents),* } };
+                let construct = quote! {
+                    {
+                        #( #field_idents: ::spire_enum::prelude::WireDecode::decode(__reader)? ),*
+                    }
+                };
+
+                (pattern, field_idents, construct)
+            }
+            SaneVarFields::Unnamed(unnamed) => {
+                let field_idents = (0..unnamed.fields.len())
+                    .map(|idx| format_ident!("__{idx}"))
+                    .collect::<Vec<_>>();
+
+                let decodes = field_idents
+                    .iter()
+                    .map(|_| quote! { ::spire_enum::prelude::WireDecode::decode(__reader)? });
+
+                let pattern = quote! { ( #(#field_idents),* ) };
+                let construct = quote! { ( #(#decodes),* ) };
+
+                (pattern, field_idents, construct)
+            }
+            SaneVarFields::Unit => (TokenStream::new(), Vec::new(), TokenStream::new()),
+        };
+
+        let (pattern, construct) = if extracted {
+            (
+                quote! { #enum_path::#var_ident(#var_ident #pattern) },
+                quote! { #enum_path::#var_ident(#var_ident #construct) },
+            )
+        } else {
+            (
+                quote! { #enum_path::#var_ident #pattern },
+                quote! { #enum_path::#var_ident #construct },
+            )
+        };
+
+        encode_arms.push(quote! {
+            #var_cfgs
+            #pattern => {
+                ::spire_enum::prelude::WireEncode::encode(&#tag, __writer)?;
+                #( ::spire_enum::prelude::WireEncode::encode(#field_idents, __writer)?; )*
+            }
+        });
+
+        decode_branches.push(quote! {
+            #var_cfgs
+            {
+                if __tag == #tag {
+                    return ::core::result::Result::Ok(#construct);
+                }
+            }
+        });
+    }
+
+    let encode_where = enum_def
+        .generics
+        .where_clause_with_bound(quote! { ::spire_enum::prelude::WireEncode });
+    let decode_where = enum_def
+        .generics
+        .where_clause_with_bound(quote! { ::spire_enum::prelude::WireDecode });
+
+    let (decode_impl, from_variant_id) = match variant_ids {
+        Some(_) => {
+            (
+                quote! { Self::from_variant_id(__tag, __reader) },
+                quote! {
+                    #[allow(unused)]
+                    impl #gen_params #enum_ty #decode_where {
+                        #[doc = "Decodes the fields of the variant whose id is `id`, for formats that store the id \
+                                 apart from the fields (`decode` reads the id first, then calls this).\n\n\
+                                 Returns `WireError::InvalidTag` if no variant has the id."]
+                        pub fn from_variant_id<__R: ::spire_enum::prelude::WireReader + ?Sized>(
+                            id: u32,
+                            __reader: &mut __R,
+                        ) -> ::core::result::Result<Self, ::spire_enum::prelude::WireError> {
+                            let __tag = id;
+
+                            #( #decode_branches )*
+
+                            ::core::result::Result::Err(::spire_enum::prelude::WireError::InvalidTag {
+                                type_name: #enum_name,
+                                tag: __tag,
+                            })
+                        }
+                    }
+                },
+            )
+        }
+        None => {
+            (
+                quote! {
+                    #( #decode_branches )*
+
+                    ::core::result::Result::Err(::spire_enum::prelude::WireError::InvalidTag {
+                        type_name: #enum_name,
+                        tag: ::core::convert::From::from(__tag),
+                    })
+                },
+                TokenStream::new(),
+            )
+        }
+    };
+
+    quote! {
+        #from_variant_id
+
+        impl #gen_params ::spire_enum::prelude::WireEncode for #enum_ty #encode_where {
+            fn encode<__W: ::spire_enum::prelude::WireWriter + ?Sized>(
+                &self,
+                __writer: &mut __W,
+            ) -> ::core::result::Result<(), ::spire_enum::prelude::WireError> {
+                match self {
+                    #( #encode_arms )*
+                }
+
+                ::core::result::Result::Ok(())
+            }
+        }
+
+        impl #gen_params ::spire_enum::prelude::WireDecode for #enum_ty #decode_where {
+            fn decode<__R: ::spire_enum::prelude::WireReader + ?Sized>(
+                __reader: &mut __R,
+            ) -> ::core::result::Result<Self, ::spire_enum::prelude::WireError> {
+                let __tag = <#tag_ty as ::spire_enum::prelude::WireDecode>::decode(__reader)?;
+                #decode_impl
+            }
+        }
+    }
+}
@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+
+use syn::{
+    Fields,
+    GenericArgument,
+    ItemEnum,
+    ItemImpl,
+    ItemTrait,
+    PathArguments,
+    TraitItem,
+    TraitItemFn,
+    punctuated::Punctuated,
+    token::Comma,
+    visit_mut::{self, VisitMut},
+};
+
+use super::*;
+
+/// `#[enum_dispatch]` on traits, `#[enum_dispatch(Trait, ..)]` on enums.
+pub fn run(args_stream: TokenStream1, item_stream: TokenStream1) -> Result<TokenStream> {
+    let args = syn::parse::Parser::parse(Punctuated::<Path, Comma>::parse_terminated, args_stream)?;
+
+    match syn::parse::<syn::Item>(item_stream)? {
+        syn::Item::Trait(item_trait) => run_trait(args, item_trait),
+        syn::Item::Enum(item_enum) => run_enum(args, item_enum),
+        other => {
+            bail!(other => "`#[enum_dispatch]` can only be applied to traits and enums")
+        }
+    }
+}
+
+/// Emits the trait unchanged, along with a `macro_rules!` macro which the enums invoke to implement the trait.
+///
+/// The macro is re-exported with the trait's name, so that it is imported alongside the trait.
+///
+/// If the trait lists enums (`#[enum_dispatch(Enum)]`), the trait is also implemented for those.
+fn run_trait(enums: Punctuated<Path, Comma>, item_trait: ItemTrait) -> Result<TokenStream> {
+    let signatures = dispatched_signatures(&item_trait)?;
+
+    let trait_ident = &item_trait.ident;
+    let macro_ident = enum_dispatch_macro_ident(trait_ident);
+    let unsafety = &item_trait.unsafety;
+    let (impl_generics, ty_generics, where_clause) = item_trait.generics.split_for_impl();
+
+    let mut enum_impls = Vec::with_capacity(enums.len());
+
+    for enum_path in &enums {
+        if enum_path
+            .segments
+            .iter()
+            .any(|seg| !seg.arguments.is_none())
+        {
+            bail!(enum_path => "`#[enum_dispatch(..)]` on traits does not support generic enums.\n\
+                Help: Link the trait from the enum instead: `#[enum_dispatch(Trait)]`");
+        }
+
+        enum_impls.push(quote! {
+            #[::spire_enum::prelude::delegate_impl]
+            #unsafety impl #impl_generics #trait_ident #ty_generics for #enum_path #where_clause {
+                #(#signatures)*
+            }
+        });
+    }
+
+    let macro_docs = docs_tokens(format!(
+        "Implements [`{trait_ident}`] for enums annotated with `#[enum_dispatch({trait_ident})]`.\n\n\
+         This macro was generated by an invocation of [`enum_dispatch`](spire_enum::compat::enum_dispatch)."
+    ));
+
+    Ok(quote! {
+        #item_trait
+
+        #macro_docs
+        #[allow(unused)]
+        macro_rules! #macro_ident {
+            ($($impl_tokens: tt)*) => {
+                ::spire_enum::compat::__enum_dispatch_impl! {
+                    #item_trait
+                    $($impl_tokens)*
+                }
+            };
+        }
+
+        #[allow(unused_imports)]
+        pub(crate) use #macro_ident as #trait_ident;
+
+        #(#enum_impls)*
+    })
+}
+
+/// Emits the enum with `#[delegated_enum(impl_conversions)]`, expanding unit variants `Name` into `Name(Name)`,
+/// then invokes the macro generated by `#[enum_dispatch]` on each trait.
+fn run_enum(traits: Punctuated<Path, Comma>, mut item_enum: ItemEnum) -> Result<TokenStream> {
+    for var in &mut item_enum.variants {
+        if let Some((_, discriminant)) = &var.discriminant {
+            bail!(discriminant => "`#[enum_dispatch]` does not support explicit discriminants");
+        }
+
+        if let Fields::Unit = var.fields {
+            let var_ident = &var.ident;
+            var.fields = Fields::Unnamed(parse_quote! { (#var_ident) });
+        }
+    }
+
+    let enum_ident = &item_enum.ident;
+    let (impl_generics, ty_generics, where_clause) = item_enum.generics.split_for_impl();
+
+    let mut trait_impls = Vec::with_capacity(traits.len());
+
+    for trait_path in &traits {
+        let mut macro_path = trait_path.clone();
+        let Some(last) = macro_path.segments.last_mut() else {
+            bail!(trait_path => "expected the path of a trait");
+        };
+
+        last.arguments = PathArguments::None;
+
+        trait_impls.push(quote! {
+            #macro_path! {
+                impl #impl_generics #trait_path for #enum_ident #ty_generics #where_clause {}
+            }
+        });
+    }
+
+    Ok(quote! {
+        #[::spire_enum::prelude::delegated_enum(impl_conversions)]
+        #item_enum
+
+        #(#trait_impls)*
+    })
+}
+
+/// The input of `__enum_dispatch_impl!`: the trait's definition followed by an empty impl block
+/// (`impl<Generics> Trait<Args> for Enum<Generics> {}`), filled with the trait's methods.
+///
+/// The trait's generic parameters are replaced by the arguments of the impl's trait path.
+pub fn run_impl(input_stream: TokenStream1) -> Result<TokenStream> {
+    let TraitAndImpl {
+        item_trait,
+        mut item_impl,
+    } = syn::parse(input_stream)?;
+
+    let Some((_, trait_path, _)) = &item_impl.trait_ else {
+        bail!(item_impl => "expected a trait impl");
+    };
+
+    let mut substitute = SubstituteParams::new(&item_trait, trait_path)?;
+
+    let signatures = dispatched_signatures(&item_trait)?
+        .into_iter()
+        .map(|mut signature| {
+            substitute.visit_trait_item_fn_mut(&mut signature);
+            signature
+        });
+
+    item_impl.unsafety = item_trait.unsafety;
+
+    let ItemImpl {
+        unsafety,
+        generics,
+        trait_,
+        self_ty,
+        ..
+    } = &item_impl;
+
+    let trait_path = trait_.as_ref().map(|(_, path, _)| path);
+    let where_clause = &generics.where_clause;
+
+    Ok(quote! {
+        #[::spire_enum::prelude::delegate_impl]
+        #unsafety impl #generics #trait_path for #self_ty #where_clause {
+            #(#signatures)*
+        }
+    })
+}
+
+struct TraitAndImpl {
+    item_trait: ItemTrait,
+    item_impl:  ItemImpl,
+}
+
+impl Parse for TraitAndImpl {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(TraitAndImpl {
+            item_trait: input.parse()?,
+            item_impl:  input.parse()?,
+        })
+    }
+}
+
+/// The methods of the trait, without their default implementations.
+///
+/// # Errors
+/// If the trait has items that cannot be dispatched: associated types/constants, macros or methods without a receiver.
+fn dispatched_signatures(item_trait: &ItemTrait) -> Result<Vec<TraitItemFn>> {
+    const HELP: &str = "Help: Implement the trait with `#[delegate_impl]` instead, \
+        which supports associated types, constants and functions.";
+
+    item_trait
+        .items
+        .iter()
+        .map(|item| {
+            match item {
+                TraitItem::Fn(func) if func.sig.receiver().is_some() => {
+                    let attrs = func
+                        .attrs
+                        .iter()
+                        .filter(|attr| attr.path().is_ident("cfg"))
+                        .cloned()
+                        .collect();
+
+                    Ok(TraitItemFn {
+                        attrs,
+                        sig: func.sig.clone(),
+                        default: None,
+                        semi_token: None,
+                    })
+                }
+                TraitItem::Fn(func) => {
+                    bail!(func.sig => format!("`enum_dispatch` cannot dispatch functions without a receiver.\n{HELP}"))
+                }
+                TraitItem::Type(ty) => {
+                    bail!(ty => format!("`enum_dispatch` does not support associated types.\n{HELP}"))
+                }
+                TraitItem::Const(constant) => {
+                    bail!(constant => format!("`enum_dispatch` does not support associated constants.\n{HELP}"))
+                }
+                other => bail!(other => "`enum_dispatch` only supports methods"),
+            }
+        })
+        .try_collect()
+}
+
+/// Replaces the generic parameters of a trait with the arguments of a path to the trait.
+struct SubstituteParams {
+    types: HashMap<Ident, Type>,
+    lifetimes: HashMap<Ident, Lifetime>,
+    consts: HashMap<Ident, Expr>,
+}
+
+impl SubstituteParams {
+    fn new(item_trait: &ItemTrait, trait_path: &Path) -> Result<Self> {
+        let args = match trait_path.segments.last().map(|seg| &seg.arguments) {
+            Some(PathArguments::AngleBracketed(args)) => args.args.iter().collect::<Vec<_>>(),
+            Some(PathArguments::Parenthesized(args)) => {
+                bail!(args => "`enum_dispatch` does not support parenthesized generic arguments")
+            }
+            _ => Vec::new(),
+        };
+
+        let params = &item_trait.generics.params;
+        if args.len() > params.len() {
+            bail!(trait_path => format!(
+                "`{}` expects {} generic arguments, found {}",
+                item_trait.ident,
+                params.len(),
+                args.len()
+            ));
+        }
+
+        let mut substitute = SubstituteParams {
+            types: HashMap::new(),
+            lifetimes: HashMap::new(),
+            consts: HashMap::new(),
+        };
+
+        let mut args = args.into_iter();
+
+        for param in params {
+            let arg = args.next();
+
+            match (param, arg) {
+                (GenericParam::Type(param), Some(GenericArgument::Type(ty))) => {
+                    substitute.types.insert(param.ident.clone(), ty.clone());
+                }
+                (
+                    GenericParam::Type(TypeParam {
+                        ident,
+                        default: Some(ty),
+                        ..
+                    }),
+                    None,
+                ) => {
+                    substitute.types.insert(ident.clone(), ty.clone());
+                }
+                (GenericParam::Lifetime(param), Some(GenericArgument::Lifetime(lifetime))) => {
+                    substitute
+                        .lifetimes
+                        .insert(param.lifetime.ident.clone(), lifetime.clone());
+                }
+                (GenericParam::Const(param), Some(GenericArgument::Const(expr))) => {
+                    substitute.consts.insert(param.ident.clone(), expr.clone());
+                }
+                // A const argument that is a single identifier is parsed as a type.
+                (GenericParam::Const(param), Some(GenericArgument::Type(ty))) => {
+                    substitute
+                        .consts
+                        .insert(param.ident.clone(), parse_quote! { #ty });
+                }
+                (
+                    GenericParam::Const(syn::ConstParam {
+                        ident,
+                        default: Some(expr),
+                        ..
+                    }),
+                    None,
+                ) => {
+                    substitute.consts.insert(ident.clone(), expr.clone());
+                }
+                (param, Some(arg)) => {
+                    bail!(arg => "mismatched generic argument..", param => "..for this parameter");
+                }
+                (param, None) => {
+                    bail!(trait_path => "missing generic argument..", param => "..for this parameter");
+                }
+            }
+        }
+
+        Ok(substitute)
+    }
+}
+
+impl VisitMut for SubstituteParams {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        if let Type::Path(TypePath { qself: None, path }) = ty
+            && path.leading_colon.is_none()
+            && let Some(first) = path.segments.first()
+            && first.arguments.is_none()
+            && let Some(arg) = self.types.get(&first.ident)
+        {
+            let rest = path.segments.iter().skip(1);
+            *ty = if path.segments.len() == 1 {
+                arg.clone()
+            } else {
+                parse_quote! { <#arg> #(:: #rest)* }
+            };
+
+            return;
+        }
+
+        visit_mut::visit_type_mut(self, ty);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if let Some(arg) = self.lifetimes.get(&lifetime.ident) {
+            *lifetime = arg.clone();
+        }
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let Expr::Path(syn::ExprPath {
+            qself: None, path, ..
+        }) = expr
+            && let Some(ident) = path.get_ident()
+            && let Some(arg) = self.consts.get(ident)
+        {
+            *expr = arg.clone();
+            return;
+        }
+
+        visit_mut::visit_expr_mut(self, expr);
+    }
+}
+
+fn enum_dispatch_macro_ident(trait_ident: &Ident) -> Ident {
+    let ident = format!("__enum_dispatch_{}", trait_ident.to_string().to_case(Case::Snake));
+    Ident::new(&ident, trait_ident.span())
+}
@@ -10,6 +10,7 @@ pub fn tokenize_enum_ref<'a>(
     var_cfgs: &[&Any<Attribute<CfgMeta>>],
     var_tys: &[&'a Type],
     var_idents: &[&'a Ident],
+    impl_from: bool,
 ) -> (Ident, Type, TokenStream) {
     let lf = Lifetime::new("'_r", Span::call_site());
     let ident = format_ident!("{enum_ident}Ref");
@@ -32,6 +33,11 @@ pub fn tokenize_enum_ref<'a>(
         }
     };
 
+    // `From` impls conflict when the variant types aren't unique.
+    if !impl_from {
+        return (ident, ty, def);
+    }
+
     for ((var_cfg, var_ty), var_ident) in var_cfgs.iter().zip(var_tys.iter()).zip(var_idents.iter())
     {
         def.extend(quote! {
@@ -58,6 +64,7 @@ pub fn tokenize_enum_mut<'a>(
     var_cfgs: &[&Any<Attribute<CfgMeta>>],
     var_tys: &[&'a Type],
     var_idents: &[&'a Ident],
+    impl_from: bool,
 ) -> (Ident, Type, TokenStream) {
     let lf = Lifetime::new("'_r", Span::call_site());
     let ident = format_ident!("{enum_ident}Mut");
@@ -80,6 +87,11 @@ pub fn tokenize_enum_mut<'a>(
         }
     };
 
+    // `From` impls conflict when the variant types aren't unique.
+    if !impl_from {
+        return (ident, ty, def);
+    }
+
     for ((var_cfg, var_ty), var_ident) in var_cfgs.iter().zip(var_tys.iter()).zip(var_idents.iter())
     {
         def.extend(quote! {
@@ -7,11 +7,15 @@
 
 mod delegate_impl;
 mod delegated_enum;
+mod enum_dispatch;
 mod enum_refs;
 mod ident_map;
 mod macros;
+mod repr_conversions;
 mod shared;
+mod strategies;
 mod tables;
+mod variant_meta;
 
 use convert_case::{Case, Casing};
 use ident_map::*;
@@ -65,10 +69,140 @@ pub fn delegated_enum(settings_stream: TokenStream1, enum_stream: TokenStream1)
         .into()
 }
 
+/// Function-like form of [`macro@delegated_enum`], for enums defined elsewhere (e.g. in other crates).
+///
+/// Takes the path of the enum and a restatement of its variants (including their fields),
+/// followed by the same settings accepted by [`macro@delegated_enum`], except `extract_variants`.
+/// The restated variants accept the same attributes as the ones of [`macro@delegated_enum`]
+/// (e.g. `#[delegator]`, `#[delegate_via(..)]`, `#[dont_impl_conversions]`).
+///
+/// ```rust ignore
+/// foreign_delegated_enum!(
+///     other_crate::Shape {
+///         Circle(Circle),
+///         Square(Square),
+///     },
+///     impl_conversions,
+/// );
+///
+/// // The generated macro can be used by `delegate_impl`, as long as the trait is local.
+/// #[delegate_impl]
+/// impl Area for other_crate::Shape {
+///     fn area(&self) -> f64;
+/// }
+/// ```
+///
+/// ## Exhaustiveness
+/// The restated variants are matched exhaustively against the enum's real definition,
+/// if the enum gains or loses a variant, the invocation fails to compile.
+///
+/// Enums marked with `#[non_exhaustive]` are not supported.
+///
+/// ## Conversions
+/// Only the impls allowed by the orphan rules are generated:
+/// - `TryFrom<Enum>`/`FromEnum<Enum>` (and their reference counterparts) for each variant's type.
+/// - `From<VarType>` for the enum.
+///
+/// These are only allowed if the variant's type is defined in the current crate,
+/// mark variants whose types are also foreign with `#[dont_impl_conversions]`.
+///
+/// `EnumExtensions` is not implemented for the enum, since both the trait and the enum are foreign.
+#[proc_macro]
+pub fn foreign_delegated_enum(input_stream: TokenStream1) -> TokenStream1 {
+    delegated_enum::run_foreign(input_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Compatibility shim for the syntax of the crate [`enum_dispatch`](https://crates.io/crates/enum_dispatch),
+/// re-exported as `spire_enum::compat::enum_dispatch`.
+///
+/// ```rust ignore
+/// use spire_enum::compat::enum_dispatch;
+///
+/// #[enum_dispatch]
+/// trait Shape {
+///     fn area(&self) -> f64;
+/// }
+///
+/// #[enum_dispatch(Shape)]
+/// enum Figure {
+///     Circle,         // Shorthand for `Circle(Circle)`
+///     Square(Square),
+/// }
+///
+/// // Expands to:
+/// #[delegated_enum(impl_conversions)]
+/// enum Figure {
+///     Circle(Circle),
+///     Square(Square),
+/// }
+///
+/// #[delegate_impl]
+/// impl Shape for Figure {
+///     fn area(&self) -> f64;
+/// }
+/// ```
+///
+/// - Traits may also list the enums that implement them: `#[enum_dispatch(Figure)]`, as long as those are not generic.
+/// - Generic traits are supported, the enum lists the arguments: `#[enum_dispatch(Convert<u32>)]`.
+/// - The trait must be annotated with `#[enum_dispatch]` and defined in the current crate,
+///   it generates a macro with the trait's name, which is invoked by the enums that list the trait.
+///   The macro is imported alongside the trait (`use shapes::Shape;`).
+/// - Only methods with a receiver can be dispatched, implement traits with associated types, constants or functions
+///   with [`macro@delegate_impl`] instead.
+#[proc_macro_attribute]
+pub fn enum_dispatch(args_stream: TokenStream1, item_stream: TokenStream1) -> TokenStream1 {
+    enum_dispatch::run(args_stream, item_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Used by the macros generated by [`macro@enum_dispatch`], not meant to be used directly.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __enum_dispatch_impl(input_stream: TokenStream1) -> TokenStream1 {
+    enum_dispatch::run_impl(input_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Shorthand of [`macro@delegated_enum`] for enums whose variants are named after their single field's type.
+///
+/// ```rust ignore
+/// union! {
+///     #[derive(Debug)]
+///     pub enum State<T> = Idle | Aiming | ChargingShot<T> | #[cfg(feature = "heat")] CoolingDown;
+///     settings(impl_conversions)
+/// }
+///
+/// // Expands to:
+/// #[delegated_enum(impl_conversions)]
+/// #[derive(Debug)]
+/// pub enum State<T> {
+///     Idle(Idle),
+///     Aiming(Aiming),
+///     ChargingShot(ChargingShot<T>),
+///     #[cfg(feature = "heat")]
+///     CoolingDown(CoolingDown),
+/// }
+/// ```
+///
+/// - Each variant's name is the last segment of its type's path, without generics (`a::b::Foo<T>` => `Foo`).
+///   Types that are not paths (e.g. references, tuples) are not supported.
+/// - Each type may be preceded by the variant attributes accepted by [`macro@delegated_enum`].
+/// - `settings(..)` is optional, and accepts the same settings as [`macro@delegated_enum`].
+#[proc_macro]
+pub fn union(input_stream: TokenStream1) -> TokenStream1 {
+    delegated_enum::run_union(input_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
 /// See the [crate-level](crate) documentation
 #[proc_macro_attribute]
-pub fn delegate_impl(_input_stream: TokenStream1, impl_stream: TokenStream1) -> TokenStream1 {
-    delegate_impl::run(impl_stream)
+pub fn delegate_impl(settings_stream: TokenStream1, impl_stream: TokenStream1) -> TokenStream1 {
+    delegate_impl::run(settings_stream, impl_stream)
         .unwrap_or_else(|err| err.into_compile_error())
         .into()
 }
@@ -130,6 +264,34 @@ pub fn delegate_impl(_input_stream: TokenStream1, impl_stream: TokenStream1) ->
 ///
 /// // IntoIter impl
 /// fn into_iter(self) -> impl Iterator<Item = Enum>;
+///
+/// // Returns a builder with no values, see `Builder` below.
+/// pub const fn builder() -> TableBuilder;
+/// ```
+///
+/// ## Builder
+///
+/// A builder type named `[TableName]Builder` is also generated, it accepts values in any order
+/// (useful when loading the table from partial sources, such as save files or plugin registrations):
+///
+/// ```rust ignore
+/// pub const fn new() -> Self;
+/// // Sets the value of `Var`, replacing the previous one.
+/// pub fn with::<Var>(self, value: Var) -> Self;
+/// // Sets the value of the variant in `value`, returns it back inside the error if that variant already has a value.
+/// pub fn insert_enum(&mut self, value: Enum) -> Result<(), Duplicate<Enum>>;
+/// // Fails if any variant has no value, the error lists the names of the missing variants.
+/// pub fn build(self) -> Result<Table, MissingVariants<LEN>>;
+///
+/// // If a variant is yielded more than once, the last value is kept.
+/// impl FromIterator<Enum> for TableBuilder;
+/// ```
+///
+/// ```rust ignore
+/// let table = SettingVariantTypeTable::builder()
+///     .with::<Percentage>(Percentage(20.0))
+///     .with::<WindowSize>(WindowSize(1920, 1080))
+///     .build(); // Err: missing values for variants: `ProfileName`
 /// ```
 ///
 /// ## Example - Using generated impls
@@ -172,12 +334,40 @@ pub fn delegate_impl(_input_stream: TokenStream1, impl_stream: TokenStream1) ->
 ///     ),
 ///     // Shorthand for attrs(derive(Clone, Debug))
 ///     derive(Clone, Debug),
+///     // By default, the variant's types are used as keys to access their values (e.g. `get::<Var>()`).
+///     // This generates a zero-sized tag type for each variant, which are used as keys instead (e.g. `get::<tags::Var>()`),
+///     // lifting the limitations on generic and duplicate variant types (see Limitations - Generics).
+///     // - The tags are generated in a module named `tags`, use `keys = tags(my_tags)` to customize the module name.
+///     keys = tags,
+///     // Implements `arbitrary::Arbitrary` for the table, requires the feature `arbitrary` of `spire_enum`.
+///     // Each value is generated independently, in the order the variants were declared.
+///     arbitrary,
+///     // Implements `proptest::arbitrary::Arbitrary` for the table, requires the feature `proptest` of `spire_enum`.
+///     // The strategy shrinks the values one at a time, in the order the variants were declared.
+///     proptest,
 /// )]
 /// ```
 ///
+/// ## Variant Fields
+/// The type associated with each variant depends on its fields:
+/// - A single field (named or unnamed): the field's type, e.g. `Circle { radius: f32 }` => `f32`.
+/// - Several fields: a tuple of the fields' types (in declaration order), e.g. `Rect(u32, u32)` => `(u32, u32)`.
+/// - No fields: `()`.
+///
+/// Variants can be left out of the table with the attribute `#[table_skip]`:
+/// ```rust ignore
+/// pub enum Shape {
+///     Circle { radius: f32 },
+///     Rect(u32, u32),
+///     #[table_skip]
+///     Empty,
+/// }
+/// ```
+///
+/// Tip: combine with [`macro@delegated_enum`]'s setting `extract_variants` to have a unique type generated for each variant.
+///
 /// ## Limitations
-/// - Each variant should have exactly a single field (named or unnamed).
-/// - Each variant's type must be unique (no two variants can share the same type).
+/// - Each variant's type must be unique (no two variants can share the same type), unless using the setting `keys = tags`.
 ///
 /// ## Limitations - Generics
 /// Any number of generics is allowed, as well as their bounds/where clauses.
@@ -213,6 +403,9 @@ pub fn delegate_impl(_input_stream: TokenStream1, impl_stream: TokenStream1) ->
 /// uncovered generics result in implementations that conflict with all types,
 /// since T can be anything.
 ///
+/// The setting `keys = tags` lifts this limitation, at the cost of having to name the tags instead of the variant's types:
+/// `table.get::<tags::Bar>()`, `table.set::<tags::Bar>(value)`.
+///
 /// If you need a similar table but storing a generic value instead of each variant's type, check [`macro@variant_generic_table`].
 #[proc_macro_attribute]
 pub fn variant_type_table(input_stream: TokenStream1, enum_stream: TokenStream1) -> TokenStream1 {
@@ -264,6 +457,9 @@ pub fn variant_type_table(input_stream: TokenStream1, enum_stream: TokenStream1)
 /// // - Fields: snake-case variant names
 /// pub const fn new( #(#Fields: T),* ) -> Self;
 /// pub fn filled_with(T) -> Self where T: Clone;
+/// // - EnumKey: an enum with the same variant names as the input enum, but without any fields.
+/// pub fn from_fn(impl FnMut(EnumKey) -> T) -> Self;
+/// pub fn try_from_iter(impl IntoIterator<Item = (EnumKey, T)>) -> Result<Self, MissingVariants<LEN>>;
 ///
 /// // Get a reference/mut-reference to the value associated with a given variant based on the generic type.
 /// // - Var: any of the enum's variant types
@@ -278,9 +474,23 @@ pub fn variant_type_table(input_stream: TokenStream1, enum_stream: TokenStream1)
 /// // but contain a reference to a value `T`.
 /// pub fn iter<'a>(&'a self) -> impl Iterator<Item = EnumRef<'a, T>>;
 /// pub fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = EnumMut<'a, T>>;
+/// pub fn keys(&self) -> impl Iterator<Item = EnumKey>;
+/// pub fn values(&self) -> impl Iterator<Item = &T>;
+/// pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T>;
+/// pub fn into_values(self) -> impl Iterator<Item = T>;
+///
+/// // Combinators
+/// pub fn map<U>(self, impl FnMut(EnumKey, T) -> U) -> Table<U>;
+/// pub fn try_map<U, E>(self, impl FnMut(EnumKey, T) -> Result<U, E>) -> Result<Table<U>, E>;
+/// pub fn zip<U>(self, other: Table<U>) -> Table<(T, U)>;
+/// pub const fn as_ref(&self) -> Table<&T>;
+/// pub const fn as_mut(&mut self) -> Table<&mut T>;
 ///
 /// // IntoIter impl
 /// fn into_iter(self) -> impl Iterator<Item = T>;
+///
+/// // FromIterator impl, panics if any variant is missing (see `try_from_iter`)
+/// fn from_iter(impl IntoIterator<Item = (EnumKey, T)>) -> Self;
 /// ```
 ///
 /// ## Example - Using generated impls
@@ -323,12 +533,75 @@ pub fn variant_type_table(input_stream: TokenStream1, enum_stream: TokenStream1)
 ///     ),
 ///     // Shorthand for attrs(derive(Clone, Debug))
 ///     derive(Clone, Debug),
+///     // Implements element-wise arithmetic for the table, each impl is bounded on `T` implementing the same operator:
+///     // - `Add`, `Sub`, `Mul`, `Div` (and their `*Assign` forms), between two tables and between a table and a `T`.
+///     // - `Neg`.
+///     // - `Sum`, over iterators of tables (requires `T: Default`).
+///     impl_ops,
+///     // By default, the variant's types are used as keys to access their values (e.g. `get::<Var>()`).
+///     // This generates a zero-sized tag type for each variant, which are used as keys instead (e.g. `get::<tags::Var>()`),
+///     // lifting the limitations on generic and duplicate variant types (see Limitations - Generics).
+///     // - The tags are generated in a module named `tags`, use `keys = tags(my_tags)` to customize the module name.
+///     keys = tags,
+///     // Generates `Default` and the methods below for `Table<f32>`, see Default Values.
+///     value_ty = f32,
+///     // Implements `arbitrary::Arbitrary` for the table, requires the feature `arbitrary` of `spire_enum`.
+///     // Each value is generated independently, in the order the variants were declared.
+///     arbitrary,
+///     // Implements `proptest::arbitrary::Arbitrary` for the table, requires the feature `proptest` of `spire_enum`.
+///     // The strategy shrinks the values one at a time, in the order the variants were declared.
+///     proptest,
+///     // Generates the ordinal helpers for the key type, see Ordinal Navigation.
+///     ordinal,
 /// )]
 /// ```
 ///
+/// ## Default Values
+/// With the setting `value_ty = T`, each variant's default value can be declared with the attribute `#[default_value(..)]`,
+/// variants without it use `T::default()`. The expressions are evaluated inside an impl block of the table
+/// (`Self` is the table type).
+///
+/// ```rust ignore
+/// #[variant_generic_table(ty_name = Volumes, value_ty = f32)]
+/// pub enum Channel {
+///     #[default_value(1.0)]
+///     Master(Master),
+///     #[default_value(0.8)]
+///     Music(Music),
+///     Voice(Voice), // 0.0
+/// }
+/// ```
+///
+/// Generates, for `Table<T>`:
+/// ```rust ignore
+/// impl Default for Table<T>;
+/// pub fn defaults() -> Self;
+/// pub fn default_value(EnumKey) -> T;
+/// // Replaces the value of `Var` with its default value.
+/// pub fn reset::<Var>(&mut self);
+/// pub fn reset_all(&mut self);
+/// ```
+///
+/// ## Variant Fields
+/// The type associated with each variant depends on its fields:
+/// - A single field (named or unnamed): the field's type, e.g. `Circle { radius: f32 }` => `f32`.
+/// - Several fields: a tuple of the fields' types (in declaration order), e.g. `Rect(u32, u32)` => `(u32, u32)`.
+/// - No fields: `()`.
+///
+/// Variants can be left out of the table with the attribute `#[table_skip]`:
+/// ```rust ignore
+/// pub enum Shape {
+///     Circle { radius: f32 },
+///     Rect(u32, u32),
+///     #[table_skip]
+///     Empty,
+/// }
+/// ```
+///
+/// Tip: combine with [`macro@delegated_enum`]'s setting `extract_variants` to have a unique type generated for each variant.
+///
 /// ## Limitations
-/// - Each variant should have exactly a single field (named or unnamed).
-/// - Each variant's type must be unique (no two variants can share the same type).
+/// - Each variant's type must be unique (no two variants can share the same type), unless using the setting `keys = tags`.
 ///
 /// ## Limitations - Generics
 /// Any number of generics is allowed, as well as their bounds/where clauses.
@@ -364,6 +637,9 @@ pub fn variant_type_table(input_stream: TokenStream1, enum_stream: TokenStream1)
 /// uncovered generics result in implementations that conflict with all types,
 /// since T can be anything.
 ///
+/// The setting `keys = tags` lifts this limitation, at the cost of having to name the tags instead of the variant's types:
+/// `table.get::<tags::Bar>()`, `table.set::<tags::Bar>(value)`.
+///
 /// If you need a similar table but with unit variants, check [`macro@discriminant_generic_table`].
 /// If you need a similar table but storing each variant's type, check [`macro@variant_type_table`].
 #[proc_macro_attribute]
@@ -376,7 +652,8 @@ pub fn variant_generic_table(
         .into()
 }
 
-/// Similar to [`macro@variant_generic_table`], except this is meant for enums with unit variants.
+/// Similar to [`macro@variant_generic_table`], except this is meant for enums with unit variants,
+/// or enums whose variants should be keyed by discriminant regardless of their fields (see [Data-carrying Variants](#data-carrying-variants)).
 ///
 /// Given an enum, generates a struct that contains a generic value
 /// mapped to one of each of the enum's variants,
@@ -419,6 +696,7 @@ pub fn variant_generic_table(
 /// pub const fn new( #(#Fields: T),* ) -> Self;
 /// pub fn filled_with(T) -> Self where T: Clone;
 /// pub fn from_fn(impl FnMut(Enum) -> T) -> Self;
+/// pub fn try_from_iter(impl IntoIterator<Item = (Enum, T)>) -> Result<Self, MissingVariants<LEN>>;
 ///
 /// // Get a reference/mut-reference to the value associated with a given variant.
 /// // These methods can also be invoked by using indexing (e.g. `&table[VolumeSetting::Main]`)
@@ -431,9 +709,23 @@ pub fn variant_generic_table(
 /// // Iter by reference/mut-reference
 /// pub fn iter(&lf self) -> impl Iterator<Item = (Enum, &T)>;
 /// pub fn iter_mut(&mut self) -> impl Iterator<Item = (Enum, &mut T)>;
+/// pub fn keys(&self) -> impl Iterator<Item = Enum>;
+/// pub fn values(&self) -> impl Iterator<Item = &T>;
+/// pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T>;
+/// pub fn into_values(self) -> impl Iterator<Item = T>;
+///
+/// // Combinators
+/// pub fn map<U>(self, impl FnMut(Enum, T) -> U) -> Table<U>;
+/// pub fn try_map<U, E>(self, impl FnMut(Enum, T) -> Result<U, E>) -> Result<Table<U>, E>;
+/// pub fn zip<U>(self, other: Table<U>) -> Table<(T, U)>;
+/// pub const fn as_ref(&self) -> Table<&T>;
+/// pub const fn as_mut(&mut self) -> Table<&mut T>;
 ///
 /// // IntoIter impl
 /// fn into_iter(self) -> impl Iterator<Item = (Enum, T)>;
+///
+/// // FromIterator impl, panics if any variant is missing (see `try_from_iter`)
+/// fn from_iter(impl IntoIterator<Item = (Enum, T)>) -> Self;
 /// ```
 ///
 /// ## Example - Using generated impls
@@ -475,13 +767,99 @@ pub fn variant_generic_table(
 ///     ),
 ///     // Shorthand for attrs(derive(Clone, Debug))
 ///     derive(Clone, Debug),
+///     // Implements element-wise arithmetic for the table, each impl is bounded on `T` implementing the same operator:
+///     // - `Add`, `Sub`, `Mul`, `Div` (and their `*Assign` forms), between two tables and between a table and a `T`.
+///     // - `Neg`.
+///     // - `Sum`, over iterators of tables (requires `T: Default`).
+///     impl_ops,
+///     // Stores the values in a single array field (`pub values: [T; LEN]`) instead of one field per variant,
+///     // where each variant's value is at its ordinal (position in the declaration order).
+///     // Generates these additional impls:
+///     // - `pub const fn from_array([T; LEN]) -> Self;`, `pub fn into_array(self) -> [T; LEN];`
+///     // - `pub const fn as_slice(&self) -> &[T];`, `pub const fn as_mut_slice(&mut self) -> &mut [T];`
+///     // - `From<[T; LEN]>` for the table and `From<Table<T>>` for `[T; LEN]`.
+///     // - `Index<usize>` and `IndexMut<usize>`, accessing values by ordinal.
+///     storage = array,
+///     // Generates `Default` and the methods below for `Table<f32>`, see Default Values.
+///     value_ty = f32,
+///     // Implements `arbitrary::Arbitrary` for the table, requires the feature `arbitrary` of `spire_enum`.
+///     // Each value is generated independently, in the order the variants were declared.
+///     arbitrary,
+///     // Implements `proptest::arbitrary::Arbitrary` for the table, requires the feature `proptest` of `spire_enum`.
+///     // The strategy shrinks the values one at a time, in the order the variants were declared.
+///     proptest,
 /// )]
 /// ```
 ///
+/// ## Default Values
+/// With the setting `value_ty = T`, each variant's default value can be declared with the attribute `#[default_value(..)]`,
+/// variants without it use `T::default()`. The expressions are evaluated inside an impl block of the table
+/// (`Self` is the table type).
+///
+/// ```rust ignore
+/// #[discriminant_generic_table(ty_name = Cooldowns, value_ty = u32)]
+/// pub enum Ability {
+///     #[default_value(30)]
+///     Dash,
+///     Block, // 0
+/// }
+/// ```
+///
+/// Generates, for `Table<T>`:
+/// ```rust ignore
+/// impl Default for Table<T>;
+/// pub fn defaults() -> Self;
+/// pub fn default_value(Enum) -> T;
+/// // Replaces the value of `var` with its default value.
+/// pub fn reset(&mut self, var: Enum);
+/// pub fn reset_all(&mut self);
+/// ```
+///
+/// ## Data-carrying Variants
+/// Variants may have fields, in which case the table is keyed by the variant's discriminant, ignoring its payload.
+///
+/// Since such variants cannot be constructed without their fields, the macro also generates a fieldless enum
+/// named `[EnumName]Kind`, which takes the place of `Enum` in the methods above (`from_fn`, `iter`, `keys`, `map`, ...).
+///
+/// `get`, `get_mut`, `set` and indexing accept anything that converts into the kind enum,
+/// which includes references to the original enum (`impl From<&Enum> for EnumKind`).
+/// In this mode, `get` and `get_mut` are not `const`.
+///
+/// ```rust ignore
+/// #[discriminant_generic_table(ty_name = StateTimers)]
+/// pub enum PlayerState {
+///     Idle,
+///     Walking(f32),
+///     Attacking { target: u32 },
+/// }
+///
+/// let mut timers = StateTimers::from_fn(|kind| {
+///     match kind {
+///         PlayerStateKind::Idle => 0.0,
+///         PlayerStateKind::Walking => 0.5,
+///         PlayerStateKind::Attacking => 1.0,
+///     }
+/// });
+///
+/// let state = PlayerState::Walking(2.0);
+/// timers[&state] += 1.0;
+/// assert_eq!(timers.get(&state), &1.5);
+/// assert_eq!(timers[PlayerStateKind::Walking], 1.5);
+/// ```
+///
+/// ## Ordinal Navigation
+/// With the setting `ordinal`, the key type (the enum, or `[EnumName]Kind`) also receives the inherent helpers
+/// generated by [`macro@ordinal`]: `LEN`, `ordinal`, `from_ordinal`, `first`, `last`, `next`, `prev`, `cycle_next`,
+/// `cycle_prev`, `all` and `range`. Without it, only `LEN`, `ordinal` and `from_ordinal` are available,
+/// through the trait `DiscriminantKey`.
+///
+/// Like the trait implementation of `DiscriminantKey`, the setting is not supported for fieldless enums defined elsewhere.
+///
 /// ## Limitations
-/// All variants should be unit variants (they cannot have any fields).
+/// - Every variant needs a value, `#[table_skip]` is not supported (unless it's meant for another table macro on the
+///   same enum).
 ///
-/// If you need a similar table but with non-unit variants, check [`macro@variant_generic_table`] or [`macro@variant_type_table`].
+/// If you need a table that stores each variant's fields, check [`macro@variant_generic_table`] or [`macro@variant_type_table`].
 #[proc_macro_attribute]
 pub fn discriminant_generic_table(
     input_stream: TokenStream1,
@@ -492,6 +870,782 @@ pub fn discriminant_generic_table(
         .into()
 }
 
+/// Generates helpers to navigate the variants of a fieldless enum by their ordinal (position in the declaration order),
+/// such as stepping through the options of a settings menu.
+///
+/// The order is the order the variants were declared (Top to bottom), explicit discriminant values are ignored.
+/// Variants disabled by `#[cfg]` attributes are skipped.
+///
+/// [`macro@discriminant_generic_table`] generates the same helpers with the setting `ordinal`, which cannot be combined
+/// with this attribute.
+///
+/// ## Example
+///
+/// ```rust ignore
+/// use spire_enum_macros::ordinal;
+///
+/// #[ordinal]
+/// #[derive(Clone, Copy, PartialEq, Debug)]
+/// pub enum Quality {
+///     Low = 10,
+///     Medium = 5,
+///     High = 0,
+/// }
+///
+/// assert_eq!(Quality::Low.next(), Some(Quality::Medium));
+/// assert_eq!(Quality::High.cycle_next(), Quality::Low);
+/// assert_eq!(Quality::from_ordinal(2), Some(Quality::High));
+/// assert_eq!(Quality::range(Quality::Medium..=Quality::High).count(), 2);
+/// ```
+///
+/// ## Generated Items
+///
+/// ```rust ignore
+/// impl Enum {
+///     // The amount of variants.
+///     pub const LEN: usize;
+///     pub const fn ordinal(&self) -> usize;
+///     pub const fn from_ordinal(usize) -> Option<Self>;
+///     // Both panic if the enum has no variants.
+///     pub const fn first() -> Self;
+///     pub const fn last() -> Self;
+///     // `None` at the ends.
+///     pub const fn next(&self) -> Option<Self>;
+///     pub const fn prev(&self) -> Option<Self>;
+///     // Wrap around at the ends.
+///     pub const fn cycle_next(&self) -> Self;
+///     pub const fn cycle_prev(&self) -> Self;
+///     // Iterators over all variants, or over the variants in an inclusive range (empty if `end` comes before `start`).
+///     pub fn all() -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + Clone;
+///     pub fn range(RangeInclusive<Self>) -> impl DoubleEndedIterator<Item = Self> + ExactSizeIterator + Clone;
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn ordinal(settings_stream: TokenStream1, enum_stream: TokenStream1) -> TokenStream1 {
+    tables::ordinal::run(settings_stream, enum_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Generates conversions between a fieldless enum and the integer type in its `#[repr(..)]` attribute,
+/// such as when enums are encoded as integers in network packets or save files.
+///
+/// Explicit discriminant values are honoured, variants disabled by `#[cfg]` attributes are skipped.
+///
+/// ## Example
+///
+/// ```rust ignore
+/// use spire_enum::prelude::{InvalidRepr, repr_conversions};
+///
+/// #[repr_conversions]
+/// #[repr(u8)]
+/// #[derive(Clone, Copy, PartialEq, Debug)]
+/// pub enum PacketKind {
+///     Handshake = 1,
+///     Ping, // 2
+///     Disconnect = 10,
+/// }
+///
+/// assert_eq!(u8::from(PacketKind::Ping), 2);
+/// assert_eq!(PacketKind::try_from(10), Ok(PacketKind::Disconnect));
+/// assert_eq!(PacketKind::try_from(3), Err(InvalidRepr::new("PacketKind", 3)));
+/// assert_eq!(PacketKind::DISCRIMINANTS, [1, 2, 10]);
+/// ```
+///
+/// ## Generated Items
+///
+/// ```rust ignore
+/// impl Enum {
+///     // The discriminant of each variant, in the order they were declared (Top to bottom).
+///     pub const DISCRIMINANTS: [Repr; LEN];
+///     // Returns the variant whose discriminant is `value`, if any.
+///     pub const fn from_repr(value: Repr) -> Option<Self>;
+/// }
+///
+/// impl From<Enum> for Repr;
+/// // The error contains the rejected value.
+/// impl TryFrom<Repr> for Enum { type Error = InvalidRepr<Repr>; }
+/// ```
+///
+/// ## Duplicate Discriminants
+/// The compiler rejects duplicate discriminants among the variants that are enabled, but a duplicate may only
+/// appear with a different combination of `#[cfg]` attributes (e.g. when a feature is enabled by another crate).
+///
+/// The macro reports two variants with the same discriminant regardless of their `#[cfg]` attributes,
+/// unless one has `#[cfg(P)]` and the other has `#[cfg(not(P))]`.
+/// This check only considers discriminants that are integer literals, or that follow one without conditionally
+/// compiled variants in between.
+///
+/// ```rust ignore
+/// #[repr_conversions]
+/// #[repr(u8)]
+/// pub enum PacketKind {
+///     Handshake = 1,
+///     #[cfg(feature = "compression")]
+///     Compressed = 2,
+///     Ping = 2, // Error: variants `Compressed` and `Ping` have the same discriminant
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn repr_conversions(settings_stream: TokenStream1, enum_stream: TokenStream1) -> TokenStream1 {
+    repr_conversions::run(settings_stream, enum_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Given two fieldless enums, generates a struct that contains a generic value
+/// mapped to each pair of their variants, and several implementations for the generated type as well.
+///
+/// Pairs of variants `(Row, Column)` are used as keys to access their associated values.
+///
+/// Both enums must implement the trait `spire_enum::prelude::DiscriminantKey`,
+/// which is implemented by [`macro@discriminant_generic_table`].
+///
+/// The type generated by this macro does not allocate any heap memory, thus it is `no_std` compatible.
+///
+/// ## Example
+///
+/// ```rust ignore
+/// use spire_enum::prelude::{discriminant_generic_table, discriminant_matrix};
+///
+/// #[discriminant_generic_table]
+/// #[derive(Clone, Copy)]
+/// pub enum DamageType {
+///     Physical,
+///     Fire,
+/// }
+///
+/// #[discriminant_generic_table]
+/// #[derive(Clone, Copy)]
+/// pub enum ArmorType {
+///     Plate,
+///     Cloth,
+///     Scales,
+/// }
+///
+/// discriminant_matrix!(DamageType, ArmorType);
+/// ```
+///
+/// Generates:
+///
+/// ```rust ignore
+/// pub struct DamageTypeArmorTypeMatrix<T> {
+///     // Indexed by the ordinal of the row (DamageType), then by the ordinal of the column (ArmorType).
+///     pub values: [[T; 3]; 2],
+/// }
+///
+/// // Same as above, except the rows are `ArmorType` and the columns are `DamageType`.
+/// pub struct DamageTypeArmorTypeMatrixTransposed<T> {
+///     pub values: [[T; 2]; 3],
+/// }
+/// ```
+///
+/// ## Generated Impls
+///
+/// The code emitted with all generated impls includes documentation, this is merely a summary.
+///
+/// ```rust ignore
+/// // Constructors
+/// pub const fn from_array([[T; COLUMNS]; ROWS]) -> Self;
+/// pub fn filled_with(T) -> Self where T: Clone;
+/// pub fn from_fn(impl FnMut(Row, Column) -> T) -> Self;
+///
+/// // Get a reference/mut-reference to the value associated with a given pair.
+/// // These methods can also be invoked by using indexing (e.g. `&matrix[(DamageType::Fire, ArmorType::Cloth)]`)
+/// pub fn get(&self, Row, Column) -> &T;
+/// pub fn get_mut(&mut self, Row, Column) -> &mut T;
+/// pub fn set(&mut self, Row, Column, value: T);
+///
+/// // Row and column views
+/// pub fn row(&self, Row) -> &[T; COLUMNS];
+/// pub fn row_mut(&mut self, Row) -> &mut [T; COLUMNS];
+/// pub fn column(&self, Column) -> impl Iterator<Item = (Row, &T)>;
+/// pub fn column_mut(&mut self, Column) -> impl Iterator<Item = (Row, &mut T)>;
+///
+/// // Iter by reference/mut-reference, row by row
+/// pub fn iter(&self) -> impl Iterator<Item = (Row, Column, &T)>;
+/// pub fn iter_mut(&mut self) -> impl Iterator<Item = (Row, Column, &mut T)>;
+///
+/// // Conversions
+/// pub fn map<U>(self, impl FnMut(Row, Column, T) -> U) -> Matrix<U>;
+/// pub fn transpose(self) -> MatrixTransposed<T>;
+/// pub fn into_array(self) -> [[T; COLUMNS]; ROWS];
+/// ```
+///
+/// ## Settings
+/// Settings are placed after the enums, comma-separated.
+///
+/// ```rust ignore
+/// discriminant_matrix!(
+///     DamageType,
+///     ArmorType,
+///     // By default, the type name is `[RowName][ColumnName]Matrix`,
+///     // the transposed type is always named `[TypeName]Transposed`.
+///     ty_name = DamageMultipliers,
+///     // By default, generated code is included in a module named `[row_name]_[column_name]_matrix`.
+///     mod_name = damage_multipliers,
+///     // Add attributes to both generated types.
+///     attrs(derive(Clone, Debug)),
+///     // Shorthand for attrs(derive(Clone, Debug))
+///     derive(Clone, Debug),
+/// );
+/// ```
+#[proc_macro]
+pub fn discriminant_matrix(input_stream: TokenStream1) -> TokenStream1 {
+    tables::discriminant_matrix::run(input_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Function-like form of [`macro@discriminant_generic_table`], for enums defined elsewhere (e.g. in other crates).
+///
+/// Attribute macros can only be placed where the enum is defined, this macro instead takes the path of the enum
+/// and a restatement of its variants, followed by the same settings accepted by [`macro@discriminant_generic_table`].
+///
+/// ## Example
+///
+/// ```rust ignore
+/// use spire_enum::prelude::foreign_discriminant_generic_table;
+///
+/// // Defined in another crate
+/// pub enum KeyCode {
+///     Up,
+///     Down,
+///     Escape,
+/// }
+///
+/// foreign_discriminant_generic_table!(
+///     other_crate::KeyCode {
+///         Up,
+///         Down,
+///         Escape,
+///     },
+///     ty_name = KeyBindings,
+///     derive(Clone, Debug),
+/// );
+///
+/// let bindings = KeyBindings::new("move_up", "move_down", "pause");
+/// assert_eq!(bindings[other_crate::KeyCode::Escape], "pause");
+/// ```
+///
+/// ## Exhaustiveness check
+/// The macro emits a function that matches the enum with the restated variants, if the restatement goes stale
+/// (a variant is added, removed or renamed), compilation fails.
+///
+/// `#[cfg]` attributes on the restated variants are respected.
+///
+/// ## Limitations
+/// - Enums marked with `#[non_exhaustive]` cannot be used, as they can't be matched exhaustively outside their crate.
+/// - The trait `DiscriminantKey` is not implemented for the enum (the orphan rule forbids it), so the resulting table
+///   cannot be used with [`macro@discriminant_matrix`], unless the enum has data-carrying variants (the trait is then
+///   implemented for the generated `[EnumName]Kind`).
+#[proc_macro]
+pub fn foreign_discriminant_generic_table(input_stream: TokenStream1) -> TokenStream1 {
+    tables::discriminant_to_generic::run_foreign(input_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Function-like form of [`macro@variant_type_table`], for enums defined elsewhere (e.g. in other crates).
+///
+/// Takes the path of the enum and a restatement of its variants (including their fields),
+/// followed by the same settings accepted by [`macro@variant_type_table`].
+///
+/// ```rust ignore
+/// foreign_variant_type_table!(
+///     other_crate::Event {
+///         Click(Click),
+///         Key(Key),
+///     },
+///     ty_name = EventTable,
+/// );
+/// ```
+///
+/// See [`macro@foreign_discriminant_generic_table`] for details about the exhaustiveness check and limitations.
+#[proc_macro]
+pub fn foreign_variant_type_table(input_stream: TokenStream1) -> TokenStream1 {
+    tables::variant_type_to_variant_type::run_foreign(input_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Function-like form of [`macro@variant_generic_table`], for enums defined elsewhere (e.g. in other crates).
+///
+/// Takes the path of the enum and a restatement of its variants (including their fields),
+/// followed by the same settings accepted by [`macro@variant_generic_table`].
+///
+/// ```rust ignore
+/// foreign_variant_generic_table!(
+///     other_crate::Event {
+///         Click(Click),
+///         Key(Key),
+///     },
+///     ty_name = EventCounters,
+/// );
+/// ```
+///
+/// See [`macro@foreign_discriminant_generic_table`] for details about the exhaustiveness check and limitations.
+#[proc_macro]
+pub fn foreign_variant_generic_table(input_stream: TokenStream1) -> TokenStream1 {
+    tables::variant_type_to_generic::run_foreign(input_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Derive form of [`macro@variant_type_table`], which generates the same table without re-emitting the enum.
+///
+/// The settings are provided by the helper attribute `#[table(..)]`,
+/// which accepts the same settings as [`macro@variant_type_table`].
+///
+/// Unlike the attribute form, its order relative to other attribute macros doesn't matter,
+/// so several tables can be generated for an enum that also has [`macro@delegated_enum`]:
+///
+/// ```rust ignore
+/// #[delegated_enum]
+/// #[derive(VariantTypeTable, VariantGenericTable)]
+/// #[table(VariantTypeTable, ty_name = SettingsTable)]
+/// #[table(VariantGenericTable, ty_name = SettingsCounters, impl_ops)]
+/// pub enum Setting {
+///     WindowSize(WindowSize),
+///     MaxFps(MaxFps),
+///     #[table_skip]
+///     Unsupported(Unsupported),
+/// }
+/// ```
+///
+/// An attribute `#[table(..)]` qualified with the name of a derive only applies to that derive,
+/// otherwise it applies to all table derives of the enum.
+#[proc_macro_derive(VariantTypeTable, attributes(table, table_skip))]
+pub fn derive_variant_type_table(enum_stream: TokenStream1) -> TokenStream1 {
+    tables::variant_type_to_variant_type::run_derive(enum_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Derive form of [`macro@variant_generic_table`], which generates the same table without re-emitting the enum.
+///
+/// The settings are provided by the helper attribute `#[table(..)]`,
+/// see [`derive@VariantTypeTable`] for details.
+#[proc_macro_derive(VariantGenericTable, attributes(table, table_skip, default_value))]
+pub fn derive_variant_generic_table(enum_stream: TokenStream1) -> TokenStream1 {
+    tables::variant_type_to_generic::run_derive(enum_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Derive form of [`macro@discriminant_generic_table`], which generates the same table without re-emitting the enum.
+///
+/// The settings are provided by the helper attribute `#[table(..)]`,
+/// see [`derive@VariantTypeTable`] for details.
+///
+/// Every variant needs a value, so the helper attribute `#[table_skip]` is not registered by this derive.
+#[proc_macro_derive(DiscriminantTable, attributes(table, default_value))]
+pub fn derive_discriminant_table(enum_stream: TokenStream1) -> TokenStream1 {
+    tables::discriminant_to_generic::run_derive(enum_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Given an enum with unit variants, generates a compact set of the enum's variants,
+/// backed by the smallest unsigned integer that has one bit per variant (`u8`, `u16`, `u32`, `u64` or `u128`).
+///
+/// The type generated by this macro does not allocate any heap memory, thus it is `no_std` compatible.
+///
+/// ## Example
+///
+/// ```rust ignore
+/// use spire_enum_macros::discriminant_set;
+///
+/// #[discriminant_set]
+/// #[derive(Clone, Copy)]
+/// pub enum Input {
+///     Jump,
+///     Crouch,
+///     Sprint,
+/// }
+/// ```
+///
+/// Generates:
+///
+/// ```rust ignore
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+/// pub struct InputDiscriminantSet {
+///     bits: u8,
+/// }
+///
+/// // Constructs a set from a list of variants, usable in `const` contexts.
+/// macro_rules! input_discriminant_set { .. }
+/// ```
+///
+/// ## Generated Impls
+///
+/// The code emitted with all generated impls includes documentation, this is merely a summary.
+///
+/// ```rust ignore
+/// // Constructors
+/// pub const fn new() -> Self;
+/// pub const fn all() -> Self;
+/// pub const fn from_bits_truncate(bits: Bits) -> Self;
+/// pub const fn bits(&self) -> Bits;
+///
+/// // Queries/Modifications
+/// pub const fn contains(&self, var: Enum) -> bool;
+/// pub const fn insert(&mut self, var: Enum) -> bool;
+/// pub const fn remove(&mut self, var: Enum) -> bool;
+/// pub const fn with(self, var: Enum) -> Self;
+/// pub const fn without(self, var: Enum) -> Self;
+/// pub const fn clear(&mut self);
+/// pub const fn len(&self) -> usize;
+/// pub const fn is_empty(&self) -> bool;
+///
+/// // Set operations, also available through the operators `|`, `&`, `-`, `^` and `!`.
+/// pub const fn union(self, other: Self) -> Self;
+/// pub const fn intersection(self, other: Self) -> Self;
+/// pub const fn difference(self, other: Self) -> Self;
+/// pub const fn symmetric_difference(self, other: Self) -> Self;
+/// pub const fn complement(self) -> Self;
+/// pub const fn is_subset(&self, other: &Self) -> bool;
+/// pub const fn is_superset(&self, other: &Self) -> bool;
+/// pub const fn is_disjoint(&self, other: &Self) -> bool;
+///
+/// // Iterates the variants present, in declaration order.
+/// pub const fn iter(&self) -> impl Iterator<Item = Enum>;
+///
+/// // Other impls: `From<Enum>`, `FromIterator<Enum>`, `Extend<Enum>`, `IntoIterator`.
+/// ```
+///
+/// ## Example - Using generated impls
+/// ```rust ignore
+/// const MOVEMENT: InputDiscriminantSet = input_discriminant_set![Crouch, Sprint];
+///
+/// let mut pressed = InputDiscriminantSet::new();
+/// pressed.insert(Input::Jump);
+/// pressed |= MOVEMENT;
+///
+/// assert_eq!(pressed.len(), 3);
+/// assert!((pressed - MOVEMENT).contains(Input::Jump));
+/// assert!((!pressed).is_empty());
+/// ```
+///
+/// ## Settings
+/// Here are the ways you may customize the output code, settings should be comma-separated.
+///
+/// ```rust ignore
+/// #[discriminant_set(
+///     // By default, the set type name is `[EnumName]DiscriminantSet`,
+///     // this allows you to customize the type name.
+///     ty_name = MyCustomName,
+///     // By default, generated code is included in a module named `[enum_name_snake_case]_discriminant_set`,
+///     // this allows you to customize the module name.
+///     mod_name = my_custom_module,
+///     // Add attributes to the generated set type.
+///     // - The attribute list should be comma-separated.
+///     // - There is no need to surround the attributes with `#[  ]`.
+///     attrs(
+///         derive(Debug), // will make the set derive Debug.
+///         some_other_attribute,
+///     ),
+///     // Shorthand for attrs(derive(Debug))
+///     derive(Debug),
+/// )]
+/// ```
+///
+/// The table-only settings (`impl_ops`, `value_ty`, `storage`, `ordinal`, `keys`, `arbitrary`, `proptest` and `capacity`)
+/// are rejected.
+///
+/// The constructor macro is named after the set type in snake case (`input_discriminant_set!` above).
+/// It names the set type and the enum without a path, so both must be in scope wherever the macro is invoked
+/// (e.g. `use defs::{Input, InputDiscriminantSet, input_discriminant_set};`).
+///
+/// `Clone`, `Copy`, `PartialEq`, `Eq`, `Hash` and `Default` are always derived, do not include them in the settings.
+///
+/// ## Conditional compilation
+/// Bits are assigned in declaration order, skipping variants removed by `#[cfg]` attributes.
+///
+/// ## Limitations
+/// - All variants should be unit variants (they cannot have any fields).
+/// - The enum cannot have more than 128 variants.
+/// - `#[table_skip]` is not supported (unless it's meant for another table macro on the same enum).
+#[proc_macro_attribute]
+pub fn discriminant_set(input_stream: TokenStream1, enum_stream: TokenStream1) -> TokenStream1 {
+    tables::discriminant_set::run(input_stream, enum_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Attaches static metadata to each variant of an enum, such as display labels, icons or sort weights.
+///
+/// The metadata type is defined by you, each variant assigns its fields with the attribute `#[meta(..)]`.
+///
+/// ## Example
+///
+/// ```rust ignore
+/// use spire_enum_macros::variant_meta;
+///
+/// pub struct StateMeta {
+///     pub label: &'static str,
+///     pub icon: &'static str,
+///     pub weight: u32,
+///     pub tooltip: &'static str,
+/// }
+///
+/// #[variant_meta(struct = StateMeta, docs = tooltip)]
+/// pub enum State {
+///     /// Standing still.
+///     #[meta(label = "Idle", icon = "idle.png", weight = 3)]
+///     Idle,
+///     /// Moving towards the target.
+///     #[meta(label = "Walking", icon = "walk.png", weight = 1)]
+///     Walking { speed: f32 },
+/// }
+///
+/// assert_eq!(State::Idle.meta().label, "Idle");
+/// assert_eq!(State::METAS[1].tooltip, "Moving towards the target.");
+/// ```
+///
+/// ## Generated Items
+///
+/// ```rust ignore
+/// impl Enum {
+///     // The metadata of each variant, in the order they were declared (Top to bottom).
+///     pub const METAS: [Meta; LEN];
+///     // Returns a reference to the variant's entry in `METAS`.
+///     pub const fn meta(&self) -> &'static Meta;
+/// }
+/// ```
+///
+/// ## Settings
+///
+/// ```rust ignore
+/// #[variant_meta(
+///     // Required, the type of the metadata, must be a struct with named fields.
+///     struct = StateMeta,
+///     // Fills the field `tooltip` with the text of each variant's doc comments (`///`),
+///     // unless the variant assigns the field explicitly with `#[meta(tooltip = ..)]`.
+///     docs = tooltip,
+/// )]
+/// ```
+///
+/// ## Validation
+/// All variants must assign the same fields, any field assigned by one variant but not by another is reported
+/// at compile time, pointing to the variant. Fields of the struct that no variant assigns are reported by the compiler.
+///
+/// The values are evaluated in a `const` context (the `METAS` constant), inside an impl block of the enum.
+#[proc_macro_attribute]
+pub fn variant_meta(settings_stream: TokenStream1, enum_stream: TokenStream1) -> TokenStream1 {
+    variant_meta::run(settings_stream, enum_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Similar to [`macro@variant_type_table`], except each variant may be absent.
+///
+/// Given an enum, generates a struct that contains at most one of each of the enum's variant types,
+/// and several implementations for the generated type as well.
+///
+/// The variant's types are used as keys to access their respective values.
+///
+/// The type generated by this macro does not allocate any heap memory, thus it is `no_std` compatible.
+///
+/// ## Example
+///
+/// ```rust ignore
+/// use spire_enum_macros::variant_type_set;
+///
+/// #[variant_type_set]
+/// pub enum StatusEffect {
+///     Poison(Poison),
+///     Stun(Stun),
+///     Haste(Haste),
+/// }
+/// ```
+///
+/// Generates:
+///
+/// ```rust ignore
+/// pub struct StatusEffectVariantTypeSet {
+///     pub poison: Option<Poison>,
+///     pub stun: Option<Stun>,
+///     pub haste: Option<Haste>,
+/// }
+/// ```
+///
+/// ## Generated Impls
+///
+/// The code of all generated impls includes documentation, this is merely a summary.
+///
+/// ```rust ignore
+/// // Constructor, all variants start absent.
+/// // Also available through `Default::default()`.
+/// pub const fn new() -> Self;
+///
+/// // Get a reference/mut-reference to a given variant based on the generic type, if present.
+/// // - Var: any of the enum's variant types
+/// pub fn get<Var>(&self) -> Option<&Var>;
+/// pub fn get_mut<Var>(&mut self) -> Option<&mut Var>;
+/// pub fn contains<Var>(&self) -> bool;
+///
+/// // Insert/remove variants.
+/// // `insert` returns the value previously stored in the variant's slot.
+/// pub fn insert(&mut self, value: Enum) -> Option<Enum>;
+/// pub fn remove<Var>(&mut self) -> Option<Var>;
+/// pub fn clear(&mut self);
+///
+/// // Amount of variants present.
+/// pub const fn len(&self) -> usize;
+/// pub const fn is_empty(&self) -> bool;
+///
+/// // Iter by reference/mut-reference, only yields the variants present.
+/// // - EnumRef/Mut: an enum where all variants have the same name as the input enum,
+/// // but contain a reference to the variant's type instead of owning it.
+/// pub fn iter<'a>(&'a self) -> impl Iterator<Item = EnumRef<'a>>;
+/// pub fn iter_mut<'a>(&'a mut self) -> impl Iterator<Item = EnumMut<'a>>;
+///
+/// // IntoIter impl
+/// fn into_iter(self) -> impl Iterator<Item = Enum>;
+/// ```
+///
+/// ## Example - Using generated impls
+/// ```rust ignore
+/// let mut effects = StatusEffectVariantTypeSet::new();
+/// assert_eq!(effects.insert(StatusEffect::Poison(Poison { damage: 5 })), None);
+/// assert!(effects.contains::<Poison>());
+/// assert!(!effects.contains::<Stun>());
+///
+/// // Inserting a variant that is already present replaces it.
+/// let old = effects.insert(StatusEffect::Poison(Poison { damage: 8 }));
+/// assert!(matches!(old, Some(StatusEffect::Poison(Poison { damage: 5 }))));
+///
+/// assert_eq!(effects.remove::<Poison>(), Some(Poison { damage: 8 }));
+/// assert!(effects.is_empty());
+/// ```
+///
+/// ## Settings
+/// Accepts the same settings as [`macro@variant_type_table`], the default names are
+/// `[EnumName]VariantTypeSet` (type) and `[enum_name_snake_case]_variant_type_set` (module).
+///
+/// ## Limitations
+/// Same as [`macro@variant_type_table`].
+#[proc_macro_attribute]
+pub fn variant_type_set(input_stream: TokenStream1, enum_stream: TokenStream1) -> TokenStream1 {
+    tables::variant_type_set::run(input_stream, enum_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+/// Generates a collection that stores the values of an enum segregated by variant ("struct of vecs"),
+/// with one vector per variant type.
+///
+/// Values are pushed as the enum, but stored unwrapped, which allows processing all values of a variant
+/// in batches, without branching on the variant.
+///
+/// The variant's types are used as keys to access their respective vectors.
+///
+/// By default, each variant is stored in a `Vec`, which requires the feature `alloc` of `spire_enum`.
+/// With the setting `capacity = N`, each variant is instead stored in a
+/// `spire_enum::prelude::FixedVec<T, N>`, which does not allocate any heap memory,
+/// thus it is `no_std` compatible.
+///
+/// ## Example
+///
+/// ```rust ignore
+/// use spire_enum_macros::variant_vecs;
+///
+/// #[delegated_enum]
+/// #[variant_vecs]
+/// pub enum Particle {
+///     Spark(Spark),
+///     Smoke(Smoke),
+/// }
+/// ```
+///
+/// Generates:
+///
+/// ```rust ignore
+/// pub struct ParticleVariantVecs {
+///     pub spark: Vec<Spark>,
+///     pub smoke: Vec<Smoke>,
+/// }
+///
+/// macro_rules! for_each_delegated_particle_variant_vecs { .. }
+/// ```
+///
+/// ## Generated Impls
+///
+/// The code of all generated impls includes documentation, this is merely a summary.
+///
+/// ```rust ignore
+/// // Constructor, all vectors start empty.
+/// // Also available through `Default::default()`.
+/// pub const fn new() -> Self;
+///
+/// // Appends the value to the vector of its variant.
+/// // With `capacity = N`, returns `Err(value)` if that vector is full.
+/// pub fn push(&mut self, value: Enum);
+///
+/// // Access the vector of a given variant based on the generic type.
+/// // - Var: any of the enum's variant types
+/// pub fn get<Var>(&self) -> &Vec<Var>;
+/// pub fn get_mut<Var>(&mut self) -> &mut Vec<Var>;
+/// pub fn iter<Var>(&self) -> core::slice::Iter<'_, Var>;
+/// pub fn iter_mut<Var>(&mut self) -> core::slice::IterMut<'_, Var>;
+/// pub fn len_of<Var>(&self) -> usize;
+/// pub fn retain<Var>(&mut self, f: impl FnMut(&Var) -> bool);
+///
+/// // Amount of values of all variants.
+/// pub fn len(&self) -> usize;
+/// pub fn is_empty(&self) -> bool;
+/// pub fn clear(&mut self);
+///
+/// // Removes all values, yielding them wrapped in the enum, grouped by variant.
+/// pub fn drain(&mut self) -> impl Iterator<Item = Enum>;
+///
+/// // Only without `capacity = N`
+/// impl Extend<Enum>;
+/// impl FromIterator<Enum>;
+/// ```
+///
+/// ## Example - Using generated impls
+/// ```rust ignore
+/// let mut particles = ParticleVariantVecs::new();
+/// particles.push(Particle::Spark(Spark { heat: 5 }));
+/// particles.push(Particle::Smoke(Smoke { density: 2 }));
+/// particles.push(Particle::Spark(Spark { heat: 1 }));
+/// assert_eq!(particles.len_of::<Spark>(), 2);
+///
+/// // Batch processing of a single variant
+/// particles.iter_mut::<Spark>().for_each(|spark| spark.heat -= 1);
+/// particles.retain::<Spark>(|spark| spark.heat > 0);
+///
+/// // Runs the same expression on every value, with one loop per variant.
+/// // Each value is delegated through `delegate_particle!` (generated by `delegated_enum`, which is required),
+/// // so `#[delegator]` fields and `#[delegate_via(..)]` are honoured.
+/// for_each_delegated_particle_variant_vecs!(particles => |particle| particle.update());
+///
+/// let all: Vec<Particle> = particles.drain().collect();
+/// ```
+///
+/// ## Settings
+/// Accepts the same settings as [`macro@variant_type_table`], the default names are
+/// `[EnumName]VariantVecs` (type) and `[enum_name_snake_case]_variant_vecs` (module).
+///
+/// Additionally accepts:
+/// - `capacity = N`: stores each variant in a `FixedVec` of capacity `N` instead of a `Vec`,
+///   `N` is also exposed as the constant `[TYPE_NAME]_CAPACITY` (e.g. `PARTICLE_VARIANT_VECS_CAPACITY`).
+///   Requires all variant types to implement `Default`.
+///
+/// The settings `arbitrary` and `proptest` are not supported.
+///
+/// ## Limitations
+/// Same as [`macro@variant_type_table`], except that `#[table_skip]` is not supported, every variant
+/// needs a vector.
+#[proc_macro_attribute]
+pub fn variant_vecs(input_stream: TokenStream1, enum_stream: TokenStream1) -> TokenStream1 {
+    tables::variant_vecs::run(input_stream, enum_stream)
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
 fn delegate_macro_ident(enum_ident: &Ident) -> Ident {
     use convert_case::{Case, Casing};
     let mut ident = enum_ident.to_string();
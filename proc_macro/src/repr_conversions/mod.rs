@@ -0,0 +1,259 @@
+use syn::{punctuated::Punctuated, token::Comma};
+
+use super::*;
+
+const REPR_INTS: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+struct SaneVariant {
+    cfg_attrs: Any<Attribute<CfgMeta>>,
+    ident: Ident,
+    /// The discriminant, if it can be known without evaluating `#[cfg]` attributes, see [`static_discriminants`].
+    static_discriminant: Option<i128>,
+}
+
+pub fn run(settings_stream: TokenStream1, enum_stream: TokenStream1) -> Result<TokenStream> {
+    let settings = TokenStream::from(settings_stream);
+    if !settings.is_empty() {
+        bail!(settings => "`#[repr_conversions]` does not accept any settings");
+    }
+
+    let input_enum = syn::parse::<Enum<SynMeta, SynMeta>>(enum_stream)?;
+    let repr = find_repr(&input_enum)?;
+
+    if let _Some(generics) = &input_enum.generics {
+        bail!(generics => "`#[repr_conversions]` cannot be placed on enums with generics");
+    }
+
+    for var in input_enum.variants.iter() {
+        if var.fields.len() > 0 {
+            bail!(var.fields => "`#[repr_conversions]` can only be placed on fieldless enums");
+        }
+    }
+
+    let variants = static_discriminants(&input_enum);
+    check_duplicates(&variants)?;
+
+    let enum_ident = &input_enum.ident;
+    let enum_name = enum_ident.to_string();
+    let var_cfgs = variants
+        .iter()
+        .map(|var| &var.cfg_attrs)
+        .collect::<Vec<_>>();
+    let var_idents = variants.iter().map(|var| &var.ident).collect::<Vec<_>>();
+
+    let len = quote! {{
+        let mut count = 0;
+
+        #(
+            #var_cfgs
+            {
+                count += 1;
+            }
+        )*
+
+        count
+    }};
+
+    let docs_discriminants = docs_tokens(format!(
+        "The discriminant of each variant of [`{enum_ident}`], in the order they were declared (Top to bottom).\n\n\
+         Variants disabled by `#[cfg]` attributes are not included.\n\n\
+         This constant was generated by an invocation of the macro [`repr_conversions`](spire_enum_macros::repr_conversions)."
+    ));
+
+    let docs_from_repr = docs_tokens(format!(
+        "Returns the variant whose discriminant is `value`, or `None` if no variant of [`{enum_ident}`] has it.\n\n\
+         See also the implementation of [`TryFrom<{repr}>`](TryFrom)."
+    ));
+
+    Ok(quote! {
+        #input_enum
+
+        #[allow(unused)]
+        impl #enum_ident {
+            #docs_discriminants
+            pub const DISCRIMINANTS: [#repr; #len] = [
+                #(
+                    #var_cfgs
+                    { Self::#var_idents as #repr }
+                ),*
+            ];
+
+            #docs_from_repr
+            pub const fn from_repr(value: #repr) -> ::core::option::Option<Self> {
+                #(
+                    #var_cfgs
+                    {
+                        if value == Self::#var_idents as #repr {
+                            return ::core::option::Option::Some(Self::#var_idents);
+                        }
+                    }
+                )*
+
+                ::core::option::Option::None
+            }
+        }
+
+        impl ::core::convert::From<#enum_ident> for #repr {
+            #[doc = "Returns the discriminant of `var`."]
+            fn from(var: #enum_ident) -> Self {
+                var as #repr
+            }
+        }
+
+        impl ::core::convert::TryFrom<#repr> for #enum_ident {
+            type Error = ::spire_enum::prelude::InvalidRepr<#repr>;
+
+            #[doc = "See [`from_repr`](Self::from_repr)."]
+            fn try_from(value: #repr) -> ::core::result::Result<Self, Self::Error> {
+                match Self::from_repr(value) {
+                    ::core::option::Option::Some(var) => ::core::result::Result::Ok(var),
+                    ::core::option::Option::None => {
+                        ::core::result::Result::Err(::spire_enum::prelude::InvalidRepr::new(#enum_name, value))
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Returns the integer type in the enum's attribute `#[repr(..)]`.
+fn find_repr(input: &Enum<SynMeta, SynMeta>) -> Result<Ident> {
+    for attr in input.attrs.iter() {
+        let SynMeta::List(list) = &*attr.inner else {
+            continue;
+        };
+
+        if !list.path.is_ident("repr") {
+            continue;
+        }
+
+        let reprs = list.parse_args_with(Punctuated::<Ident, Comma>::parse_terminated)?;
+        if let Some(repr) = reprs
+            .into_iter()
+            .find(|repr| REPR_INTS.iter().any(|int| repr == int))
+        {
+            return Ok(repr);
+        }
+    }
+
+    Err(Error::new(
+        input.ident.span(),
+        "`#[repr_conversions]` requires the enum to have an integer representation.\n\
+         Help: Add an attribute such as `#[repr(u8)]` to the enum",
+    ))
+}
+
+/// Computes the discriminants that do not depend on `#[cfg]` attributes:
+/// - Explicit discriminants that are integer literals.
+/// - Implicit discriminants (previous + 1), as long as all variants up to the previous explicit one are not
+///   conditionally compiled.
+///
+/// The discriminants of the enabled variants are already checked by the compiler, the static ones allow reporting
+/// duplicates that only happen with other combinations of `#[cfg]` attributes.
+fn static_discriminants(input: &Enum<SynMeta, SynMeta>) -> Vec<SaneVariant> {
+    let mut next = Some(0_i128);
+
+    input
+        .variants
+        .iter()
+        .map(|var| {
+            let cfg_attrs = parse_cfg_attrs(var.attrs.clone());
+
+            let static_discriminant = match &var.discriminant {
+                _Some(InputDiscriminant { expr, .. }) => literal_value(expr),
+                _None => next,
+            };
+
+            next = if cfg_attrs.is_empty() {
+                static_discriminant.and_then(|value| value.checked_add(1))
+            } else {
+                None
+            };
+
+            SaneVariant {
+                cfg_attrs,
+                ident: var.ident.clone(),
+                static_discriminant,
+            }
+        })
+        .collect()
+}
+
+fn literal_value(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(int),
+            ..
+        }) => int.base10_parse().ok(),
+        Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal_value(expr).and_then(i128::checked_neg),
+        Expr::Paren(syn::ExprParen { expr, .. }) | Expr::Group(syn::ExprGroup { expr, .. }) => {
+            literal_value(expr)
+        }
+        _ => None,
+    }
+}
+
+/// Errors if two variants have the same static discriminant, unless they are mutually exclusive:
+/// one has the attribute `#[cfg(P)]` and the other has `#[cfg(not(P))]`.
+fn check_duplicates(variants: &[SaneVariant]) -> Result<()> {
+    let predicates = variants
+        .iter()
+        .map(|var| {
+            var.cfg_attrs
+                .iter()
+                .map(|attr| TokenStream::clone(&attr.inner.tokens))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let is_exclusive = |lhs: &[TokenStream], rhs: &[TokenStream]| {
+        let negates = |pred: &TokenStream, other: &TokenStream| {
+            other.to_string() == quote! { not(#pred) }.to_string()
+        };
+
+        lhs.iter().any(|pred| {
+            rhs.iter()
+                .any(|other| negates(pred, other) || negates(other, pred))
+        })
+    };
+
+    let mut errors = None::<Error>;
+
+    for (idx, var) in variants.iter().enumerate() {
+        let Some(value) = var.static_discriminant else {
+            continue;
+        };
+
+        let first = variants[..idx].iter().enumerate().find(|(prev_idx, prev)| {
+            prev.static_discriminant == Some(value)
+                && !is_exclusive(&predicates[*prev_idx], &predicates[idx])
+        });
+
+        if let Some((_, first)) = first {
+            let err = Error::new(
+                var.ident.span(),
+                format!(
+                    "variants `{}` and `{}` have the same discriminant (`{value}`) when both are enabled by their \
+                     `#[cfg]` attributes",
+                    first.ident, var.ident
+                ),
+            );
+
+            match &mut errors {
+                Some(errors) => errors.combine(err),
+                None => errors = Some(err),
+            }
+        }
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(()),
+    }
+}
@@ -4,3 +4,29 @@ pub fn docs_tokens(string: impl AsRef<str>) -> TokenStream {
     let str = string.as_ref();
     quote! { #[doc = #str] }
 }
+
+/// Returns the text of the doc comments (`///` or `#[doc = ".."]`) in `metas`, one line per attribute.
+///
+/// The space that follows `///` is removed from each line, returns `None` if there are no doc comments.
+pub fn docs_from_metas<'a>(metas: impl IntoIterator<Item = &'a SynMeta>) -> Option<String> {
+    let lines = metas
+        .into_iter()
+        .filter_map(|meta| {
+            match meta {
+                SynMeta::NameValue(syn::MetaNameValue {
+                    path,
+                    value:
+                        Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(str),
+                            ..
+                        }),
+                    ..
+                }) if path.is_ident("doc") => Some(str.value()),
+                _ => None,
+            }
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_owned).unwrap_or(line))
+        .collect::<Vec<_>>();
+
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
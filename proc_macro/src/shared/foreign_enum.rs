@@ -0,0 +1,72 @@
+use super::*;
+
+/// The input of the function-like macros for enums defined elsewhere (such as other crates),
+/// e.g. `foreign_delegated_enum!` or `foreign_variant_type_table!`.
+///
+/// Syntax: `path::Enum<Generics> { Variants }, settings`, the variants are restated as they appear in the
+/// definition of the enum.
+pub struct ForeignEnum<VarAttr, FieldsAttr> {
+    pub path: Path,
+    pub input_enum: Enum<VarAttr, FieldsAttr>,
+    pub settings: TokenStream,
+}
+
+impl<VarAttr, FieldsAttr> Parse for ForeignEnum<VarAttr, FieldsAttr>
+where Enum<VarAttr, FieldsAttr>: Parse
+{
+    fn parse(input: ParseStream) -> Result<Self> {
+        let path = Path::parse_mod_style(input)?;
+        let generics = input.parse::<syn::Generics>()?;
+        let content;
+        syn::braced!(content in input);
+        let variants = content.parse::<TokenStream>()?;
+
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+
+        let ident = match path.segments.last() {
+            Some(segment) => &segment.ident,
+            None => bail!(path => "expected the path of an enum"),
+        };
+
+        let input_enum = syn::parse2(quote! { enum #ident #generics { #variants } })?;
+
+        Ok(ForeignEnum {
+            path,
+            input_enum,
+            settings: input.parse()?,
+        })
+    }
+}
+
+/// Generates a function that matches the foreign enum exhaustively with the restated variants,
+/// which fails to compile if the enum's definition gains (or loses) a variant.
+///
+/// `enum_path` is how the variants are named (`#enum_path::Variant`), `enum_ty` is the enum's type, including its
+/// generic arguments.
+pub fn tokenize_exhaustiveness_check(
+    enum_path: &impl ToTokens,
+    enum_ty: &impl ToTokens,
+    gen_params: &impl ToTokens,
+    where_clause: &impl ToTokens,
+    var_cfgs: impl IntoIterator<Item = impl ToTokens>,
+    var_idents: impl IntoIterator<Item = impl ToTokens>,
+) -> TokenStream {
+    let var_cfgs = var_cfgs.into_iter();
+    let var_idents = var_idents.into_iter();
+
+    quote! {
+        const _: () = {
+            #[allow(unused)]
+            fn __exhaustiveness_check #gen_params (value: &#enum_ty) #where_clause {
+                match value {
+                    #(
+                        #var_cfgs
+                        #enum_path::#var_idents { .. } => {}
+                    )*
+                }
+            }
+        };
+    }
+}
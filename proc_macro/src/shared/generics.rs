@@ -141,6 +141,40 @@ impl Optional<SaneGenerics> {
             _None => Default::default(),
         }
     }
+
+    /// Returns the where clause of the generics (if any), extended with `bound` on each type parameter.
+    pub fn where_clause_with_bound(&self, bound: TokenStream) -> TokenStream {
+        let generics = match self {
+            _Some(generics) => Some(generics),
+            _None => None,
+        };
+
+        let predicates = generics
+            .and_then(|generics| {
+                generics
+                    .where_clause
+                    .as_ref()
+                    .map(|clause| &clause.predicates)
+            })
+            .into_iter()
+            .flatten();
+
+        let type_params = generics
+            .into_iter()
+            .flat_map(|generics| generics.input.params.inner.iter())
+            .filter_map(|param| {
+                match param {
+                    GenericParam::Type(TypeParam { ident, .. }) => Some(ident),
+                    _ => None,
+                }
+            });
+
+        quote! {
+            where
+                #( #predicates, )*
+                #( #type_params: #bound, )*
+        }
+    }
 }
 
 impl CollectIdents for SaneGenerics {
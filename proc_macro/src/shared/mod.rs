@@ -3,6 +3,7 @@ use super::*;
 mod attribute;
 mod documentation;
 mod enum_;
+mod foreign_enum;
 mod generics;
 mod optional;
 mod punctuated;
@@ -13,6 +14,7 @@ pub use Optional::{_None, _Some};
 pub use attribute::*;
 pub use documentation::*;
 pub use enum_::*;
+pub use foreign_enum::*;
 pub use generics::*;
 pub use optional::*;
 pub use punctuated::*;
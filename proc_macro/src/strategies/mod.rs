@@ -0,0 +1,436 @@
+//! Generation of the `Arbitrary` impls of the crates `arbitrary` and `proptest` (settings `arbitrary` and
+//! `proptest`), shared by `delegated_enum` and the table macros.
+
+use proc_macro2::Literal;
+
+use super::*;
+
+/// Proptest only implements `Strategy` for tuples of up to 12 elements.
+const MAX_TUPLE_LEN: usize = 12;
+
+/// One of the values that a generated `Arbitrary` impl may produce: a variant of an enum, or a struct.
+pub struct ArbitraryCase<'a> {
+    pub cfg_attrs: Option<&'a Any<Attribute<CfgMeta>>>,
+    /// How often this case is generated, relative to the other cases (`#[weight(n)]`).
+    pub weight: u32,
+    /// The path used to construct the value, such as `Enum::Variant` or `Struct`.
+    pub path: TokenStream,
+    pub fields: ArbitraryFields<'a>,
+}
+
+pub enum ArbitraryFields<'a> {
+    Named(Vec<(&'a Ident, TokenStream)>),
+    Unnamed(Vec<TokenStream>),
+    Unit,
+}
+
+/// A value stored in a table, see [`arbitrary_table_impl`] and [`proptest_table_impl`].
+pub struct TableValue<'a> {
+    pub cfg_attrs: &'a Any<Attribute<CfgMeta>>,
+    pub ty: TokenStream,
+}
+
+/// The generics of a generated impl: the list of parameters (without brackets) and the where clause.
+pub struct ImplGenerics {
+    pub params_list:  TokenStream,
+    pub where_clause: TokenStream,
+}
+
+impl ImplGenerics {
+    /// Bounds each type parameter of `generics` with `bound`.
+    pub fn bounded(generics: &Optional<SaneGenerics>, bound: TokenStream) -> Self {
+        ImplGenerics {
+            params_list:  generics.stream_params_list(),
+            where_clause: generics.where_clause_with_bound(bound),
+        }
+    }
+}
+
+pub fn arbitrary_bound() -> TokenStream {
+    quote! { ::spire_enum::arbitrary::Arbitrary<'__arbitrary> }
+}
+
+pub fn proptest_bound() -> TokenStream {
+    quote! { ::spire_enum::proptest::arbitrary::Arbitrary + 'static }
+}
+
+/// Generates the implementation of `arbitrary::Arbitrary` for `self_ty`, which picks one of the `cases` according
+/// to their weights, then fills the fields of the picked case.
+///
+/// `generics` must be bounded by [`arbitrary_bound`], which uses the lifetime `'__arbitrary`.
+pub fn arbitrary_impl(
+    self_ty: &TokenStream,
+    generics: &ImplGenerics,
+    cases: &[ArbitraryCase],
+) -> TokenStream {
+    let weights = cases
+        .iter()
+        .map(|case| Literal::u32_suffixed(case.weight))
+        .collect::<Vec<_>>();
+    let var_cfgs = cases.iter().map(|case| case.cfg_attrs).collect::<Vec<_>>();
+    let constructs = cases.iter().map(arbitrary_construct).collect::<Vec<_>>();
+
+    let body = match constructs.as_slice() {
+        // Structs have a single case, which can't be disabled.
+        [construct] if cases[0].cfg_attrs.is_none() => {
+            quote! { ::core::result::Result::Ok(#construct) }
+        }
+        _ => {
+            quote! {
+                const TOTAL_WEIGHT: u32 = {
+                    let mut total = 0;
+                    #(
+                        #var_cfgs
+                        {
+                            total += #weights;
+                        }
+                    )*
+                    total
+                };
+
+                let ::core::option::Option::Some(max) = TOTAL_WEIGHT.checked_sub(1) else {
+                    // All cases are disabled by `#[cfg]` attributes.
+                    return ::core::result::Result::Err(::spire_enum::arbitrary::Error::EmptyChoose);
+                };
+
+                let mut pick = __u.int_in_range(0..=max)?;
+
+                #(
+                    #var_cfgs
+                    {
+                        if pick < #weights {
+                            return ::core::result::Result::Ok(#constructs);
+                        }
+
+                        pick -= #weights;
+                    }
+                )*
+
+                ::core::unreachable!()
+            }
+        }
+    };
+
+    wrap_arbitrary_impl(self_ty, generics, body)
+}
+
+/// Generates the implementation of `arbitrary::Arbitrary` for the table `self_ty`, given an expression that
+/// constructs the table from an expression for each of its values (see [`TableStorage::construct`]).
+///
+/// [`TableStorage::construct`]: crate::tables
+pub fn arbitrary_table_impl(
+    self_ty: &TokenStream,
+    generics: &ImplGenerics,
+    construct: impl FnOnce(&[TokenStream]) -> TokenStream,
+    values: &[TableValue],
+) -> TokenStream {
+    let exprs = values
+        .iter()
+        .map(|_| quote! { ::spire_enum::arbitrary::Arbitrary::arbitrary(__u)? })
+        .collect::<Vec<_>>();
+
+    let construct = construct(&exprs);
+    wrap_arbitrary_impl(self_ty, generics, quote! { ::core::result::Result::Ok(#construct) })
+}
+
+fn wrap_arbitrary_impl(
+    self_ty: &TokenStream,
+    generics: &ImplGenerics,
+    body: TokenStream,
+) -> TokenStream {
+    let ImplGenerics {
+        params_list,
+        where_clause,
+    } = generics;
+
+    quote! {
+        impl<'__arbitrary, #params_list> ::spire_enum::arbitrary::Arbitrary<'__arbitrary> for #self_ty #where_clause {
+            fn arbitrary(
+                __u: &mut ::spire_enum::arbitrary::Unstructured<'__arbitrary>,
+            ) -> ::spire_enum::arbitrary::Result<Self> {
+                #body
+            }
+        }
+    }
+}
+
+fn arbitrary_construct(case: &ArbitraryCase) -> TokenStream {
+    let path = &case.path;
+    let value = quote! { ::spire_enum::arbitrary::Arbitrary::arbitrary(__u)? };
+
+    match &case.fields {
+        ArbitraryFields::Named(fields) => {
+            let idents = fields.iter().map(|(ident, _)| ident);
+            quote! { #path { #( #idents: #value ),* } }
+        }
+        ArbitraryFields::Unnamed(fields) => {
+            let values = fields.iter().map(|_| &value);
+            quote! { #path( #(#values),* ) }
+        }
+        ArbitraryFields::Unit => path.clone(),
+    }
+}
+
+/// Generates the implementation of `proptest::arbitrary::Arbitrary` for `self_ty`, whose strategy picks one of the
+/// `cases` according to their weights, then generates the fields of the picked case.
+///
+/// `generics` must be bounded by [`proptest_bound`].
+pub fn proptest_impl(
+    self_ty: &TokenStream,
+    generics: &ImplGenerics,
+    cases: &[ArbitraryCase],
+) -> TokenStream {
+    let strategies = cases.iter().map(proptest_case_strategy).collect::<Vec<_>>();
+
+    let body = match strategies.as_slice() {
+        [strategy] if cases[0].cfg_attrs.is_none() => strategy.clone(),
+        _ => {
+            let weights = cases.iter().map(|case| Literal::u32_suffixed(case.weight));
+            let var_cfgs = cases.iter().map(|case| case.cfg_attrs);
+
+            quote! {
+                // Panics if all cases are disabled by `#[cfg]` attributes.
+                ::spire_enum::proptest::strategy::Strategy::boxed(
+                    ::spire_enum::proptest::strategy::Union::new_weighted(
+                        ::core::iter::Iterator::collect(::core::iter::IntoIterator::into_iter([
+                            #(
+                                #var_cfgs
+                                { (#weights, #strategies) }
+                            ),*
+                        ])),
+                    ),
+                )
+            }
+        }
+    };
+
+    wrap_proptest_impl(self_ty, generics, body)
+}
+
+/// Returns a boxed strategy that generates the fields of `case`, then constructs it.
+fn proptest_case_strategy(case: &ArbitraryCase) -> TokenStream {
+    let path = &case.path;
+
+    let (tys, idents, construct) = match &case.fields {
+        ArbitraryFields::Named(fields) => {
+            let idents = fields
+                .iter()
+                .map(|(ident, _)| ident.to_token_stream())
+                .collect::<Vec<_>>();
+            let tys = fields.iter().map(|(_, ty)| ty.clone()).collect::<Vec<_>>();
+            let construct = quote! { #path { #(#idents),* } };
+            (tys, idents, construct)
+        }
+        ArbitraryFields::Unnamed(tys) => {
+            let idents = (0..tys.len())
+                .map(|idx| format_ident!("__{idx}").to_token_stream())
+                .collect::<Vec<_>>();
+            let construct = quote! { #path( #(#idents),* ) };
+            (tys.clone(), idents, construct)
+        }
+        ArbitraryFields::Unit => {
+            return quote! {
+                ::spire_enum::proptest::strategy::Strategy::boxed(
+                    ::spire_enum::proptest::strategy::LazyJust::new(|| #path)
+                )
+            };
+        }
+    };
+
+    let strategies = tys
+        .iter()
+        .map(|ty| quote! { ::spire_enum::proptest::arbitrary::any::<#ty>() })
+        .collect::<Vec<_>>();
+
+    let strategy = nested_tuple(&strategies);
+    let pattern = nested_tuple(&idents);
+
+    quote! {
+        ::spire_enum::proptest::strategy::Strategy::boxed(
+            ::spire_enum::proptest::strategy::Strategy::prop_map(#strategy, |#pattern| #construct)
+        )
+    }
+}
+
+/// Returns a tuple containing `items`, nested in chunks of [`MAX_TUPLE_LEN`] if there are too many of them.
+fn nested_tuple(items: &[TokenStream]) -> TokenStream {
+    if items.len() <= MAX_TUPLE_LEN {
+        return quote! { ( #(#items,)* ) };
+    }
+
+    let chunks = items
+        .chunks(MAX_TUPLE_LEN)
+        .map(nested_tuple)
+        .collect::<Vec<_>>();
+
+    nested_tuple(&chunks)
+}
+
+/// Generates the implementation of `proptest::arbitrary::Arbitrary` for the table `self_ty`, given an expression
+/// that constructs the table from an expression for each of its values (see [`TableStorage::construct`]).
+///
+/// The values of a table may be disabled by `#[cfg]` attributes, which can't be applied to the elements of a tuple,
+/// so the strategy and its value tree are generated as structs with one field per value.
+/// Like the strategies of tuples, the values are simplified one at a time, in order.
+///
+/// [`TableStorage::construct`]: crate::tables
+pub fn proptest_table_impl(
+    self_ty: &TokenStream,
+    generics: &ImplGenerics,
+    construct: impl FnOnce(&[TokenStream]) -> TokenStream,
+    values: &[TableValue],
+) -> TokenStream {
+    let ImplGenerics {
+        params_list,
+        where_clause,
+    } = generics;
+
+    let fields = (0..values.len())
+        .map(|idx| format_ident!("__{idx}"))
+        .collect::<Vec<_>>();
+    let var_cfgs = values
+        .iter()
+        .map(|value| value.cfg_attrs)
+        .collect::<Vec<_>>();
+    let tys = values.iter().map(|value| &value.ty).collect::<Vec<_>>();
+
+    let construct = construct(
+        &fields
+            .iter()
+            .map(|field| quote! { ::spire_enum::proptest::strategy::ValueTree::current(&self.#field) })
+            .collect::<Vec<_>>(),
+    );
+
+    let marker_ty = quote! { ::core::marker::PhantomData<fn() -> #self_ty> };
+
+    quote! {
+        const _: () = {
+            struct __Strategy<#params_list> #where_clause {
+                #(
+                    #var_cfgs
+                    #fields: ::spire_enum::proptest::strategy::BoxedStrategy<#tys>,
+                )*
+                __marker: #marker_ty,
+            }
+
+            impl<#params_list> ::core::fmt::Debug for __Strategy<#params_list> #where_clause {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.write_str(::core::stringify!(#self_ty))
+                }
+            }
+
+            struct __ValueTree<#params_list> #where_clause {
+                #(
+                    #var_cfgs
+                    #fields: <::spire_enum::proptest::strategy::BoxedStrategy<#tys> as ::spire_enum::proptest::strategy::Strategy>::Tree,
+                )*
+                /// The index of the value being simplified.
+                shrinking: usize,
+                __marker: #marker_ty,
+            }
+
+            impl<#params_list> ::spire_enum::proptest::strategy::Strategy for __Strategy<#params_list> #where_clause {
+                type Tree = __ValueTree<#params_list>;
+                type Value = #self_ty;
+
+                fn new_tree(
+                    &self,
+                    runner: &mut ::spire_enum::proptest::test_runner::TestRunner,
+                ) -> ::spire_enum::proptest::strategy::NewTree<Self> {
+                    ::core::result::Result::Ok(__ValueTree {
+                        #(
+                            #var_cfgs
+                            #fields: ::spire_enum::proptest::strategy::Strategy::new_tree(&self.#fields, runner)?,
+                        )*
+                        shrinking: 0,
+                        __marker: ::core::marker::PhantomData,
+                    })
+                }
+            }
+
+            impl<#params_list> ::spire_enum::proptest::strategy::ValueTree for __ValueTree<#params_list> #where_clause {
+                type Value = #self_ty;
+
+                fn current(&self) -> Self::Value {
+                    #construct
+                }
+
+                fn simplify(&mut self) -> bool {
+                    let mut idx = 0;
+
+                    #(
+                        #var_cfgs
+                        {
+                            if idx >= self.shrinking
+                                && ::spire_enum::proptest::strategy::ValueTree::simplify(&mut self.#fields)
+                            {
+                                self.shrinking = idx;
+                                return true;
+                            }
+
+                            idx += 1;
+                        }
+                    )*
+
+                    false
+                }
+
+                fn complicate(&mut self) -> bool {
+                    let mut idx = 0;
+
+                    #(
+                        #var_cfgs
+                        {
+                            if idx == self.shrinking {
+                                return ::spire_enum::proptest::strategy::ValueTree::complicate(&mut self.#fields);
+                            }
+
+                            idx += 1;
+                        }
+                    )*
+
+                    false
+                }
+            }
+
+            impl<#params_list> ::spire_enum::proptest::arbitrary::Arbitrary for #self_ty #where_clause {
+                type Parameters = ();
+                type Strategy = ::spire_enum::proptest::strategy::BoxedStrategy<Self>;
+
+                fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                    ::spire_enum::proptest::strategy::Strategy::boxed(__Strategy {
+                        #(
+                            #var_cfgs
+                            #fields: ::spire_enum::proptest::strategy::Strategy::boxed(
+                                ::spire_enum::proptest::arbitrary::any::<#tys>()
+                            ),
+                        )*
+                        __marker: ::core::marker::PhantomData,
+                    })
+                }
+            }
+        };
+    }
+}
+
+fn wrap_proptest_impl(
+    self_ty: &TokenStream,
+    generics: &ImplGenerics,
+    body: TokenStream,
+) -> TokenStream {
+    let ImplGenerics {
+        params_list,
+        where_clause,
+    } = generics;
+
+    quote! {
+        impl<#params_list> ::spire_enum::proptest::arbitrary::Arbitrary for #self_ty #where_clause {
+            type Parameters = ();
+            type Strategy = ::spire_enum::proptest::strategy::BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                #body
+            }
+        }
+    }
+}
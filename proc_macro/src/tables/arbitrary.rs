@@ -0,0 +1,72 @@
+use strategies::{ImplGenerics, TableValue};
+
+use super::*;
+
+/// The generics of the table, used to bound its type parameters in the impls generated by [`tokenize_arbitrary_impls`].
+pub(super) enum TableGenerics<'a> {
+    /// The generics of the enum, for tables that store the variants' types.
+    Enum(&'a Optional<SaneGenerics>),
+    /// The type parameter of the values, for generic tables.
+    Value(&'a Ident),
+}
+
+impl TableGenerics<'_> {
+    fn bounded(&self, bound: TokenStream) -> ImplGenerics {
+        match self {
+            TableGenerics::Enum(generics) => ImplGenerics::bounded(generics, bound),
+            TableGenerics::Value(gen_t) => {
+                ImplGenerics {
+                    params_list:  gen_t.to_token_stream(),
+                    where_clause: quote! { where #gen_t: #bound },
+                }
+            }
+        }
+    }
+}
+
+/// Generates the `Arbitrary` impls requested by the settings `arbitrary` and `proptest`, for the table `table_ty`.
+///
+/// Each value is generated independently, `construct` must return an expression that constructs the table given
+/// an expression for each variant's value (see [`TableStorage::construct`]).
+pub(super) fn tokenize_arbitrary_impls(
+    arbitrary: &Optional<kw_arbitrary>,
+    proptest: &Optional<kw_proptest>,
+    table_ty: &TokenStream,
+    generics: TableGenerics,
+    var_cfgs: &[&Any<Attribute<CfgMeta>>],
+    value_tys: &[TokenStream],
+    construct: impl Fn(&[TokenStream]) -> TokenStream,
+) -> TokenStream {
+    let values = var_cfgs
+        .iter()
+        .zip(value_tys)
+        .map(|(cfg_attrs, ty)| {
+            TableValue {
+                cfg_attrs,
+                ty: ty.clone(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut stream = TokenStream::new();
+
+    if arbitrary.is_some() {
+        stream.extend(strategies::arbitrary_table_impl(
+            table_ty,
+            &generics.bounded(strategies::arbitrary_bound()),
+            &construct,
+            &values,
+        ));
+    }
+
+    if proptest.is_some() {
+        stream.extend(strategies::proptest_table_impl(
+            table_ty,
+            &generics.bounded(strategies::proptest_bound()),
+            &construct,
+            &values,
+        ));
+    }
+
+    stream
+}
@@ -0,0 +1,117 @@
+use super::*;
+
+/// How the method that resets a single variant's value is generated.
+pub(super) enum Reset<'a> {
+    /// `reset::<Var>()`, the variant is resolved by the associated constant `__KEY` of the trait `key_trait`.
+    ByType { key_trait: &'a Ident },
+    /// `reset(var)`, `var` is matched with the same patterns as in `default_value`, then the value at the matching
+    /// place is replaced.
+    ByValue { places: &'a [TokenStream] },
+}
+
+/// Generates the impls requested by the setting `value_ty`, for the table `table_ident<value_ty>`:
+/// - `default_value(var)`, `defaults()`, `reset_all()` and the method described by `reset`.
+/// - `Default`, which uses `defaults()`.
+///
+/// The value of each variant is the expression in its attribute `#[default_value(..)]`, or `value_ty::default()`.
+///
+/// `default_value(var)` takes `var` as `key_param_ty`, then matches `key_expr` against `key_paths`.
+///
+/// # Errors
+/// If a variant has the attribute `#[default_value]` but the setting `value_ty` was not provided.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn tokenize_defaults_impls(
+    table_ident: &Ident,
+    value_ty: Optional<&SettingValueTy>,
+    key_param_ty: &TokenStream,
+    key_expr: &TokenStream,
+    key_paths: &[TokenStream],
+    var_cfgs: &[&Any<Attribute<CfgMeta>>],
+    default_values: &[Option<Expr>],
+    reset: Reset,
+) -> Result<TokenStream> {
+    let value_ty = match value_ty {
+        _Some(SettingValueTy { ty, .. }) => ty,
+        _None => {
+            if let Some(expr) = default_values.iter().flatten().next() {
+                bail!(expr => "`#[default_value]` requires the setting `value_ty = Type`, \
+                    the type of the table's values");
+            }
+
+            return Ok(TokenStream::new());
+        }
+    };
+
+    let default_exprs = default_values
+        .iter()
+        .map(|value| {
+            match value {
+                Some(expr) => expr.to_token_stream(),
+                None => quote! { <#value_ty as ::core::default::Default>::default() },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let reset_fn = match reset {
+        Reset::ByType { key_trait } => {
+            quote! {
+                #[doc = "Replaces the value associated with the variant's type `Var` with its default value, \
+                         see [`default_value`](Self::default_value)."]
+                pub fn reset<Var: #key_trait>(&mut self) {
+                    *self.get_mut::<Var>() = Self::default_value(Var::__KEY);
+                }
+            }
+        }
+        Reset::ByValue { places } => {
+            quote! {
+                #[doc = "Replaces the value associated with the variant `var` with its default value, \
+                         see [`default_value`](Self::default_value)."]
+                pub fn reset(&mut self, var: #key_param_ty) {
+                    match #key_expr {
+                        #(
+                            #var_cfgs
+                            #key_paths => {
+                                #places = #default_exprs;
+                            }
+                        )*
+                    }
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        #[allow(unused)]
+        impl #table_ident<#value_ty> {
+            #[doc = "Returns the default value of the variant `var`: the expression in its attribute `#[default_value(..)]`, \
+                     or the value type's [`Default`] if the variant doesn't have the attribute."]
+            pub fn default_value(var: #key_param_ty) -> #value_ty {
+                match #key_expr {
+                    #(
+                        #var_cfgs
+                        #key_paths => #default_exprs
+                    ),*
+                }
+            }
+
+            #[doc = "Constructs a new instance of the type, where each variant's value is its \
+                     [`default_value`](Self::default_value)."]
+            pub fn defaults() -> Self {
+                Self::from_fn(Self::default_value)
+            }
+
+            #[doc = "Replaces the values of all variants with their default values, \
+                     see [`default_value`](Self::default_value)."]
+            pub fn reset_all(&mut self) {
+                *self = Self::defaults();
+            }
+
+            #reset_fn
+        }
+
+        impl ::core::default::Default for #table_ident<#value_ty> {
+            #[doc = "See [`defaults`](Self::defaults)."]
+            fn default() -> Self { Self::defaults() }
+        }
+    })
+}
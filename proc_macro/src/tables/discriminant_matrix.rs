@@ -0,0 +1,337 @@
+use super::*;
+
+pub fn run(input_stream: TokenStream1) -> Result<TokenStream> {
+    let MatrixInput {
+        row_path,
+        col_path,
+        settings,
+    } = syn::parse(input_stream)?;
+
+    let table_attrs = parse_table_metas_syn(settings)?;
+    table_attrs.reject_variant_keyed_only()?;
+    table_attrs.reject_discriminant_generic_only()?;
+    table_attrs.reject_vecs_only()?;
+    if let _Some(kw) = &table_attrs.impl_ops {
+        bail!(kw => "the setting `impl_ops` is not supported by `discriminant_matrix`");
+    }
+
+    if let _Some(value_ty) = &table_attrs.value_ty {
+        bail!(value_ty => "the setting `value_ty` is not supported by `discriminant_matrix`");
+    }
+
+    if let _Some(kw) = &table_attrs.arbitrary {
+        bail!(kw => "the setting `arbitrary` is not supported by `discriminant_matrix`");
+    }
+
+    if let _Some(kw) = &table_attrs.proptest {
+        bail!(kw => "the setting `proptest` is not supported by `discriminant_matrix`");
+    }
+
+    let row_ident = path_ident(&row_path)?;
+    let col_ident = path_ident(&col_path)?;
+
+    let mod_ident = if let _Some(SettingModName {
+        kw: _,
+        eq_token: _,
+        name,
+    }) = table_attrs.mod_name
+    {
+        name
+    } else {
+        let row_lower = row_ident.to_string().to_case(Case::Snake);
+        let col_lower = col_ident.to_string().to_case(Case::Snake);
+        Ident::new(&format!("{row_lower}_{col_lower}_matrix"), Span::call_site())
+    };
+
+    let table_ident = if let _Some(SettingTypeName {
+        kw: _,
+        eq_token: _,
+        name,
+    }) = table_attrs.ty_name
+    {
+        name
+    } else {
+        Ident::new(&format!("{row_ident}{col_ident}Matrix"), Span::call_site())
+    };
+
+    let transposed_ident = format_ident!("{table_ident}Transposed");
+    let attrs = &table_attrs.syn_metas;
+
+    let table = tokenize_matrix(
+        &table_ident,
+        &transposed_ident,
+        (&row_path, &row_ident),
+        (&col_path, &col_ident),
+        attrs,
+    );
+
+    let transposed = tokenize_matrix(
+        &transposed_ident,
+        &table_ident,
+        (&col_path, &col_ident),
+        (&row_path, &row_ident),
+        attrs,
+    );
+
+    Ok(quote! {
+        #[allow(unused_imports)]
+        pub(crate) use #mod_ident::{#table_ident, #transposed_ident};
+
+        #[allow(unused_imports)]
+        mod #mod_ident {
+            use super::*;
+
+            fn __key_at<Key: ::spire_enum::prelude::DiscriminantKey>(ordinal: usize) -> Key {
+                match Key::from_ordinal(ordinal) {
+                    ::core::option::Option::Some(key) => key,
+                    ::core::option::Option::None => ::core::unreachable!(),
+                }
+            }
+
+            fn __next<I: ::core::iter::Iterator>(iter: &mut I) -> I::Item {
+                match iter.next() {
+                    ::core::option::Option::Some(item) => item,
+                    ::core::option::Option::None => ::core::unreachable!(),
+                }
+            }
+
+            #table
+            #transposed
+        }
+    })
+}
+
+struct MatrixInput {
+    row_path: Path,
+    col_path: Path,
+    settings: TokenStream,
+}
+
+impl Parse for MatrixInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let row_path = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let col_path = input.parse()?;
+
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(MatrixInput {
+            row_path,
+            col_path,
+            settings: input.parse()?,
+        })
+    }
+}
+
+fn path_ident(path: &Path) -> Result<Ident> {
+    match path.segments.last() {
+        Some(segment) if segment.arguments.is_none() => Ok(segment.ident.clone()),
+        _ => bail!(path => "expected the path of a fieldless enum, without generics"),
+    }
+}
+
+fn tokenize_matrix(
+    table_ident: &Ident,
+    transposed_ident: &Ident,
+    (row_ty, row_ident): (&Path, &Ident),
+    (col_ty, col_ident): (&Path, &Ident),
+    attrs: &[SynMeta],
+) -> TokenStream {
+    let gen_t = Ident::new("Value", Span::call_site());
+    let lf = Lifetime::new("'_r", Span::call_site());
+    let key_trait = quote! { ::spire_enum::prelude::DiscriminantKey };
+    let rows = quote! { <#row_ty as #key_trait>::LEN };
+    let cols = quote! { <#col_ty as #key_trait>::LEN };
+    let array_ty = quote! { [[#gen_t; #cols]; #rows] };
+
+    let docs = docs_tokens(format!(
+        "This type was generated by an invocation of the macro [`discriminant_matrix`](spire_enum_macros::discriminant_matrix).\n\n\
+         A fixed-size collection that contains exactly one value of the generic [`{gen_t}`] for each pair of variants `({row_ident}, {col_ident})`.\n\
+         Values can be accessed by calling [`get`]({table_ident}::get) or [`get_mut`]({table_ident}::get_mut), \
+         or by indexing with a tuple (e.g. `matrix[(row, column)]`).\n\n\
+         This type does not allocate heap memory(can be used in `no_std`), though no such guarantee is provided to the generic [`{gen_t}`]."
+    ));
+
+    let docs_from_fn = docs_tokens(format!(
+        "Constructs a new instance of the type, invoking the closure `f` for every pair of variants `({row_ident}, {col_ident})`.\n\n\
+         The closure is invoked row by row, in the exact order the variants were declared (Top to bottom)."
+    ));
+
+    let docs_column = docs_tokens(format!(
+        "Iterates through references of the values associated with `column`, along with the variant of [`{row_ident}`] of each value."
+    ));
+
+    let docs_column_mut = docs_tokens(format!(
+        "Iterates through mutable references of the values associated with `column`, along with the variant of [`{row_ident}`] of each value."
+    ));
+
+    let docs_transpose = docs_tokens(format!(
+        "Converts this matrix into a [`{transposed_ident}`], where the rows become the columns (and vice-versa)."
+    ));
+
+    quote! {
+        #docs
+        #(#[#attrs])*
+        pub struct #table_ident<#gen_t> {
+            #[doc = "The values of each pair of variants, indexed by the ordinal of the row, then the ordinal of the column."]
+            pub values: #array_ty,
+        }
+
+        #[allow(unused)]
+        impl<#gen_t> #table_ident<#gen_t> {
+            #[doc = "The amount of rows (variants of the first enum)."]
+            pub const ROWS: usize = #rows;
+
+            #[doc = "The amount of columns (variants of the second enum)."]
+            pub const COLUMNS: usize = #cols;
+
+            #[doc = "Constructs a new instance of the type from the values of each row."]
+            pub const fn from_array(values: #array_ty) -> Self {
+                Self { values }
+            }
+
+            #[doc = "Converts this matrix into an array of its rows."]
+            pub fn into_array(self) -> #array_ty {
+                self.values
+            }
+
+            #[doc = "Constructs a new instance of the type, populating every value with the parameter `__val`"]
+            pub fn filled_with(__val: #gen_t) -> Self where #gen_t: Clone {
+                Self::from_fn(|_, _| __val.clone())
+            }
+
+            #docs_from_fn
+            pub fn from_fn(mut f: impl FnMut(#row_ty, #col_ty) -> #gen_t) -> Self {
+                Self {
+                    values: ::core::array::from_fn(|row| {
+                        ::core::array::from_fn(|col| f(__key_at(row), __key_at(col)))
+                    }),
+                }
+            }
+
+            #[doc = "Returns a reference to the value associated with the pair `(row, column)`."]
+            pub fn get(&self, row: #row_ty, column: #col_ty) -> &#gen_t {
+                &self.values[#key_trait::ordinal(&row)][#key_trait::ordinal(&column)]
+            }
+
+            #[doc = "Returns a mutable reference to the value associated with the pair `(row, column)`."]
+            pub fn get_mut(&mut self, row: #row_ty, column: #col_ty) -> &mut #gen_t {
+                &mut self.values[#key_trait::ordinal(&row)][#key_trait::ordinal(&column)]
+            }
+
+            #[doc = "Replaces the value associated with the pair `(row, column)`, with the contents of `value`."]
+            pub fn set(&mut self, row: #row_ty, column: #col_ty, value: #gen_t) {
+                *self.get_mut(row, column) = value;
+            }
+
+            #[doc = "Returns a reference to the values associated with `row`, indexed by the ordinal of the column."]
+            pub fn row(&self, row: #row_ty) -> &[#gen_t; #cols] {
+                &self.values[#key_trait::ordinal(&row)]
+            }
+
+            #[doc = "Returns a mutable reference to the values associated with `row`, indexed by the ordinal of the column."]
+            pub fn row_mut(&mut self, row: #row_ty) -> &mut [#gen_t; #cols] {
+                &mut self.values[#key_trait::ordinal(&row)]
+            }
+
+            #docs_column
+            pub fn column(&self, column: #col_ty) -> impl ::core::iter::Iterator<Item = (#row_ty, &#gen_t)> {
+                let column = #key_trait::ordinal(&column);
+                self.values
+                    .iter()
+                    .enumerate()
+                    .map(move |(row, values)| (__key_at(row), &values[column]))
+            }
+
+            #docs_column_mut
+            pub fn column_mut(&mut self, column: #col_ty) -> impl ::core::iter::Iterator<Item = (#row_ty, &mut #gen_t)> {
+                let column = #key_trait::ordinal(&column);
+                self.values
+                    .iter_mut()
+                    .enumerate()
+                    .map(move |(row, values)| (__key_at(row), &mut values[column]))
+            }
+
+            #[doc = "Iterates through references of all values, along with their pair of variants, row by row."]
+            pub fn iter(&self) -> impl ::core::iter::Iterator<Item = (#row_ty, #col_ty, &#gen_t)> {
+                self.values.iter().enumerate().flat_map(|(row, values)| {
+                    values
+                        .iter()
+                        .enumerate()
+                        .map(move |(col, value)| (__key_at(row), __key_at(col), value))
+                })
+            }
+
+            #[doc = "Iterates through mutable references of all values, along with their pair of variants, row by row."]
+            pub fn iter_mut(&mut self) -> impl ::core::iter::Iterator<Item = (#row_ty, #col_ty, &mut #gen_t)> {
+                self.values.iter_mut().enumerate().flat_map(|(row, values)| {
+                    values
+                        .iter_mut()
+                        .enumerate()
+                        .map(move |(col, value)| (__key_at(row), __key_at(col), value))
+                })
+            }
+
+            #[doc = "Converts this matrix into a matrix of `Mapped`, by invoking the closure `f` for every value, row by row."]
+            pub fn map<Mapped>(self, mut f: impl FnMut(#row_ty, #col_ty, #gen_t) -> Mapped) -> #table_ident<Mapped> {
+                let mut rows = ::core::iter::IntoIterator::into_iter(self.values).enumerate();
+                #table_ident {
+                    values: ::core::array::from_fn(|_| {
+                        let (row, values) = __next(&mut rows);
+                        let mut values = ::core::iter::IntoIterator::into_iter(values);
+                        ::core::array::from_fn(|col| f(__key_at(row), __key_at(col), __next(&mut values)))
+                    }),
+                }
+            }
+
+            #docs_transpose
+            pub fn transpose(self) -> #transposed_ident<#gen_t> {
+                let mut values = self.values.map(|values| values.map(::core::option::Option::Some));
+                #transposed_ident {
+                    values: ::core::array::from_fn(|col| {
+                        ::core::array::from_fn(|row| {
+                            match values[row][col].take() {
+                                ::core::option::Option::Some(value) => value,
+                                ::core::option::Option::None => ::core::unreachable!(),
+                            }
+                        })
+                    }),
+                }
+            }
+        }
+
+        impl<#lf, #gen_t> ::core::iter::IntoIterator for &#lf #table_ident<#gen_t> {
+            type Item = &#lf #gen_t;
+            type IntoIter = ::core::iter::Flatten<::core::slice::Iter<#lf, [#gen_t; #cols]>>;
+
+            #[doc = "Iterates through references of all values, row by row."]
+            fn into_iter(self) -> Self::IntoIter { self.values.iter().flatten() }
+        }
+
+        impl<#lf, #gen_t> ::core::iter::IntoIterator for &#lf mut #table_ident<#gen_t> {
+            type Item = &#lf mut #gen_t;
+            type IntoIter = ::core::iter::Flatten<::core::slice::IterMut<#lf, [#gen_t; #cols]>>;
+
+            #[doc = "Iterates through mutable references of all values, row by row."]
+            fn into_iter(self) -> Self::IntoIter { self.values.iter_mut().flatten() }
+        }
+
+        impl<#gen_t> ::core::ops::Index<(#row_ty, #col_ty)> for #table_ident<#gen_t> {
+            type Output = #gen_t;
+
+            #[doc = "See [`get`](Self::get)"]
+            fn index(&self, (row, column): (#row_ty, #col_ty)) -> &Self::Output {
+                self.get(row, column)
+            }
+        }
+
+        impl<#gen_t> ::core::ops::IndexMut<(#row_ty, #col_ty)> for #table_ident<#gen_t> {
+            #[doc = "See [`get_mut`](Self::get_mut)"]
+            fn index_mut(&mut self, (row, column): (#row_ty, #col_ty)) -> &mut Self::Output {
+                self.get_mut(row, column)
+            }
+        }
+    }
+}
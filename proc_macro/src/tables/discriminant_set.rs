@@ -0,0 +1,482 @@
+use super::{
+    discriminant_to_generic::{SaneEnum, SaneVariant, sanitize_enum},
+    *,
+};
+
+pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<TokenStream> {
+    let table_attrs = parse_table_metas(input_stream)?;
+    table_attrs.reject_generic_only()?;
+    table_attrs.reject_variant_keyed_only()?;
+    table_attrs.reject_discriminant_generic_only()?;
+    table_attrs.reject_table_only()?;
+    table_attrs.reject_vecs_only()?;
+    let input_enum = syn::parse::<Enum<SynMeta, SynMeta>>(enum_stream)?;
+    reject_table_skips(&input_enum, "discriminant_set")?;
+    let enum_def = input_enum.to_token_stream();
+
+    let SaneEnum {
+        ident: enum_ident,
+        ty: enum_ty,
+        variants,
+        ..
+    } = sanitize_enum(
+        input_enum,
+        Some(
+            "A discriminant set can only be generated if all variants are units (have zero fields).",
+        ),
+    )?;
+
+    let vis = Visibility::Public(Default::default());
+
+    let mod_ident = if let _Some(SettingModName {
+        kw: _,
+        eq_token: _,
+        name,
+    }) = table_attrs.mod_name
+    {
+        name
+    } else {
+        let mut enum_lower = enum_ident.to_string();
+        enum_lower = enum_lower.to_case(Case::Snake);
+        Ident::new(&format!("{enum_lower}_discriminant_set"), Span::call_site())
+    };
+
+    let set_ident = if let _Some(SettingTypeName {
+        kw: _,
+        eq_token: _,
+        name,
+    }) = table_attrs.ty_name
+    {
+        name
+    } else {
+        Ident::new(&format!("{enum_ident}DiscriminantSet"), Span::call_site())
+    };
+
+    let iter_ident = format_ident!("{set_ident}Iter");
+
+    let bits_ty = match variants.len() {
+        0..=8 => quote! { u8 },
+        9..=16 => quote! { u16 },
+        17..=32 => quote! { u32 },
+        33..=64 => quote! { u64 },
+        65..=128 => quote! { u128 },
+        _ => {
+            bail!(enum_ident => "A discriminant set can only be generated for enums with at most 128 variants.")
+        }
+    };
+
+    let var_cfgs = variants
+        .iter()
+        .map(|SaneVariant { cfg_attrs, .. }| cfg_attrs)
+        .collect::<Vec<_>>();
+
+    let var_idents = variants.iter().map(|var| &var.ident).collect::<Vec<_>>();
+
+    // The bit of each variant is its position among the variants that survived `#[cfg]` evaluation.
+    let var_bit_indexes = (0..variants.len())
+        .map(|idx| {
+            let previous_cfgs = &var_cfgs[..idx];
+            quote! {{
+                let mut count: u32 = 0;
+
+                #(
+                    #previous_cfgs
+                    {
+                        count += 1;
+                    }
+                )*
+                count
+            }}
+        })
+        .collect::<Vec<_>>();
+
+    let len_ident = {
+        let set_upper = set_ident.to_string().to_case(Case::Constant);
+        Ident::new(&format!("{}_LEN", set_upper), Span::call_site())
+    };
+
+    let len_def = length_definition(&len_ident, var_cfgs.iter().cloned());
+
+    let all_bits_ident = {
+        let set_upper = set_ident.to_string().to_case(Case::Constant);
+        Ident::new(&format!("{}_ALL_BITS", set_upper), Span::call_site())
+    };
+
+    let all_bits_def = quote! {
+        const #all_bits_ident: #bits_ty = if #len_ident as u32 == #bits_ty::BITS {
+            #bits_ty::MAX
+        } else {
+            (1 << #len_ident) - 1
+        };
+    };
+
+    const MACRO_LINK: &str = "spire_enum_macros::discriminant_set";
+    const DOCS_INTRO: &str = "This type was generated by an invocation of the macro [`discriminant_set`](spire_enum_macros::discriminant_set).";
+
+    let set_def = {
+        let attrs = table_attrs.syn_metas;
+
+        let docs = docs_tokens(format!(
+            "{DOCS_INTRO}\n\n\
+             A set of the variants of [`{enum_ident}`], backed by a single `{bits_ty}` where each bit represents a variant.\n\
+             Variants can be inserted with [`insert`]({set_ident}::insert) and queried with [`contains`]({set_ident}::contains).\n\
+             For a full list of all methods generated for this type, see the [macro]({MACRO_LINK}) documentation.\n\n\
+             This type does not allocate heap memory (can be used in `no_std`)."
+        ));
+
+        quote! {
+            #docs
+            #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+            #(#[#attrs])*
+            #vis struct #set_ident {
+                bits: #bits_ty,
+            }
+        }
+    };
+
+    let iter_def = {
+        let docs = docs_tokens(format!(
+            "{DOCS_INTRO}\n\n\
+             An iterator over the variants present in a [`{set_ident}`].\n\n\
+             This iterator does not allocate heap memory, it merely copies the set's bits."
+        ));
+
+        quote! {
+            #docs
+            #[derive(Clone)]
+            #vis struct #iter_ident {
+                bits: #bits_ty,
+            }
+
+            impl ::core::iter::Iterator for #iter_ident {
+                type Item = #enum_ty;
+
+                fn next(&mut self) -> ::core::option::Option<Self::Item> {
+                    if self.bits == 0 {
+                        return ::core::option::Option::None;
+                    }
+
+                    let index = self.bits.trailing_zeros();
+                    self.bits &= self.bits - 1;
+                    ::core::option::Option::Some(#set_ident::__variant_at(index))
+                }
+
+                fn size_hint(&self) -> (usize, ::core::option::Option<usize>) {
+                    let len = self.bits.count_ones() as usize;
+                    (len, ::core::option::Option::Some(len))
+                }
+            }
+
+            impl ::core::iter::DoubleEndedIterator for #iter_ident {
+                fn next_back(&mut self) -> ::core::option::Option<Self::Item> {
+                    if self.bits == 0 {
+                        return ::core::option::Option::None;
+                    }
+
+                    let index = #bits_ty::BITS - 1 - self.bits.leading_zeros();
+                    self.bits &= !(1 << index);
+                    ::core::option::Option::Some(#set_ident::__variant_at(index))
+                }
+            }
+
+            impl ::core::iter::ExactSizeIterator for #iter_ident {}
+            impl ::core::iter::FusedIterator for #iter_ident {}
+        }
+    };
+
+    let set_impls = {
+        let docs_iter = docs_tokens(format!(
+            "Iterates through all variants of [`{enum_ident}`] present in this set.\n\
+             - It is guaranteed that variants will be yielded in the exact order they were declared in [`{enum_ident}`] (Top to bottom).\n\
+             - The iterator returned does not allocate heap memory."
+        ));
+
+        quote! {
+            #[allow(unused)]
+            impl #set_ident {
+                #[doc = "Constructs a new, empty, set."]
+                pub const fn new() -> Self {
+                    Self { bits: 0 }
+                }
+
+                #[doc = "Constructs a set that contains every variant."]
+                pub const fn all() -> Self {
+                    Self { bits: #all_bits_ident }
+                }
+
+                #[doc = "Constructs a set from its raw bits, ignoring bits that do not represent any variant."]
+                pub const fn from_bits_truncate(bits: #bits_ty) -> Self {
+                    Self { bits: bits & #all_bits_ident }
+                }
+
+                #[doc = "Returns the raw bits of this set, where the bit `n` represents the `n`th (enabled) variant."]
+                pub const fn bits(&self) -> #bits_ty {
+                    self.bits
+                }
+
+                #[doc(hidden)]
+                pub const fn __bit(var: #enum_ty) -> #bits_ty {
+                    let index = match var {
+                        #(
+                            #var_cfgs
+                            #enum_ty::#var_idents {..} => #var_bit_indexes
+                        ),*
+                    };
+
+                    1 << index
+                }
+
+                #[doc(hidden)]
+                pub const fn __variant_at(index: u32) -> #enum_ty {
+                    #(
+                        #var_cfgs
+                        {
+                            if index == #var_bit_indexes {
+                                return #enum_ty::#var_idents;
+                            }
+                        }
+                    )*
+
+                    panic!("bit index does not represent any variant")
+                }
+
+                #[doc = "Returns `true` if `var` is present in this set."]
+                pub const fn contains(&self, var: #enum_ty) -> bool {
+                    self.bits & Self::__bit(var) != 0
+                }
+
+                #[doc = "Adds `var` to this set.\n\n\
+                         Returns whether `var` was newly inserted (`false` if it was already present)."]
+                pub const fn insert(&mut self, var: #enum_ty) -> bool {
+                    let bit = Self::__bit(var);
+                    let was_present = self.bits & bit != 0;
+                    self.bits |= bit;
+                    !was_present
+                }
+
+                #[doc = "Removes `var` from this set.\n\n\
+                         Returns whether `var` was present in the set."]
+                pub const fn remove(&mut self, var: #enum_ty) -> bool {
+                    let bit = Self::__bit(var);
+                    let was_present = self.bits & bit != 0;
+                    self.bits &= !bit;
+                    was_present
+                }
+
+                #[doc = "Returns a copy of this set, with `var` inserted."]
+                pub const fn with(self, var: #enum_ty) -> Self {
+                    Self { bits: self.bits | Self::__bit(var) }
+                }
+
+                #[doc = "Returns a copy of this set, with `var` removed."]
+                pub const fn without(self, var: #enum_ty) -> Self {
+                    Self { bits: self.bits & !Self::__bit(var) }
+                }
+
+                #[doc = "Removes all variants from this set."]
+                pub const fn clear(&mut self) {
+                    self.bits = 0;
+                }
+
+                #[doc = "Returns the amount of variants present in this set."]
+                pub const fn len(&self) -> usize {
+                    self.bits.count_ones() as usize
+                }
+
+                #[doc = "Returns `true` if this set contains no variants."]
+                pub const fn is_empty(&self) -> bool {
+                    self.bits == 0
+                }
+
+                #[doc = "Returns the set of variants present in `self` or in `other`.\n\n\
+                         Also available through the `|` operator."]
+                pub const fn union(self, other: Self) -> Self {
+                    Self { bits: self.bits | other.bits }
+                }
+
+                #[doc = "Returns the set of variants present in both `self` and `other`.\n\n\
+                         Also available through the `&` operator."]
+                pub const fn intersection(self, other: Self) -> Self {
+                    Self { bits: self.bits & other.bits }
+                }
+
+                #[doc = "Returns the set of variants present in `self`, but not in `other`.\n\n\
+                         Also available through the `-` operator."]
+                pub const fn difference(self, other: Self) -> Self {
+                    Self { bits: self.bits & !other.bits }
+                }
+
+                #[doc = "Returns the set of variants present in either `self` or `other`, but not in both.\n\n\
+                         Also available through the `^` operator."]
+                pub const fn symmetric_difference(self, other: Self) -> Self {
+                    Self { bits: self.bits ^ other.bits }
+                }
+
+                #[doc = "Returns the set of variants not present in `self`.\n\n\
+                         Also available through the `!` operator."]
+                pub const fn complement(self) -> Self {
+                    Self { bits: !self.bits & #all_bits_ident }
+                }
+
+                #[doc = "Returns `true` if all variants of `self` are also present in `other`."]
+                pub const fn is_subset(&self, other: &Self) -> bool {
+                    self.bits & other.bits == self.bits
+                }
+
+                #[doc = "Returns `true` if all variants of `other` are also present in `self`."]
+                pub const fn is_superset(&self, other: &Self) -> bool {
+                    other.is_subset(self)
+                }
+
+                #[doc = "Returns `true` if `self` and `other` have no variants in common."]
+                pub const fn is_disjoint(&self, other: &Self) -> bool {
+                    self.bits & other.bits == 0
+                }
+
+                #docs_iter
+                pub const fn iter(&self) -> #iter_ident {
+                    #iter_ident { bits: self.bits }
+                }
+            }
+
+            impl ::core::convert::From<#enum_ty> for #set_ident {
+                fn from(var: #enum_ty) -> Self {
+                    Self::new().with(var)
+                }
+            }
+
+            impl ::core::iter::FromIterator<#enum_ty> for #set_ident {
+                fn from_iter<I: ::core::iter::IntoIterator<Item = #enum_ty>>(iter: I) -> Self {
+                    let mut set = Self::new();
+                    set.extend(iter);
+                    set
+                }
+            }
+
+            impl ::core::iter::Extend<#enum_ty> for #set_ident {
+                fn extend<I: ::core::iter::IntoIterator<Item = #enum_ty>>(&mut self, iter: I) {
+                    for var in iter {
+                        self.insert(var);
+                    }
+                }
+            }
+
+            impl ::core::iter::IntoIterator for #set_ident {
+                type Item = #enum_ty;
+                type IntoIter = #iter_ident;
+
+                #[doc = "See [`iter`](Self::iter)"]
+                fn into_iter(self) -> Self::IntoIter { self.iter() }
+            }
+
+            impl ::core::iter::IntoIterator for &#set_ident {
+                type Item = #enum_ty;
+                type IntoIter = #iter_ident;
+
+                #[doc = "See [`iter`](Self::iter)"]
+                fn into_iter(self) -> Self::IntoIter { self.iter() }
+            }
+
+            impl ::core::ops::BitOr for #set_ident {
+                type Output = Self;
+
+                #[doc = "See [`union`](Self::union)"]
+                fn bitor(self, rhs: Self) -> Self { self.union(rhs) }
+            }
+
+            impl ::core::ops::BitOrAssign for #set_ident {
+                fn bitor_assign(&mut self, rhs: Self) { *self = self.union(rhs); }
+            }
+
+            impl ::core::ops::BitAnd for #set_ident {
+                type Output = Self;
+
+                #[doc = "See [`intersection`](Self::intersection)"]
+                fn bitand(self, rhs: Self) -> Self { self.intersection(rhs) }
+            }
+
+            impl ::core::ops::BitAndAssign for #set_ident {
+                fn bitand_assign(&mut self, rhs: Self) { *self = self.intersection(rhs); }
+            }
+
+            impl ::core::ops::BitXor for #set_ident {
+                type Output = Self;
+
+                #[doc = "See [`symmetric_difference`](Self::symmetric_difference)"]
+                fn bitxor(self, rhs: Self) -> Self { self.symmetric_difference(rhs) }
+            }
+
+            impl ::core::ops::BitXorAssign for #set_ident {
+                fn bitxor_assign(&mut self, rhs: Self) { *self = self.symmetric_difference(rhs); }
+            }
+
+            impl ::core::ops::Sub for #set_ident {
+                type Output = Self;
+
+                #[doc = "See [`difference`](Self::difference)"]
+                fn sub(self, rhs: Self) -> Self { self.difference(rhs) }
+            }
+
+            impl ::core::ops::SubAssign for #set_ident {
+                fn sub_assign(&mut self, rhs: Self) { *self = self.difference(rhs); }
+            }
+
+            impl ::core::ops::Not for #set_ident {
+                type Output = Self;
+
+                #[doc = "See [`complement`](Self::complement)"]
+                fn not(self) -> Self { self.complement() }
+            }
+        }
+    };
+
+    let macro_ident = {
+        let mut str = set_ident.to_string();
+        str = str.to_case(Case::Snake);
+        Ident::new(&str, Span::call_site())
+    };
+
+    let set_macro = {
+        let docs = docs_tokens(format!(
+            "{DOCS_INTRO}\n\n\
+             Constructs a [`{set_ident}`] from a comma-separated list of variant names, this can be used in `const` contexts.\n\n\
+             ```rust ignore\n\
+             const SET: {set_ident} = {macro_ident}![VariantA, VariantB];\n\
+             ```\n\n\
+             The expansion names `{set_ident}` and `{enum_ident}` without a path, so both must be in scope \
+             wherever the macro is invoked."
+        ));
+
+        quote! {
+            #docs
+            #[allow(unused_macros)]
+            macro_rules! #macro_ident {
+                ( $( $var: ident ),* $(,)? ) => {
+                    #set_ident::new() $( .with(#enum_ident::$var) )*
+                };
+            }
+
+            #[allow(unused_imports)]
+            pub(crate) use #macro_ident;
+        }
+    };
+
+    Ok(quote! {
+        #enum_def
+
+        #[allow(unused_imports)]
+        pub(crate) use #mod_ident::{#set_ident, #iter_ident, #macro_ident};
+
+        #[allow(unused_imports)]
+        mod #mod_ident {
+            use super::*;
+
+            #len_def
+            #all_bits_def
+            #set_def
+            #iter_def
+            #set_impls
+            #set_macro
+        }
+    })
+}
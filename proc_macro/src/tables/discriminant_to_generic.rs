@@ -3,13 +3,63 @@ use super::*;
 pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<TokenStream> {
     let table_attrs = parse_table_metas(input_stream)?;
     let input_enum = syn::parse::<Enum<SynMeta, SynMeta>>(enum_stream)?;
-    let enum_def = input_enum.to_token_stream();
+    tokenize(table_attrs, input_enum, EnumOrigin::Attribute)
+}
+
+/// Derive form, which leaves the enum untouched, see [`parse_derive_table_metas`].
+pub fn run_derive(enum_stream: TokenStream1) -> Result<TokenStream> {
+    let input_enum = syn::parse::<Enum<SynMeta, SynMeta>>(enum_stream)?;
+    let table_attrs = parse_derive_table_metas(&input_enum, "DiscriminantTable")?;
+    tokenize(table_attrs, input_enum, EnumOrigin::Derive)
+}
+
+/// Function-like form, for enums defined elsewhere, see [`ForeignEnum`].
+pub fn run_foreign(input_stream: TokenStream1) -> Result<TokenStream> {
+    let foreign = syn::parse::<ForeignEnum<SynMeta, SynMeta>>(input_stream)?;
+    let module_items = tokenize_foreign_module_items(&foreign);
+    let ForeignEnum {
+        input_enum,
+        settings,
+        ..
+    } = foreign;
+
+    tokenize(parse_table_metas_syn(settings)?, input_enum, EnumOrigin::Foreign(module_items))
+}
+
+/// See [`EnumOrigin`] for the differences between the forms of the macro.
+fn tokenize(
+    table_attrs: SaneTableMetas,
+    mut input_enum: Enum<SynMeta, SynMeta>,
+    origin: EnumOrigin,
+) -> Result<TokenStream> {
+    table_attrs.reject_variant_keyed_only()?;
+    table_attrs.reject_vecs_only()?;
+
+    if let _Some(kw) = &table_attrs.ordinal
+        && let Some(attr) = ordinal::find_enum_attr(&input_enum, "ordinal")
+    {
+        bail!(
+            kw => "the setting `ordinal` generates the same helpers as the attribute `#[ordinal]`",
+            attr => "Help: Remove either the attribute or the setting",
+        );
+    }
+
+    // The derive form doesn't register `#[table_skip]`, so the attributes belong to the derive of another table.
+    if !matches!(origin, EnumOrigin::Derive) {
+        reject_table_skips(&input_enum, "discriminant_generic_table")?;
+    }
+
+    let default_values = take_default_values(&mut input_enum)?;
+    let enum_def = origin.enum_def(&input_enum);
+    let foreign_items = origin.foreign_items();
 
     let SaneEnum {
         ident: enum_ident,
         ty: enum_ty,
+        generics,
         variants,
-    } = sanitize_enum(input_enum)?;
+        has_fields,
+    } = sanitize_enum(input_enum, None)?;
 
     let vis = Visibility::Public(Default::default());
     let lf = Lifetime::new("'_r", Span::call_site());
@@ -59,6 +109,32 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
         .map(|SaneVariant { cfg_attrs, .. }| cfg_attrs)
         .collect::<Vec<_>>();
 
+    let var_idents = variants.iter().map(|var| &var.ident).collect::<Vec<_>>();
+
+    let len_ident = {
+        let table_upper = table_ident.to_string().to_case(Case::Constant);
+        Ident::new(&format!("{}_LEN", table_upper), Span::call_site())
+    };
+
+    let (ordinals_def, ordinals) =
+        storage::ordinal_definitions(&table_ident, &var_idents, &var_cfgs);
+
+    let storage = if table_attrs.storage.is_some() {
+        TableStorage::Array(&ordinals)
+    } else {
+        TableStorage::Fields(&fields)
+    };
+
+    let arbitrary_impls = arbitrary::tokenize_arbitrary_impls(
+        &table_attrs.arbitrary,
+        &table_attrs.proptest,
+        &table_ty,
+        arbitrary::TableGenerics::Value(&gen_t),
+        &var_cfgs,
+        &vec![gen_t.to_token_stream(); var_cfgs.len()],
+        |exprs| storage.construct(&table_ident.to_token_stream(), &var_cfgs, exprs),
+    );
+
     const MACRO_LINK: &str = "spire_enum_macros::discriminant_generic_table";
     const DOCS_INTRO: &str = "This type was generated by an invocation of the macro [`discriminant_generic_table`](spire_enum_macros::discriminant_generic_table).";
 
@@ -73,27 +149,129 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
              This type does not allocate heap memory(can be used in `no_std`), though no such guarantee is provided to the generic [`{gen_t}`]."
         ));
 
-        quote! {
-            #docs
-            #(#[#attrs])*
-            #vis struct #table_ident #gen_params {
-                #(
-                    #var_cfgs
-                    pub #fields: #gen_t
-                ),*
+        match storage {
+            TableStorage::Fields(_) => {
+                quote! {
+                    #docs
+                    #(#[#attrs])*
+                    #vis struct #table_ident #gen_params {
+                        #(
+                            #var_cfgs
+                            pub #fields: #gen_t
+                        ),*
+                    }
+                }
+            }
+            TableStorage::Array(_) => {
+                quote! {
+                    #docs
+                    #(#[#attrs])*
+                    #vis struct #table_ident #gen_params {
+                        #[doc = "The values of each variant, in the order the variants were declared (Top to bottom)."]
+                        pub values: [#gen_t; #len_ident],
+                    }
+                }
             }
         }
     };
 
-    let var_idents = variants.iter().map(|var| &var.ident).collect::<Vec<_>>();
-
-    let len_ident = {
-        let table_upper = table_ident.to_string().to_case(Case::Constant);
-        Ident::new(&format!("{}_LEN", table_upper), Span::call_site())
+    let ops_impls = if table_attrs.impl_ops.is_some() {
+        ops::tokenize_ops_impls(&table_ident, &gen_t, &storage, &var_cfgs)
+    } else {
+        TokenStream::new()
     };
 
     let len_def = length_definition(&len_ident, var_cfgs.iter().cloned());
 
+    // Data-carrying variants cannot be constructed out of thin air, so a fieldless "kind" enum is
+    // generated to act as the key, and the table is indexed by references to the original enum.
+    let kind_ident =
+        has_fields.then(|| Ident::new(&format!("{enum_ident}Kind"), Span::call_site()));
+
+    let (key_ty, get_const, get_var_ty, get_var_expr, index_params, index_ty) = match &kind_ident {
+        Some(kind_ident) => {
+            (
+                quote! { #kind_ident },
+                TokenStream::new(),
+                quote! { impl ::core::convert::Into<#kind_ident> },
+                quote! { var.into() },
+                quote! { <#gen_t, __Key: ::core::convert::Into<#kind_ident>> },
+                quote! { __Key },
+            )
+        }
+        None => {
+            (
+                quote! { #enum_ty },
+                quote! { const },
+                quote! { #enum_ty },
+                quote! { var },
+                gen_params.clone(),
+                quote! { #enum_ty },
+            )
+        }
+    };
+
+    let defaults_impls = defaults::tokenize_defaults_impls(
+        &table_ident,
+        table_attrs.value_ty.as_ref(),
+        &get_var_ty,
+        &get_var_expr,
+        &var_idents
+            .iter()
+            .map(|var| quote! { #key_ty::#var })
+            .collect::<Vec<_>>(),
+        &var_cfgs,
+        &default_values,
+        defaults::Reset::ByValue {
+            places: &storage.places(&quote! { self }),
+        },
+    )?;
+
+    let (kind_def, kind_reexport) = match &kind_ident {
+        Some(kind_ident) => {
+            let docs = docs_tokens(format!(
+                "{DOCS_INTRO}\n\n\
+                 An enum that mirrors the variant names of [`{enum_ident}`], without any fields.\n\n\
+                 This enum is used to identify variants by value in methods of [`{table_ident}`] \
+                 (such as [`from_fn`]({table_ident}::from_fn) and [`map`]({table_ident}::map)), \
+                 it can be obtained from a reference to [`{enum_ident}`] through its [`From`] implementation."
+            ));
+
+            let gen_params = generics.stream_params();
+            let (_, where_clause) = generics.as_pair();
+
+            let def = quote! {
+                #docs
+                #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+                #vis enum #kind_ident {
+                    #(
+                        #var_cfgs
+                        #var_idents
+                    ),*
+                }
+
+                impl #gen_params ::core::convert::From<&#enum_ty> for #kind_ident #where_clause {
+                    fn from(var: &#enum_ty) -> Self {
+                        match var {
+                            #(
+                                #var_cfgs
+                                #enum_ident::#var_idents {..} => Self::#var_idents
+                            ),*
+                        }
+                    }
+                }
+            };
+
+            let reexport = quote! {
+                #[allow(unused_imports)]
+                pub(crate) use #mod_ident::#kind_ident;
+            };
+
+            (def, reexport)
+        }
+        None => (TokenStream::new(), TokenStream::new()),
+    };
+
     let table_impls = {
         let docs_new = docs_tokens(format!(
             "Constructs a new instance of the type.\n\n\
@@ -155,6 +333,100 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
              - The iterator returned does not allocate heap memory, it is merely a fixed-size array with length known at compile time."
         ));
 
+        let docs_map = docs_tokens(format!(
+            "Converts this table into a table of `Mapped`, by invoking the closure `f` for every variant of [`{enum_ident}`] \
+             and its associated value.\n\n\
+             The closure is invoked in the exact order the variants were declared in [`{enum_ident}`] (Top to bottom)."
+        ));
+
+        let docs_try_map = docs_tokens(format!(
+            "Fallible version of [`map`](Self::map).\n\n\
+             The closure is invoked in the exact order the variants were declared in [`{enum_ident}`] (Top to bottom), \
+             returning the first error encountered."
+        ));
+
+        let docs_try_from_iter = docs_tokens(format!(
+            "Constructs a new instance of the type from pairs of `({enum_ident}, {gen_t})`.\n\n\
+             If a variant appears more than once, the last value is kept.\n\n\
+             # Errors\n\
+             If any variant of [`{enum_ident}`] is not present in `iter`, the error lists all missing variants."
+        ));
+
+        let self_ty = quote! { Self };
+        let table_path = quote! { #table_ident };
+        let slots_path = quote! { #table_ident::<::core::option::Option<#gen_t>> };
+        let self_places = storage.places(&quote! { self });
+        let slots_places = storage.places(&quote! { slots });
+        let (self_prelude, self_values) =
+            storage.unpack(&Ident::new("self", Span::call_site()), Unpack::Owned);
+        let (other_prelude, other_values) =
+            storage.unpack(&Ident::new("other", Span::call_site()), Unpack::Owned);
+        let (self_mut_prelude, self_muts) =
+            storage.unpack(&Ident::new("self", Span::call_site()), Unpack::Mut);
+
+        let construct = |path: &TokenStream, exprs: Vec<TokenStream>| {
+            storage.construct(path, &var_cfgs, &exprs)
+        };
+
+        let new = construct(&self_ty, fields.iter().map(|field| quote! { #field }).collect());
+        let filled_with =
+            construct(&self_ty, fields.iter().map(|_| quote! { __val.clone() }).collect());
+        let from_fn = construct(
+            &self_ty,
+            var_idents
+                .iter()
+                .map(|var| quote! { f(#key_ty::#var) })
+                .collect(),
+        );
+        let map = construct(
+            &table_path,
+            var_idents
+                .iter()
+                .zip(&self_values)
+                .map(|(var, value)| quote! { f(#key_ty::#var, #value) })
+                .collect(),
+        );
+        let try_map = construct(
+            &table_path,
+            var_idents
+                .iter()
+                .zip(&self_values)
+                .map(|(var, value)| quote! { f(#key_ty::#var, #value)? })
+                .collect(),
+        );
+        let zip = construct(
+            &table_path,
+            self_values
+                .iter()
+                .zip(&other_values)
+                .map(|(value, other)| quote! { (#value, #other) })
+                .collect(),
+        );
+        let (as_ref, as_mut) = match storage {
+            TableStorage::Fields(_) => {
+                (
+                    construct(
+                        &table_path,
+                        self_places.iter().map(|place| quote! { &#place }).collect(),
+                    ),
+                    construct(&table_path, self_muts.clone()),
+                )
+            }
+            TableStorage::Array(_) => {
+                (
+                    quote! { #table_ident { values: self.values.each_ref() } },
+                    quote! { #table_ident { values: self.values.each_mut() } },
+                )
+            }
+        };
+        let empty_slots = construct(
+            &slots_path,
+            fields
+                .iter()
+                .map(|_| quote! { ::core::option::Option::None })
+                .collect(),
+        );
+
         quote! {
             #[allow(clippy::too_many_arguments)]
             #[allow(unused)]
@@ -166,52 +438,37 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
                         #fields: #gen_t
                     ),*
                 ) -> Self {
-                    Self {
-                        #(
-                            #var_cfgs
-                            #fields
-                        ),*
-                    }
+                    #new
                 }
 
                 #[doc = "Constructs a new instance of the type, populating every variant's value with the parameter `__val`"]
                 pub fn filled_with(__val: #gen_t) -> Self where #gen_t: Clone {
-                    Self {
-                        #(
-                            #var_cfgs
-                            #fields: __val.clone()
-                        ),*
-                    }
+                    #filled_with
                 }
 
                 #docs_from_fn
-                pub fn from_fn(mut f: impl FnMut(#enum_ty) -> #gen_t) -> Self {
-                    Self {
-                        #(
-                            #var_cfgs
-                            #fields: f(#enum_ty::#var_idents)
-                        ),*
-                    }
+                pub fn from_fn(mut f: impl FnMut(#key_ty) -> #gen_t) -> Self {
+                    #from_fn
                 }
 
                 #[doc = "Returns a reference to the value associated with the variant `var` that is always present in this table.\n\n\
                 This method will never panic, it is guaranteed at compile time that the table contains the value associated with `var`."]
-                pub const fn get(&self, var: #enum_ty) -> & #gen_t {
-                    match var {
+                pub #get_const fn get(&self, var: #get_var_ty) -> & #gen_t {
+                    match #get_var_expr {
                         #(
                             #var_cfgs
-                            #enum_ty::#var_idents {..} => &self.#fields
+                            #key_ty::#var_idents => &#self_places
                         ),*
                     }
                 }
 
                 #[doc = "Returns a mutable reference to the value associated with the variant `var` that is always present in this table.\n\n\
                 This method will never panic, it is guaranteed at compile time that the table contains the value associated with `var`."]
-                pub const fn get_mut(&mut self, var: #enum_ty) -> &mut #gen_t {
-                    match var {
+                pub #get_const fn get_mut(&mut self, var: #get_var_ty) -> &mut #gen_t {
+                    match #get_var_expr {
                         #(
                             #var_cfgs
-                            #enum_ty::#var_idents {..} => &mut self.#fields
+                            #key_ty::#var_idents => &mut #self_places
                         ),*
                     }
                 }
@@ -220,50 +477,173 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
                          This is shorthand for `*self.get_mut(var) = value;`.\n\
                          This is provided merely as \"sugar\" for those who aren't \
                          very familiar with dereferencing."]
-                pub fn set(&mut self, var: #enum_ty, value: #gen_t) {
+                pub fn set(&mut self, var: #get_var_ty, value: #gen_t) {
                     *self.get_mut(var) = value;
                 }
 
                 #[allow(clippy::needless_lifetimes)]
                 #docs_iter
-                pub fn iter<#lf>(&#lf self) -> ::core::array::IntoIter<(#enum_ty, &#lf #gen_t), #len_ident> {
+                pub fn iter<#lf>(&#lf self) -> ::core::array::IntoIter<(#key_ty, &#lf #gen_t), #len_ident> {
                     [
                         #(
                             #var_cfgs
-                            (#enum_ty::#var_idents, &self.#fields)
+                            (#key_ty::#var_idents, &#self_places)
                         ),*
                     ].into_iter()
                 }
 
                 #[allow(clippy::needless_lifetimes)]
                 #docs_iter_mut
-                pub fn iter_mut<#lf>(&#lf mut self) -> ::core::array::IntoIter<(#enum_ty, &#lf mut #gen_t), #len_ident> {
+                pub fn iter_mut<#lf>(&#lf mut self) -> ::core::array::IntoIter<(#key_ty, &#lf mut #gen_t), #len_ident> {
+                    #self_mut_prelude
+                    [
+                        #(
+                            #var_cfgs
+                            (#key_ty::#var_idents, #self_muts)
+                        ),*
+                    ].into_iter()
+                }
+
+                #[doc = "Iterates through all variants of the enum, in the exact order they were declared (Top to bottom)."]
+                pub fn keys(&self) -> ::core::array::IntoIter<#key_ty, #len_ident> {
+                    [
+                        #(
+                            #var_cfgs
+                            #key_ty::#var_idents
+                        ),*
+                    ].into_iter()
+                }
+
+                #[allow(clippy::needless_lifetimes)]
+                #[doc = "Iterates through references of all values, in the exact order their variants were declared (Top to bottom)."]
+                pub fn values<#lf>(&#lf self) -> ::core::array::IntoIter<&#lf #gen_t, #len_ident> {
+                    [
+                        #(
+                            #var_cfgs
+                            &#self_places
+                        ),*
+                    ].into_iter()
+                }
+
+                #[allow(clippy::needless_lifetimes)]
+                #[doc = "Iterates through mutable references of all values, in the exact order their variants were declared (Top to bottom)."]
+                pub fn values_mut<#lf>(&#lf mut self) -> ::core::array::IntoIter<&#lf mut #gen_t, #len_ident> {
+                    #self_mut_prelude
                     [
                         #(
                             #var_cfgs
-                            (#enum_ty::#var_idents, &mut self.#fields)
+                            #self_muts
                         ),*
                     ].into_iter()
                 }
+
+                #[doc = "Converts this table into an iterator of its values, in the exact order their variants were declared (Top to bottom)."]
+                pub fn into_values(self) -> ::core::array::IntoIter<#gen_t, #len_ident> {
+                    #self_prelude
+                    [
+                        #(
+                            #var_cfgs
+                            #self_values
+                        ),*
+                    ].into_iter()
+                }
+
+                #docs_map
+                pub fn map<Mapped>(self, mut f: impl FnMut(#key_ty, #gen_t) -> Mapped) -> #table_ident<Mapped> {
+                    #self_prelude
+                    #map
+                }
+
+                #docs_try_map
+                pub fn try_map<Mapped, Error>(
+                    self,
+                    mut f: impl FnMut(#key_ty, #gen_t) -> ::core::result::Result<Mapped, Error>,
+                ) -> ::core::result::Result<#table_ident<Mapped>, Error> {
+                    #self_prelude
+                    ::core::result::Result::Ok(#try_map)
+                }
+
+                #[doc = "Combines this table with `other`, pairing the values associated with each variant."]
+                pub fn zip<Other>(self, other: #table_ident<Other>) -> #table_ident<(#gen_t, Other)> {
+                    #self_prelude
+                    #other_prelude
+                    #zip
+                }
+
+                #[doc = "Returns a table containing references to the values of this table."]
+                pub const fn as_ref(&self) -> #table_ident<&#gen_t> {
+                    #as_ref
+                }
+
+                #[doc = "Returns a table containing mutable references to the values of this table."]
+                pub const fn as_mut(&mut self) -> #table_ident<&mut #gen_t> {
+                    #as_mut
+                }
+
+                #docs_try_from_iter
+                pub fn try_from_iter(
+                    iter: impl ::core::iter::IntoIterator<Item = (#key_ty, #gen_t)>,
+                ) -> ::core::result::Result<Self, ::spire_enum::prelude::MissingVariants<#len_ident>> {
+                    let mut slots = #empty_slots;
+
+                    for (var, value) in iter {
+                        *slots.get_mut(var) = ::core::option::Option::Some(value);
+                    }
+
+                    let mut missing = ::spire_enum::prelude::MissingVariants::new();
+
+                    #(
+                        #var_cfgs
+                        {
+                            if #slots_places.is_none() {
+                                missing.push(::core::stringify!(#var_idents));
+                            }
+                        }
+                    )*
+
+                    if !missing.is_empty() {
+                        return ::core::result::Result::Err(missing);
+                    }
+
+                    ::core::result::Result::Ok(slots.map(|_, value| {
+                        match value {
+                            ::core::option::Option::Some(value) => value,
+                            ::core::option::Option::None => ::core::unreachable!(),
+                        }
+                    }))
+                }
+            }
+
+            impl #gen_params ::core::iter::FromIterator<(#key_ty, #gen_t)> for #table_ty {
+                #[doc = "See [`try_from_iter`](Self::try_from_iter).\n\n\
+                         # Panics\n\
+                         If any variant is not present in `iter`."]
+                fn from_iter<I: ::core::iter::IntoIterator<Item = (#key_ty, #gen_t)>>(iter: I) -> Self {
+                    match Self::try_from_iter(iter) {
+                        ::core::result::Result::Ok(table) => table,
+                        ::core::result::Result::Err(missing) => ::core::panic!("{missing}"),
+                    }
+                }
             }
 
             impl #gen_params ::core::iter::IntoIterator for #table_ty {
-                type Item = (#enum_ty, #gen_t);
-                type IntoIter = ::core::array::IntoIter<(#enum_ty, #gen_t), #len_ident>;
+                type Item = (#key_ty, #gen_t);
+                type IntoIter = ::core::array::IntoIter<(#key_ty, #gen_t), #len_ident>;
 
                 #docs_into_iter
                 fn into_iter(self) -> Self::IntoIter {
+                    #self_prelude
                     [
                         #(
                             #var_cfgs
-                            (#enum_ty::#var_idents, self.#fields)
+                            (#key_ty::#var_idents, #self_values)
                         ),*
                     ].into_iter()
                 }
             }
 
             impl #gen_lf_params ::core::iter::IntoIterator for &#lf #table_ty {
-                type Item = (#enum_ty, &#lf #gen_t);
+                type Item = (#key_ty, &#lf #gen_t);
                 type IntoIter = ::core::array::IntoIter<Self::Item, #len_ident>;
 
                 #[doc = "See [`iter`](Self::iter)"]
@@ -271,31 +651,146 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
             }
 
             impl #gen_lf_params ::core::iter::IntoIterator for &#lf mut #table_ty {
-                type Item = (#enum_ty, &#lf mut #gen_t);
+                type Item = (#key_ty, &#lf mut #gen_t);
                 type IntoIter = ::core::array::IntoIter<Self::Item, #len_ident>;
 
                 #[doc = "See [`iter_mut`](Self::iter_mut)"]
                 fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
             }
 
-            impl #gen_params ::core::ops::Index<#enum_ty> for #table_ty {
+            impl #index_params ::core::ops::Index<#index_ty> for #table_ty {
                 type Output = #gen_t;
 
                 #[doc = "See [`get`](Self::get)"]
-                fn index(&self, index: #enum_ty) -> &Self::Output {
+                fn index(&self, index: #index_ty) -> &Self::Output {
                     self.get(index)
                 }
             }
 
-            impl #gen_params ::core::ops::IndexMut<#enum_ty> for #table_ty {
+            impl #index_params ::core::ops::IndexMut<#index_ty> for #table_ty {
                 #[doc = "See [`get_mut`](Self::get_mut)"]
-                fn index_mut(&mut self, index: #enum_ty) -> &mut Self::Output {
+                fn index_mut(&mut self, index: #index_ty) -> &mut Self::Output {
                     self.get_mut(index)
                 }
             }
         }
     };
 
+    let array_impls = match storage {
+        TableStorage::Fields(_) => TokenStream::new(),
+        TableStorage::Array(_) => {
+            let docs_into_array =
+                docs_tokens(format!("See [`into_array`]({table_ident}::into_array)"));
+
+            quote! {
+                #[allow(unused)]
+                impl #gen_params #table_ty {
+                    #[doc = "Constructs a new instance of the type from the values of each variant, \
+                             in the order the variants were declared (Top to bottom)."]
+                    pub const fn from_array(values: [#gen_t; #len_ident]) -> Self {
+                        Self { values }
+                    }
+
+                    #[doc = "Converts this table into an array of its values, in the order the variants were declared (Top to bottom)."]
+                    pub fn into_array(self) -> [#gen_t; #len_ident] {
+                        self.values
+                    }
+
+                    #[doc = "Returns a slice containing the values of each variant, in the order the variants were declared (Top to bottom)."]
+                    pub const fn as_slice(&self) -> &[#gen_t] {
+                        &self.values
+                    }
+
+                    #[doc = "Returns a mutable slice containing the values of each variant, in the order the variants were declared (Top to bottom)."]
+                    pub const fn as_mut_slice(&mut self) -> &mut [#gen_t] {
+                        &mut self.values
+                    }
+                }
+
+                impl #gen_params ::core::convert::From<[#gen_t; #len_ident]> for #table_ty {
+                    #[doc = "See [`from_array`](Self::from_array)"]
+                    fn from(values: [#gen_t; #len_ident]) -> Self {
+                        Self::from_array(values)
+                    }
+                }
+
+                impl #gen_params ::core::convert::From<#table_ty> for [#gen_t; #len_ident] {
+                    #docs_into_array
+                    fn from(table: #table_ty) -> Self {
+                        table.into_array()
+                    }
+                }
+
+                impl #gen_params ::core::ops::Index<usize> for #table_ty {
+                    type Output = #gen_t;
+
+                    #[doc = "Returns a reference to the value of the variant at the ordinal `index` (its position in the declaration order).\n\n\
+                             # Panics\n\
+                             If `index` is out of bounds."]
+                    fn index(&self, index: usize) -> &Self::Output {
+                        &self.values[index]
+                    }
+                }
+
+                impl #gen_params ::core::ops::IndexMut<usize> for #table_ty {
+                    #[doc = "Returns a mutable reference to the value of the variant at the ordinal `index` (its position in the declaration order).\n\n\
+                             # Panics\n\
+                             If `index` is out of bounds."]
+                    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                        &mut self.values[index]
+                    }
+                }
+            }
+        }
+    };
+
+    // The orphan rule forbids implementing the trait for enums defined in other crates.
+    let key_impl = (kind_ident.is_some() || foreign_items.is_none()).then(|| {
+        quote! {
+            impl ::spire_enum::prelude::DiscriminantKey for #key_ty {
+                const LEN: usize = #len_ident;
+
+                fn ordinal(&self) -> usize {
+                    match self {
+                        #(
+                            #var_cfgs
+                            #key_ty::#var_idents => #ordinals
+                        ),*
+                    }
+                }
+
+                fn from_ordinal(ordinal: usize) -> ::core::option::Option<Self> {
+                    #(
+                        #var_cfgs
+                        {
+                            if ordinal == #ordinals {
+                                return ::core::option::Option::Some(#key_ty::#var_idents);
+                            }
+                        }
+                    )*
+
+                    ::core::option::Option::None
+                }
+            }
+        }
+    });
+
+    let ordinal_impl = match &table_attrs.ordinal {
+        _Some(kw) if key_impl.is_none() => {
+            bail!(kw => "the setting `ordinal` cannot add methods to a fieldless enum defined elsewhere")
+        }
+        _Some(_) => {
+            Some(ordinal::tokenize_ordinal_impl(
+                &key_ty,
+                &len_ident,
+                &var_idents,
+                &var_cfgs,
+                &ordinals,
+            ))
+        }
+        _None => None,
+    };
+
     let from_const_fn_macro = {
         let macro_ident = {
             let mut str = table_ident.to_string();
@@ -303,28 +798,38 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
             Ident::new(&format!("{str}_from_const_fn"), Span::call_site())
         };
 
+        let table_path = quote! { #table_ident };
+        let from_key_closure = storage.construct(
+            &table_path,
+            &var_cfgs,
+            &var_idents
+                .iter()
+                .map(|var| {
+                    quote! {{
+                        let $var = #key_ty::#var;
+                        $closure
+                    }}
+                })
+                .collect::<Vec<_>>(),
+        );
+        let from_closure = storage.construct(
+            &table_path,
+            &var_cfgs,
+            &var_idents
+                .iter()
+                .map(|_| quote! { $closure })
+                .collect::<Vec<_>>(),
+        );
+
         quote! {
             #[allow(unused_macros)]
             macro_rules! #macro_ident {
                 ( | $var: ident | $( -> $ret: ty )? $closure: block ) => {{
-                    #table_ident {
-                        #(
-                            #var_cfgs
-                            #fields: {
-                                let $var = #enum_ty::#var_idents;
-                                $closure
-                            }
-                        ),*
-                    }
+                    #from_key_closure
                 }};
 
                 ( |_| $( -> $ret: ty )? $closure: block ) => {{
-                    #table_ident {
-                        #(
-                            #var_cfgs
-                            #fields: $closure
-                        ),*
-                    }
+                    #from_closure
                 }};
             }
         }
@@ -335,32 +840,50 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
 
         #[allow(unused_imports)]
         pub(crate) use #mod_ident::#table_ident;
+        #kind_reexport
 
         #[allow(unused_imports)]
         mod #mod_ident {
             use super::*;
 
+            #foreign_items
+
             #len_def
+            #ordinals_def
+            #kind_def
             #table_def
             #table_impls
+            #array_impls
+            #key_impl
+            #ordinal_impl
+            #ops_impls
+            #defaults_impls
+            #arbitrary_impls
             #from_const_fn_macro
         }
     })
 }
 
-struct SaneEnum {
-    ident: Ident,
-    ty: Type,
-    variants: Vec<SaneVariant>,
+pub(super) struct SaneEnum {
+    pub ident: Ident,
+    pub ty: Type,
+    pub generics: Optional<SaneGenerics>,
+    pub variants: Vec<SaneVariant>,
+    /// Whether any of the variants has fields.
+    pub has_fields: bool,
 }
 
-struct SaneVariant {
-    cfg_attrs: Any<Attribute<CfgMeta>>,
-    ident: Ident,
-    table_field_ident: Ident,
+pub(super) struct SaneVariant {
+    pub cfg_attrs: Any<Attribute<CfgMeta>>,
+    pub ident: Ident,
+    pub table_field_ident: Ident,
 }
 
-fn sanitize_enum(input: Enum<SynMeta, SynMeta>) -> Result<SaneEnum> {
+/// If `fields_error` is `Some`, errors with that message if any of the variants has fields.
+pub(super) fn sanitize_enum(
+    input: Enum<SynMeta, SynMeta>,
+    fields_error: Option<&str>,
+) -> Result<SaneEnum> {
     let Enum {
         attrs: _,
         vis: _,
@@ -373,52 +896,51 @@ fn sanitize_enum(input: Enum<SynMeta, SynMeta>) -> Result<SaneEnum> {
 
     let generics = sanitize_generics(generics, where_clause)?;
     let ty = new_ty_maybe_generic(&ident, &generics);
+    let mut has_fields = false;
 
     let variants = variants
         .into_inner()
         .inner
         .into_iter()
-        .map(|Var { attrs, ident, fields, discriminant: _ }| {
-            const HELP: &str =
-                "A discriminant table can only be generated if all variants are units (have zero fields).";
-
-            let cfg_attrs = parse_cfg_attrs(attrs);
-
-            match fields {
-                VarFields::Named(named) => {
-                    if named.is_empty() {
-                        Ok(SaneVariant {
-                            cfg_attrs,
-                            table_field_ident: var_to_field_ident(&ident),
-                            ident,
-                        })
-                    } else {
-                        bail!(named => HELP)
+        .map(
+            |Var {
+                 attrs,
+                 ident,
+                 fields,
+                 discriminant: _,
+             }| {
+                let cfg_attrs = parse_cfg_attrs(attrs);
+
+                match fields {
+                    VarFields::Named(named) if !named.is_empty() => {
+                        if let Some(msg) = fields_error {
+                            bail!(named => msg)
+                        }
+                        has_fields = true;
                     }
-                }
-                VarFields::Unnamed(unnamed) => {
-                    if unnamed.is_empty() {
-                        Ok(SaneVariant {
-                            cfg_attrs,
-                            table_field_ident: var_to_field_ident(&ident),
-                            ident,
-                        })
-                    } else {
-                        bail!(unnamed => HELP)
+                    VarFields::Unnamed(unnamed) if !unnamed.is_empty() => {
+                        if let Some(msg) = fields_error {
+                            bail!(unnamed => msg)
+                        }
+                        has_fields = true;
                     }
+                    _ => {}
                 }
-                VarFields::Unit => Ok(SaneVariant {
+
+                Ok(SaneVariant {
                     cfg_attrs,
                     table_field_ident: var_to_field_ident(&ident),
                     ident,
-                }),
-            }
-        })
+                })
+            },
+        )
         .try_collect()?;
 
     Ok(SaneEnum {
         ident,
-        variants,
         ty,
+        generics,
+        variants,
+        has_fields,
     })
 }
@@ -1,17 +1,301 @@
-use kw::{mod_name as kw_mod_name, ty_name as kw_ty_name};
+use kw::{
+    arbitrary as kw_arbitrary,
+    array as kw_array,
+    capacity as kw_capacity,
+    impl_ops as kw_impl_ops,
+    keys as kw_keys,
+    mod_name as kw_mod_name,
+    ordinal as kw_ordinal,
+    proptest as kw_proptest,
+    storage as kw_storage,
+    tags as kw_tags,
+    ty_name as kw_ty_name,
+    value_ty as kw_value_ty,
+};
+use quote::TokenStreamExt;
 use syn::{parse_quote, token::Comma};
 
 mod kw {
     syn::custom_keyword!(ty_name);
     syn::custom_keyword!(mod_name);
+    syn::custom_keyword!(impl_ops);
+    syn::custom_keyword!(keys);
+    syn::custom_keyword!(tags);
+    syn::custom_keyword!(storage);
+    syn::custom_keyword!(array);
+    syn::custom_keyword!(value_ty);
+    syn::custom_keyword!(arbitrary);
+    syn::custom_keyword!(proptest);
+    syn::custom_keyword!(capacity);
+    syn::custom_keyword!(ordinal);
 }
 
+mod arbitrary;
+mod defaults;
+pub mod discriminant_matrix;
+pub mod discriminant_set;
 pub mod discriminant_to_generic;
+mod ops;
+pub mod ordinal;
+mod storage;
+mod variant_shape;
+pub mod variant_type_set;
 pub mod variant_type_to_generic;
 pub mod variant_type_to_variant_type;
+pub mod variant_vecs;
+
+use storage::{TableStorage, Unpack};
+use variant_shape::VarShape;
 
 use super::*;
 
+/// Names of the table macros, used to detect if other table macros are still pending on an enum.
+const TABLE_MACROS: [&str; 6] = [
+    "discriminant_generic_table",
+    "discriminant_set",
+    "variant_generic_table",
+    "variant_type_set",
+    "variant_type_table",
+    "variant_vecs",
+];
+
+/// Names of the derive forms of the table macros, which may qualify the helper attribute `#[table(..)]`.
+const TABLE_DERIVES: [&str; 3] = [
+    "DiscriminantTable",
+    "VariantGenericTable",
+    "VariantTypeTable",
+];
+
+/// Where the enum of a table macro is defined, which depends on the form of the macro.
+enum EnumOrigin {
+    /// Attribute form (e.g. `#[variant_type_table]`), the enum definition is re-emitted.
+    Attribute,
+    /// Derive form (e.g. `#[derive(VariantTypeTable)]`), the enum definition is left untouched.
+    Derive,
+    /// Function-like form (e.g. `foreign_variant_type_table!`), the enum is defined elsewhere.
+    ///
+    /// Contains the items to place inside the table's module, see [`tokenize_foreign_module_items`].
+    Foreign(TokenStream),
+}
+
+impl EnumOrigin {
+    /// Returns the enum definition to emit alongside the table, only non-empty for the attribute form.
+    fn enum_def(&self, input_enum: &Enum<SynMeta, SynMeta>) -> TokenStream {
+        match self {
+            EnumOrigin::Attribute => input_enum.to_token_stream(),
+            EnumOrigin::Derive | EnumOrigin::Foreign(_) => TokenStream::new(),
+        }
+    }
+
+    fn foreign_items(&self) -> Option<&TokenStream> {
+        match self {
+            EnumOrigin::Foreign(items) => Some(items),
+            EnumOrigin::Attribute | EnumOrigin::Derive => None,
+        }
+    }
+}
+
+/// Returns `true` if `attr` is the helper attribute `#[table_skip]`.
+pub(crate) fn is_table_skip_attr(attr: &Attribute<SynMeta>) -> bool {
+    matches!(&*attr.inner, SynMeta::Path(path) if path.is_ident("table_skip"))
+}
+
+/// Returns, for each variant of `input`, whether it is marked with `#[table_skip]`.
+///
+/// The attributes are removed from `input`, unless another table macro is still pending on the enum
+/// (the compiler would otherwise complain about an unknown attribute).
+fn take_table_skips(input: &mut Enum<SynMeta, SynMeta>) -> Vec<bool> {
+    let other_table_pending = is_other_table_pending(input, "table_skip");
+
+    input
+        .variants
+        .iter_mut()
+        .map(|var| {
+            let skip = var.attrs.iter().any(is_table_skip_attr);
+            if !other_table_pending {
+                var.attrs.retain(|attr| !is_table_skip_attr(attr));
+            }
+            skip
+        })
+        .collect()
+}
+
+/// Errors if a variant of `input` is marked with `#[table_skip]`, for the table macros that need every variant.
+///
+/// Unless another table macro that honours `#[table_skip]` is still pending on the enum, in which case the attributes
+/// are meant for that table, and are left in place.
+fn reject_table_skips(input: &Enum<SynMeta, SynMeta>, macro_name: &str) -> Result<()> {
+    if is_other_table_pending(input, "table_skip") {
+        return Ok(());
+    }
+
+    match input
+        .variants
+        .iter()
+        .flat_map(|var| var.attrs.iter())
+        .find(|attr| is_table_skip_attr(attr))
+    {
+        Some(attr) => {
+            bail!(attr => format!("`#[table_skip]` is not supported by `{macro_name}`, since it needs every variant"))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Returns, for each variant of `input`, the expression of its helper attribute `#[default_value(..)]` (if any).
+///
+/// Like in [`take_table_skips`], the attributes are removed from `input` unless another table macro is still pending.
+fn take_default_values(input: &mut Enum<SynMeta, SynMeta>) -> Result<Vec<Option<Expr>>> {
+    let other_table_pending = is_other_table_pending(input, "default_value");
+
+    input
+        .variants
+        .iter_mut()
+        .map(|var| {
+            let mut value = None;
+
+            for attr in var.attrs.iter() {
+                if !attr.inner.path().is_ident("default_value") {
+                    continue;
+                }
+
+                if value.is_some() {
+                    bail!(attr => "duplicate attribute `#[default_value]`");
+                }
+
+                match &*attr.inner {
+                    SynMeta::List(list) => value = Some(syn::parse2::<Expr>(list.tokens.clone())?),
+                    meta => bail!(meta => "expected `#[default_value(expression)]`"),
+                }
+            }
+
+            if !other_table_pending {
+                var.attrs
+                    .retain(|attr| !attr.inner.path().is_ident("default_value"));
+            }
+
+            Ok(value)
+        })
+        .collect()
+}
+
+/// Returns `true` if `input` has the attribute of another table macro, which will be expanded later,
+/// or derives a table (e.g. `#[derive(VariantTypeTable)]`), and that table honours the helper attribute `helper`.
+fn is_other_table_pending(input: &Enum<SynMeta, SynMeta>, helper: &str) -> bool {
+    let is_pending = |segment: &syn::PathSegment, names: &[&str]| {
+        names.iter().any(|name| segment.ident == name)
+            && table_honours_helper(&segment.ident.to_string(), helper)
+    };
+
+    input.attrs.iter().any(|attr| {
+        match &*attr.inner {
+            SynMeta::List(list) if list.path.is_ident("derive") => {
+                list.parse_args_with(syn::punctuated::Punctuated::<Path, Comma>::parse_terminated)
+                    .is_ok_and(|paths| {
+                        paths.iter().any(|path| {
+                            path.segments
+                                .last()
+                                .is_some_and(|segment| is_pending(segment, &TABLE_DERIVES))
+                        })
+                    })
+            }
+            meta => {
+                meta.path()
+                    .segments
+                    .last()
+                    .is_some_and(|segment| is_pending(segment, &TABLE_MACROS))
+            }
+        }
+    })
+}
+
+/// Returns `true` if the table macro (or derive) named `table` honours the helper attribute `helper`.
+fn table_honours_helper(table: &str, helper: &str) -> bool {
+    match helper {
+        "table_skip" => {
+            matches!(
+                table,
+                "variant_generic_table"
+                    | "variant_type_set"
+                    | "variant_type_table"
+                    | "VariantGenericTable"
+                    | "VariantTypeTable"
+            )
+        }
+        "default_value" => {
+            matches!(
+                table,
+                "discriminant_generic_table"
+                    | "variant_generic_table"
+                    | "DiscriminantTable"
+                    | "VariantGenericTable"
+            )
+        }
+        _ => false,
+    }
+}
+
+/// Removes the variants marked with `#[table_skip]` from `input`, see [`take_table_skips`].
+fn remove_skipped_variants(input: &mut Enum<SynMeta, SynMeta>, skips: &[bool]) {
+    input.variants.inner = std::mem::take(&mut input.variants.inner)
+        .into_iter()
+        .zip(skips)
+        .filter_map(|(var, skip)| (!skip).then_some(var))
+        .collect();
+}
+
+/// Returns the items to place inside the table's module, for the function-like table macros
+/// (e.g. `foreign_variant_type_table!`):
+/// - An alias to the enum's path (unless the path is a single identifier, which is already in scope).
+/// - A function that matches the enum exhaustively with the restated variants, ensuring they can't go stale.
+///
+/// Must be called before any variants are removed from the enum.
+fn tokenize_foreign_module_items(foreign: &ForeignEnum<SynMeta, SynMeta>) -> TokenStream {
+    let ForeignEnum {
+        path, input_enum, ..
+    } = foreign;
+
+    let ident = &input_enum.ident;
+    let generics = sanitize_generics(input_enum.generics.clone(), _None).unwrap_or(_None);
+    let gen_params = generics.stream_params();
+    let gen_args = generics.stream_args();
+
+    let alias = if path.segments.len() > 1 {
+        // The alias is declared inside the table's module, paths relative to the current module need to go one level up.
+        let path = match path.segments.first() {
+            Some(first) if first.ident == "self" => {
+                let rest = path.segments.iter().skip(1);
+                quote! { super #(::#rest)* }
+            }
+            Some(first) if first.ident == "super" => quote! { super::#path },
+            _ => quote! { #path },
+        };
+
+        quote! {
+            type #ident #gen_params = #path #gen_args;
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let exhaustiveness_check = tokenize_exhaustiveness_check(
+        ident,
+        &quote! { #ident #gen_args },
+        &gen_params,
+        &TokenStream::new(),
+        input_enum
+            .variants
+            .iter()
+            .map(|var| parse_cfg_attrs(var.attrs.clone())),
+        input_enum.variants.iter().map(|var| &var.ident),
+    );
+
+    quote! {
+        #alias
+        #exhaustiveness_check
+    }
+}
+
 fn var_to_field_ident(ident: &Ident) -> Ident {
     let str = ident.to_string().to_case(Case::Snake);
     Ident::new_raw(&str, Span::call_site())
@@ -23,12 +307,28 @@ struct SaneTableMetas {
     cfg_metas: Vec<CfgMeta>,
     ty_name: Optional<SettingTypeName>,
     mod_name: Optional<SettingModName>,
+    impl_ops: Optional<kw_impl_ops>,
+    keys: Optional<SettingKeys>,
+    storage: Optional<SettingStorage>,
+    value_ty: Optional<SettingValueTy>,
+    arbitrary: Optional<kw_arbitrary>,
+    proptest: Optional<kw_proptest>,
+    capacity: Optional<SettingCapacity>,
+    ordinal: Optional<kw_ordinal>,
 }
 
 #[derive(Parse, ToTokens)]
 enum TableMeta {
     TypeName(SettingTypeName),
     ModuleName(SettingModName),
+    ImplOps(kw_impl_ops),
+    Keys(SettingKeys),
+    Storage(SettingStorage),
+    ValueTy(SettingValueTy),
+    Arbitrary(kw_arbitrary),
+    Proptest(kw_proptest),
+    Capacity(SettingCapacity),
+    Ordinal(kw_ordinal),
     Derive(SettingDerive),
     Attrs(SettingAttrs),
 }
@@ -47,17 +347,117 @@ struct SettingModName {
     name: Ident,
 }
 
+#[derive(Parse, ToTokens)]
+struct SettingKeys {
+    kw: kw_keys,
+    eq_token: Token![=],
+    tags: SettingKeysTags,
+}
+
+#[derive(Parse, ToTokens)]
+struct SettingKeysTags {
+    kw: kw_tags,
+    mod_name: Optional<Paren<Ident>>,
+}
+
+#[derive(Parse, ToTokens)]
+struct SettingStorage {
+    kw: kw_storage,
+    eq_token: Token![=],
+    array: kw_array,
+}
+
+#[derive(Parse, ToTokens)]
+struct SettingValueTy {
+    kw: kw_value_ty,
+    eq_token: Token![=],
+    ty: Box<Type>,
+}
+
+#[derive(Parse, ToTokens)]
+struct SettingCapacity {
+    kw: kw_capacity,
+    eq_token: Token![=],
+    len: Box<Expr>,
+}
+
+impl SettingKeys {
+    /// Name of the module containing the tag types, `tags` unless specified with `keys = tags(name)`.
+    fn tags_mod_ident(&self) -> Ident {
+        match &self.tags.mod_name {
+            _Some(name) => Ident::clone(name),
+            _None => Ident::new("tags", Span::call_site()),
+        }
+    }
+}
+
 fn parse_table_metas(input: TokenStream1) -> Result<SaneTableMetas> {
     let input_attrs = syn::parse::<InputPunctuated<Meta<TableMeta>, Token![,]>>(input)?;
     parse_table_metas_inner(input_attrs)
 }
 
-#[allow(unused)]
 fn parse_table_metas_syn(input: TokenStream) -> Result<SaneTableMetas> {
     let input_attrs = syn::parse2::<InputPunctuated<Meta<TableMeta>, Token![,]>>(input)?;
     parse_table_metas_inner(input_attrs)
 }
 
+/// Parses the settings of the derive form `derive_name` from the helper attributes `#[table(..)]` of `input`.
+///
+/// An attribute may be qualified with the name of a derive (`#[table(VariantGenericTable, impl_ops)]`),
+/// in which case it only applies to that derive, otherwise it applies to all table derives of the enum.
+fn parse_derive_table_metas(
+    input: &Enum<SynMeta, SynMeta>,
+    derive_name: &str,
+) -> Result<SaneTableMetas> {
+    let mut settings = Vec::new();
+
+    for attr in input.attrs.iter() {
+        if !attr.inner.path().is_ident("table") {
+            continue;
+        }
+
+        let SynMeta::List(list) = &*attr.inner else {
+            bail!(attr => "expected `#[table(settings)]`");
+        };
+
+        let TableDeriveSettings { target, tokens } = syn::parse2(list.tokens.clone())?;
+
+        if target.is_none_or(|target| target == derive_name) && !tokens.is_empty() {
+            settings.push(tokens);
+        }
+    }
+
+    parse_table_metas_syn(quote! { #(#settings),* })
+}
+
+/// The contents of the helper attribute `#[table(..)]`, see [`parse_derive_table_metas`].
+struct TableDeriveSettings {
+    target: Option<Ident>,
+    tokens: TokenStream,
+}
+
+impl Parse for TableDeriveSettings {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let fork = input.fork();
+        let target = match fork.parse::<Ident>() {
+            Ok(ident)
+                if TABLE_DERIVES.iter().any(|name| ident == name)
+                    && (fork.is_empty() || fork.peek(Token![,])) =>
+            {
+                input.parse::<Ident>()?;
+                input.parse::<Option<Token![,]>>()?;
+                Some(ident)
+            }
+            _ => None,
+        };
+
+        Ok(TableDeriveSettings {
+            target,
+            tokens: input.parse()?,
+        })
+    }
+}
+
 fn parse_table_metas_inner(
     input_attrs: InputPunctuated<Meta<TableMeta>, Comma>,
 ) -> Result<SaneTableMetas> {
@@ -74,6 +474,40 @@ fn parse_table_metas_inner(
             TableMeta::ModuleName(mod_name) => {
                 assign_unique_or_panic!(sane.mod_name, mod_name);
             }
+            TableMeta::ImplOps(kw) => {
+                assign_unique_or_panic!(sane.impl_ops, kw);
+            }
+            TableMeta::Keys(keys) => {
+                assign_unique_or_panic!(sane.keys, keys);
+            }
+            TableMeta::Storage(storage) => {
+                assign_unique_or_panic!(sane.storage, storage);
+            }
+            TableMeta::ValueTy(value_ty) => {
+                assign_unique_or_panic!(sane.value_ty, value_ty);
+            }
+            TableMeta::Arbitrary(kw) => {
+                if !cfg!(feature = "arbitrary") {
+                    bail!(kw => "`arbitrary` requires the feature `arbitrary` of `spire_enum`.\n\
+                        Help: Enable it in your `Cargo.toml`: `spire_enum = { version = \"..\", features = [\"arbitrary\"] }`");
+                }
+
+                assign_unique_or_panic!(sane.arbitrary, kw);
+            }
+            TableMeta::Proptest(kw) => {
+                if !cfg!(feature = "proptest") {
+                    bail!(kw => "`proptest` requires the feature `proptest` of `spire_enum`.\n\
+                        Help: Enable it in your `Cargo.toml`: `spire_enum = { version = \"..\", features = [\"proptest\"] }`");
+                }
+
+                assign_unique_or_panic!(sane.proptest, kw);
+            }
+            TableMeta::Capacity(capacity) => {
+                assign_unique_or_panic!(sane.capacity, capacity);
+            }
+            TableMeta::Ordinal(kw) => {
+                assign_unique_or_panic!(sane.ordinal, kw);
+            }
             TableMeta::Derive(SettingDerive { kw, paths }) => {
                 let syn_meta: SynMeta = parse_quote! { #kw #paths };
                 sane.syn_metas.push(syn_meta);
@@ -87,6 +521,105 @@ fn parse_table_metas_inner(
     Ok(sane)
 }
 
+impl SaneTableMetas {
+    /// Errors if the table metas contain settings that are only supported by generic tables.
+    fn reject_generic_only(&self) -> Result<()> {
+        if let _Some(kw) = &self.impl_ops {
+            bail!(kw => "the setting `impl_ops` is only supported by generic tables");
+        }
+
+        if let _Some(value_ty) = &self.value_ty {
+            bail!(value_ty => "the setting `value_ty` is only supported by generic tables");
+        }
+
+        Ok(())
+    }
+
+    /// Errors if the table metas contain settings that are only supported by tables keyed by the variant's types.
+    fn reject_variant_keyed_only(&self) -> Result<()> {
+        if let _Some(keys) = &self.keys {
+            bail!(keys => "the setting `keys` is only supported by tables keyed by the variant's types");
+        }
+
+        Ok(())
+    }
+
+    /// Errors if the table metas contain settings that are only supported by tables, as opposed to sets.
+    fn reject_table_only(&self) -> Result<()> {
+        if let _Some(kw) = &self.arbitrary {
+            bail!(kw => "the setting `arbitrary` is not supported by sets");
+        }
+
+        if let _Some(kw) = &self.proptest {
+            bail!(kw => "the setting `proptest` is not supported by sets");
+        }
+
+        Ok(())
+    }
+
+    /// Errors if the table metas contain settings that are only supported by `variant_vecs`.
+    fn reject_vecs_only(&self) -> Result<()> {
+        if let _Some(capacity) = &self.capacity {
+            bail!(capacity => "the setting `capacity` is only supported by `variant_vecs`");
+        }
+
+        Ok(())
+    }
+
+    /// Errors if the table metas contain settings that are only supported by discriminant generic tables.
+    fn reject_discriminant_generic_only(&self) -> Result<()> {
+        if let _Some(storage) = &self.storage {
+            bail!(storage => "the setting `storage` is only supported by `discriminant_generic_table`");
+        }
+
+        if let _Some(kw) = &self.ordinal {
+            bail!(kw => "the setting `ordinal` is only supported by `discriminant_generic_table`.\n\
+                Help: Use the attribute `#[ordinal]` on fieldless enums");
+        }
+
+        Ok(())
+    }
+}
+
+/// Generates the module containing a zero-sized tag type for each variant (setting `keys = tags`).
+///
+/// Returns the module definition along with the path of each tag, relative to the module's parent.
+fn tokenize_tags_mod(
+    tags_mod: &Ident,
+    enum_ident: &Ident,
+    var_cfgs: &[&Any<Attribute<CfgMeta>>],
+    var_idents: &[&Ident],
+) -> (TokenStream, Vec<TokenStream>) {
+    let mod_docs = docs_tokens(format!(
+        "Zero-sized types used as keys to access the values associated with each variant of [`{enum_ident}`] in a table."
+    ));
+
+    let tag_defs = var_cfgs.iter().zip(var_idents).map(|(cfgs, var_ident)| {
+        let docs = docs_tokens(format!("Key of the variant `{enum_ident}::{var_ident}`."));
+
+        quote! {
+            #cfgs
+            #docs
+            #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+            pub struct #var_ident;
+        }
+    });
+
+    let tag_tys = var_idents
+        .iter()
+        .map(|var_ident| quote! { #tags_mod::#var_ident })
+        .collect();
+
+    let mod_def = quote! {
+        #mod_docs
+        pub mod #tags_mod {
+            #(#tag_defs)*
+        }
+    };
+
+    (mod_def, tag_tys)
+}
+
 fn length_definition<'a>(
     ident: &Ident,
     var_cfgs: impl Iterator<Item = &'a Any<Attribute<CfgMeta>>>,
@@ -105,3 +638,55 @@ fn length_definition<'a>(
         };
     }
 }
+
+/// Returns the streams `(<Params>, <Args>, <'lf, Params>, <'lf, Args>)` of the enum's generics.
+///
+/// The non-lifetime streams are empty if the enum has no generics.
+fn generic_streams(
+    generics: &Optional<SaneGenerics>,
+    lf: &Lifetime,
+) -> (TokenStream, TokenStream, TokenStream, TokenStream) {
+    let gen_params_list = {
+        let mut tokens = generics.stream_params_list();
+        if !tokens.is_empty() {
+            tokens.append_terminated(std::iter::empty::<TokenStream>(), <Token![,]>::default());
+        }
+
+        tokens
+    };
+
+    let gen_args_list = {
+        let mut tokens = generics.stream_args_list();
+        if !tokens.is_empty() {
+            tokens.append_terminated(std::iter::empty::<TokenStream>(), <Token![,]>::default());
+        }
+        tokens
+    };
+
+    let (lb, rb) = match generics {
+        _Some(SaneGenerics {
+            input:
+                InputGenerics {
+                    lb_token,
+                    params: _,
+                    rb_token,
+                },
+            where_clause: _,
+        }) => (lb_token, rb_token),
+        _None => (&Default::default(), &Default::default()),
+    };
+
+    let (gen_params, gen_lf_params) = if !gen_params_list.is_empty() {
+        (quote! { #lb #gen_params_list #rb }, quote! { #lb #lf, #gen_params_list #rb })
+    } else {
+        (Default::default(), quote! { #lb #lf #rb })
+    };
+
+    let (gen_args, gen_lf_args) = if !gen_args_list.is_empty() {
+        (quote! { #lb #gen_args_list #rb }, quote! { #lb #lf, #gen_args_list #rb })
+    } else {
+        (Default::default(), quote! { #lb #lf #rb })
+    };
+
+    (gen_params, gen_args, gen_lf_params, gen_lf_args)
+}
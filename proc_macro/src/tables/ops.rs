@@ -0,0 +1,148 @@
+use super::*;
+
+/// Generates the element-wise arithmetic impls requested by the setting `impl_ops`.
+///
+/// `table_ident` must be a struct with a single generic parameter (`gen_t`), where each value in `storage` has the
+/// type `gen_t`.
+pub(super) fn tokenize_ops_impls(
+    table_ident: &Ident,
+    gen_t: &Ident,
+    storage: &TableStorage,
+    var_cfgs: &[&Any<Attribute<CfgMeta>>],
+) -> TokenStream {
+    let table_ty = quote! { #table_ident<#gen_t> };
+    let self_ty = quote! { Self };
+    let self_places = storage.places(&quote! { self });
+    let (self_prelude, self_values) =
+        storage.unpack(&Ident::new("self", Span::call_site()), Unpack::Owned);
+    let (rhs_prelude, rhs_values) =
+        storage.unpack(&Ident::new("rhs", Span::call_site()), Unpack::Owned);
+
+    let binary_ops = [
+        ("Add", "add", "AddAssign", "add_assign"),
+        ("Sub", "sub", "SubAssign", "sub_assign"),
+        ("Mul", "mul", "MulAssign", "mul_assign"),
+        ("Div", "div", "DivAssign", "div_assign"),
+    ]
+    .into_iter()
+    .map(|(op_trait, op_fn, assign_trait, assign_fn)| {
+        let op_trait = Ident::new(op_trait, Span::call_site());
+        let op_fn = Ident::new(op_fn, Span::call_site());
+        let assign_trait = Ident::new(assign_trait, Span::call_site());
+        let assign_fn = Ident::new(assign_fn, Span::call_site());
+
+        let docs_table = docs_tokens(format!(
+            "Applies [`{op_trait}`](::core::ops::{op_trait}) element-wise, between the values of each variant in both tables."
+        ));
+
+        let docs_scalar = docs_tokens(format!(
+            "Applies [`{op_trait}`](::core::ops::{op_trait}) element-wise, between the value of each variant and `rhs`."
+        ));
+
+        let table_op = storage.construct(
+            &self_ty,
+            var_cfgs,
+            &self_values
+                .iter()
+                .zip(&rhs_values)
+                .map(|(lhs, rhs)| quote! { ::core::ops::#op_trait::#op_fn(#lhs, #rhs) })
+                .collect::<Vec<_>>(),
+        );
+
+        let scalar_op = storage.construct(
+            &self_ty,
+            var_cfgs,
+            &self_values
+                .iter()
+                .map(|lhs| quote! { ::core::ops::#op_trait::#op_fn(#lhs, ::core::clone::Clone::clone(&rhs)) })
+                .collect::<Vec<_>>(),
+        );
+
+        quote! {
+            impl<#gen_t: ::core::ops::#op_trait<Output = #gen_t>> ::core::ops::#op_trait for #table_ty {
+                type Output = Self;
+
+                #docs_table
+                fn #op_fn(self, rhs: Self) -> Self {
+                    #self_prelude
+                    #rhs_prelude
+                    #table_op
+                }
+            }
+
+            impl<#gen_t: ::core::ops::#op_trait<Output = #gen_t> + ::core::clone::Clone> ::core::ops::#op_trait<#gen_t> for #table_ty {
+                type Output = Self;
+
+                #docs_scalar
+                fn #op_fn(self, rhs: #gen_t) -> Self {
+                    #self_prelude
+                    #scalar_op
+                }
+            }
+
+            impl<#gen_t: ::core::ops::#assign_trait> ::core::ops::#assign_trait for #table_ty {
+                #docs_table
+                fn #assign_fn(&mut self, rhs: Self) {
+                    #rhs_prelude
+                    #(
+                        #var_cfgs
+                        {
+                            ::core::ops::#assign_trait::#assign_fn(&mut #self_places, #rhs_values);
+                        }
+                    )*
+                }
+            }
+
+            impl<#gen_t: ::core::ops::#assign_trait + ::core::clone::Clone> ::core::ops::#assign_trait<#gen_t> for #table_ty {
+                #docs_scalar
+                fn #assign_fn(&mut self, rhs: #gen_t) {
+                    #(
+                        #var_cfgs
+                        {
+                            ::core::ops::#assign_trait::#assign_fn(&mut #self_places, ::core::clone::Clone::clone(&rhs));
+                        }
+                    )*
+                }
+            }
+        }
+    });
+
+    let neg = storage.construct(
+        &self_ty,
+        var_cfgs,
+        &self_values
+            .iter()
+            .map(|value| quote! { ::core::ops::Neg::neg(#value) })
+            .collect::<Vec<_>>(),
+    );
+
+    let defaults = storage.construct(
+        &self_ty,
+        var_cfgs,
+        &var_cfgs
+            .iter()
+            .map(|_| quote! { ::core::default::Default::default() })
+            .collect::<Vec<_>>(),
+    );
+
+    quote! {
+        #(#binary_ops)*
+
+        impl<#gen_t: ::core::ops::Neg<Output = #gen_t>> ::core::ops::Neg for #table_ty {
+            type Output = Self;
+
+            #[doc = "Applies [`Neg`](::core::ops::Neg) to the value of each variant."]
+            fn neg(self) -> Self {
+                #self_prelude
+                #neg
+            }
+        }
+
+        impl<#gen_t: ::core::ops::Add<Output = #gen_t> + ::core::default::Default> ::core::iter::Sum for #table_ty {
+            #[doc = "Adds all tables element-wise, starting from a table filled with [`Default::default`]."]
+            fn sum<I: ::core::iter::Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(#defaults, ::core::ops::Add::add)
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use super::{
+    discriminant_to_generic::{SaneEnum, SaneVariant, sanitize_enum},
+    *,
+};
+
+pub fn run(settings_stream: TokenStream1, enum_stream: TokenStream1) -> Result<TokenStream> {
+    let settings = TokenStream::from(settings_stream);
+    if !settings.is_empty() {
+        bail!(settings => "`#[ordinal]` does not accept any settings");
+    }
+
+    let input_enum = syn::parse::<Enum<SynMeta, SynMeta>>(enum_stream)?;
+
+    if let Some(attr) = find_enum_attr(&input_enum, "discriminant_generic_table")
+        && let SynMeta::List(list) = &*attr.inner
+        && parse_table_metas_syn(list.tokens.clone())?
+            .ordinal
+            .is_some()
+    {
+        bail!(attr => "`#[discriminant_generic_table(ordinal)]` already generates the ordinal helpers.\n\
+            Help: Remove either the attribute `#[ordinal]` or the setting `ordinal`");
+    }
+
+    let enum_def = input_enum.to_token_stream();
+
+    let SaneEnum {
+        ident: enum_ident,
+        variants,
+        ..
+    } = sanitize_enum(
+        input_enum,
+        Some(
+            "`#[ordinal]` can only be placed on fieldless enums.\n\
+             Help: `#[discriminant_generic_table]` generates the ordinal helpers for the enum `[EnumName]Kind`, \
+             which mirrors the variants of an enum with fields",
+        ),
+    )?;
+
+    let var_cfgs = variants
+        .iter()
+        .map(|SaneVariant { cfg_attrs, .. }| cfg_attrs)
+        .collect::<Vec<_>>();
+
+    let var_idents = variants.iter().map(|var| &var.ident).collect::<Vec<_>>();
+
+    let len_ident = {
+        let enum_upper = enum_ident.to_string().to_case(Case::Constant);
+        Ident::new(&format!("{enum_upper}_LEN"), Span::call_site())
+    };
+
+    let len_def = length_definition(&len_ident, var_cfgs.iter().cloned());
+    let (ordinals_def, ordinals) =
+        storage::ordinal_definitions(&enum_ident, &var_idents, &var_cfgs);
+    let ordinal_impl = tokenize_ordinal_impl(
+        &enum_ident.to_token_stream(),
+        &len_ident,
+        &var_idents,
+        &var_cfgs,
+        &ordinals,
+    );
+
+    // The constants are only needed by the impl, the anonymous scope keeps them out of the user's namespace.
+    Ok(quote! {
+        #enum_def
+
+        const _: () = {
+            #len_def
+            #ordinals_def
+            #ordinal_impl
+        };
+    })
+}
+
+/// Returns the first attribute of `input` whose path ends with `name`.
+pub(super) fn find_enum_attr<'a>(
+    input: &'a Enum<SynMeta, SynMeta>,
+    name: &str,
+) -> Option<&'a Attribute<SynMeta>> {
+    input.attrs.iter().find(|attr| {
+        attr.inner
+            .path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == name)
+    })
+}
+
+/// Generates the inherent ordinal helpers of the fieldless enum `key_ty`: `LEN`, `ordinal`, `from_ordinal`,
+/// `first`, `last`, `next`, `prev`, `cycle_next`, `cycle_prev`, `all` and `range`.
+///
+/// `len_ident` and `ordinals` are the constants generated by [`length_definition`] and
+/// [`storage::ordinal_definitions`], the order of the variants is the order they were declared.
+pub(super) fn tokenize_ordinal_impl(
+    key_ty: &TokenStream,
+    len_ident: &Ident,
+    var_idents: &[&Ident],
+    var_cfgs: &[&Any<Attribute<CfgMeta>>],
+    ordinals: &[Ident],
+) -> TokenStream {
+    let key_name = key_ty.to_string().replace(' ', "");
+    let iter_ty = quote! {
+        impl ::core::iter::DoubleEndedIterator<Item = Self>
+            + ::core::iter::ExactSizeIterator
+            + ::core::clone::Clone
+    };
+
+    quote! {
+        #[allow(unused)]
+        impl #key_ty {
+            #[doc = "The amount of variants, excluding the ones disabled by `#[cfg]` attributes."]
+            pub const LEN: usize = #len_ident;
+
+            #[doc = "Returns the position of `self` in the order the variants were declared (Top to bottom), \
+                     in the range `0..LEN`.\n\n\
+                     Explicit discriminant values are ignored, and variants disabled by `#[cfg]` attributes are not counted."]
+            pub const fn ordinal(&self) -> usize {
+                match self {
+                    #(
+                        #var_cfgs
+                        Self::#var_idents => #ordinals
+                    ),*
+                }
+            }
+
+            #[doc = "Inverse of [`ordinal`](Self::ordinal), returns `None` if `ordinal` is not in the range `0..LEN`."]
+            pub const fn from_ordinal(ordinal: usize) -> ::core::option::Option<Self> {
+                match ordinal {
+                    #(
+                        #var_cfgs
+                        #ordinals => ::core::option::Option::Some(Self::#var_idents),
+                    )*
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            #[doc = "Returns the first variant declared.\n\n\
+                     # Panics\n\
+                     If the enum has no variants."]
+            pub const fn first() -> Self {
+                match Self::from_ordinal(0) {
+                    ::core::option::Option::Some(var) => var,
+                    ::core::option::Option::None => ::core::panic!(::core::concat!("`", #key_name, "` has no variants")),
+                }
+            }
+
+            #[doc = "Returns the last variant declared.\n\n\
+                     # Panics\n\
+                     If the enum has no variants."]
+            pub const fn last() -> Self {
+                match Self::from_ordinal(Self::LEN.wrapping_sub(1)) {
+                    ::core::option::Option::Some(var) => var,
+                    ::core::option::Option::None => ::core::panic!(::core::concat!("`", #key_name, "` has no variants")),
+                }
+            }
+
+            #[doc = "Returns the variant declared after `self`, or `None` if `self` is the [`last`](Self::last)."]
+            pub const fn next(&self) -> ::core::option::Option<Self> {
+                Self::from_ordinal(self.ordinal() + 1)
+            }
+
+            #[doc = "Returns the variant declared before `self`, or `None` if `self` is the [`first`](Self::first)."]
+            pub const fn prev(&self) -> ::core::option::Option<Self> {
+                match self.ordinal().checked_sub(1) {
+                    ::core::option::Option::Some(ordinal) => Self::from_ordinal(ordinal),
+                    ::core::option::Option::None => ::core::option::Option::None,
+                }
+            }
+
+            #[doc = "Like [`next`](Self::next), but wraps around to the [`first`](Self::first) variant."]
+            pub const fn cycle_next(&self) -> Self {
+                match self.next() {
+                    ::core::option::Option::Some(var) => var,
+                    ::core::option::Option::None => Self::first(),
+                }
+            }
+
+            #[doc = "Like [`prev`](Self::prev), but wraps around to the [`last`](Self::last) variant."]
+            pub const fn cycle_prev(&self) -> Self {
+                match self.prev() {
+                    ::core::option::Option::Some(var) => var,
+                    ::core::option::Option::None => Self::last(),
+                }
+            }
+
+            #[doc = "Returns an iterator over all variants, in the order they were declared."]
+            pub fn all() -> #iter_ty {
+                (0..Self::LEN).map(|ordinal| {
+                    match Self::from_ordinal(ordinal) {
+                        ::core::option::Option::Some(var) => var,
+                        ::core::option::Option::None => ::core::unreachable!(),
+                    }
+                })
+            }
+
+            #[doc = "Returns an iterator over the variants from `start` to `end` (both inclusive), in the order they were declared.\n\n\
+                     The iterator is empty if `end` was declared before `start`."]
+            pub fn range(range: ::core::ops::RangeInclusive<Self>) -> #iter_ty {
+                let (start, end) = range.into_inner();
+                (start.ordinal()..end.ordinal() + 1).map(|ordinal| {
+                    match Self::from_ordinal(ordinal) {
+                        ::core::option::Option::Some(var) => var,
+                        ::core::option::Option::None => ::core::unreachable!(),
+                    }
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use super::*;
+
+/// How the values of a generic table are laid out in its struct.
+pub(super) enum TableStorage<'a> {
+    /// One named field per variant.
+    Fields(&'a [&'a Ident]),
+    /// A single array field named `values` (setting `storage = array`),
+    /// each variant's value is at the index stored in its ordinal constant.
+    Array(&'a [Ident]),
+}
+
+/// How [`TableStorage::unpack`] accesses the values of a table.
+#[derive(Clone, Copy)]
+pub(super) enum Unpack {
+    /// Moves the values out of the table.
+    Owned,
+    /// Borrows each value mutably.
+    Mut,
+}
+
+impl TableStorage<'_> {
+    /// Returns the place expression of each variant's value, inside the table `recv`.
+    pub(super) fn places(&self, recv: &TokenStream) -> Vec<TokenStream> {
+        match self {
+            TableStorage::Fields(fields) => {
+                fields.iter().map(|field| quote! { #recv.#field }).collect()
+            }
+            TableStorage::Array(ordinals) => {
+                ordinals
+                    .iter()
+                    .map(|ordinal| quote! { #recv.values[#ordinal] })
+                    .collect()
+            }
+        }
+    }
+
+    /// Moves (or mutably borrows) the values out of the table `recv`.
+    ///
+    /// Returns the statements that must run beforehand, along with an expression for each variant's value.
+    /// The expressions must be evaluated exactly once each, in the order the variants were declared.
+    pub(super) fn unpack(&self, recv: &Ident, mode: Unpack) -> (TokenStream, Vec<TokenStream>) {
+        match self {
+            TableStorage::Fields(_) => {
+                let places = self.places(&recv.to_token_stream());
+                let exprs = match mode {
+                    Unpack::Owned => places,
+                    Unpack::Mut => {
+                        places
+                            .into_iter()
+                            .map(|place| quote! { &mut #place })
+                            .collect()
+                    }
+                };
+
+                (TokenStream::new(), exprs)
+            }
+            TableStorage::Array(ordinals) => {
+                let iter_ident = format_ident!("__{recv}_values");
+                let iter = match mode {
+                    Unpack::Owned => quote! { ::core::iter::IntoIterator::into_iter(#recv.values) },
+                    Unpack::Mut => quote! { #recv.values.iter_mut() },
+                };
+                let prelude = quote! {
+                    let mut #iter_ident = #iter;
+                };
+
+                let exprs = ordinals
+                    .iter()
+                    .map(|_| {
+                        quote! {
+                            match #iter_ident.next() {
+                                ::core::option::Option::Some(value) => value,
+                                ::core::option::Option::None => ::core::unreachable!(),
+                            }
+                        }
+                    })
+                    .collect();
+
+                (prelude, exprs)
+            }
+        }
+    }
+
+    /// Returns an expression that constructs the table `path`, given an expression for each variant's value.
+    ///
+    /// The expressions are evaluated in the order the variants were declared.
+    pub(super) fn construct(
+        &self,
+        path: &TokenStream,
+        var_cfgs: &[&Any<Attribute<CfgMeta>>],
+        exprs: &[TokenStream],
+    ) -> TokenStream {
+        match self {
+            TableStorage::Fields(fields) => {
+                quote! {
+                    #path {
+                        #(
+                            #var_cfgs
+                            #fields: #exprs
+                        ),*
+                    }
+                }
+            }
+            TableStorage::Array(_) => {
+                quote! {
+                    #path {
+                        values: [
+                            #(
+                                #var_cfgs
+                                #exprs
+                            ),*
+                        ]
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Generates a constant for each variant, containing the index of the variant's value in array-backed tables.
+///
+/// Variants disabled by `#[cfg]` attributes are not counted.
+pub(super) fn ordinal_definitions(
+    table_ident: &Ident,
+    var_idents: &[&Ident],
+    var_cfgs: &[&Any<Attribute<CfgMeta>>],
+) -> (TokenStream, Vec<Ident>) {
+    let table_upper = table_ident.to_string().to_case(Case::Constant);
+
+    let ordinals = var_idents
+        .iter()
+        .map(|var_ident| {
+            let var_upper = var_ident.to_string().to_case(Case::Constant);
+            Ident::new(&format!("{table_upper}_{var_upper}_ORDINAL"), Span::call_site())
+        })
+        .collect::<Vec<_>>();
+
+    let defs = ordinals.iter().enumerate().map(|(idx, ordinal)| {
+        let cfgs = var_cfgs[idx];
+        let prev_cfgs = &var_cfgs[..idx];
+
+        quote! {
+            #cfgs
+            #[allow(unused_mut)]
+            const #ordinal: usize = {
+                let mut count = 0;
+                #(
+                    #prev_cfgs
+                    {
+                        count += 1;
+                    }
+                )*
+                count
+            };
+        }
+    });
+
+    (quote! { #(#defs)* }, ordinals)
+}
@@ -0,0 +1,112 @@
+use super::*;
+
+/// How the fields of a variant are mapped to the single value stored in variant tables.
+///
+/// - No fields: the value is `()`.
+/// - A single field: the value is the field itself.
+/// - Several fields: the value is a tuple of the fields, in declaration order.
+pub(super) enum VarShape {
+    Named(Vec<Ident>),
+    Unnamed(usize),
+}
+
+impl VarShape {
+    /// Returns the shape of `fields`, along with the type of the value stored in tables.
+    pub(super) fn new<T>(fields: VarFields<T>) -> (Self, Type) {
+        let (shape, mut tys) = match fields {
+            VarFields::Named(named) => {
+                let (idents, tys): (Vec<_>, Vec<_>) = named
+                    .into_inner()
+                    .inner
+                    .into_iter()
+                    .map(|field| (field.ident, field.ty))
+                    .unzip();
+                (VarShape::Named(idents), tys)
+            }
+            VarFields::Unnamed(unnamed) => {
+                let tys = unnamed
+                    .into_inner()
+                    .inner
+                    .into_iter()
+                    .map(|field| field.ty)
+                    .collect::<Vec<_>>();
+                (VarShape::Unnamed(tys.len()), tys)
+            }
+            VarFields::Unit => (VarShape::Unnamed(0), Vec::new()),
+        };
+
+        let ty = if tys.len() == 1 {
+            tys.remove(0)
+        } else {
+            parse_quote! { ( #(#tys),* ) }
+        };
+
+        (shape, ty)
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            VarShape::Named(idents) => idents.len(),
+            VarShape::Unnamed(len) => *len,
+        }
+    }
+
+    fn bindings(&self) -> Vec<Ident> {
+        (0..self.len())
+            .map(|idx| format_ident!("__field_{idx}"))
+            .collect()
+    }
+
+    /// Returns a pattern that matches the variant at `path`, along with an expression that evaluates to the
+    /// variant's table value (when used in the pattern's scope).
+    ///
+    /// Single-field variants bind their field to `value`.
+    pub(super) fn destructure(
+        &self,
+        path: &TokenStream,
+        value: &Ident,
+    ) -> (TokenStream, TokenStream) {
+        match self {
+            VarShape::Unnamed(0) => (quote! { #path }, quote! { () }),
+            VarShape::Unnamed(1) => (quote! { #path(#value) }, quote! { #value }),
+            VarShape::Named(idents) if idents.len() == 1 => {
+                let ident = &idents[0];
+                (quote! { #path { #ident: #value } }, quote! { #value })
+            }
+            VarShape::Named(idents) => {
+                let bindings = self.bindings();
+                (quote! { #path { #(#idents: #bindings),* } }, quote! { ( #(#bindings),* ) })
+            }
+            VarShape::Unnamed(_) => {
+                let bindings = self.bindings();
+                (quote! { #path( #(#bindings),* ) }, quote! { ( #(#bindings),* ) })
+            }
+        }
+    }
+
+    /// Returns an expression that constructs the variant at `path` from its table value.
+    pub(super) fn construct(&self, path: &TokenStream, value: TokenStream) -> TokenStream {
+        match self {
+            VarShape::Unnamed(0) => quote! {{ let () = #value; #path }},
+            VarShape::Unnamed(1) => quote! { #path(#value) },
+            VarShape::Named(idents) if idents.len() == 1 => {
+                let ident = &idents[0];
+                quote! { #path { #ident: #value } }
+            }
+            VarShape::Named(idents) => {
+                let bindings = self.bindings();
+                quote! {{
+                    let ( #(#bindings),* ) = #value;
+                    #path { #(#idents: #bindings),* }
+                }}
+            }
+            VarShape::Unnamed(_) => {
+                let bindings = self.bindings();
+                quote! {{
+                    let ( #(#bindings),* ) = #value;
+                    #path( #(#bindings),* )
+                }}
+            }
+        }
+    }
+}
@@ -0,0 +1,397 @@
+use super::{
+    variant_type_to_variant_type::{SaneEnum, sanitize_enum, tokenize_key_trait},
+    *,
+};
+
+pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<TokenStream> {
+    let table_attrs = parse_table_metas(input_stream)?;
+    table_attrs.reject_generic_only()?;
+    table_attrs.reject_discriminant_generic_only()?;
+    table_attrs.reject_table_only()?;
+    table_attrs.reject_vecs_only()?;
+    let mut input_enum = syn::parse::<Enum<SynMeta, SynMeta>>(enum_stream)?;
+    let skips = take_table_skips(&mut input_enum);
+    let enum_def = input_enum.to_token_stream();
+    remove_skipped_variants(&mut input_enum, &skips);
+    let has_skipped = skips.contains(&true);
+    let tags_mod = table_attrs.keys.as_ref().map(SettingKeys::tags_mod_ident);
+
+    let SaneEnum {
+        enum_token: _,
+        ident: enum_ident,
+        ty: enum_ty,
+        generics,
+        variants,
+    } = sanitize_enum(input_enum)?;
+
+    let vis = Visibility::Public(Default::default());
+    let lf = Lifetime::new("'_r", Span::call_site());
+    let where_clause = generics.as_pair().1;
+
+    let mod_ident = if let _Some(SettingModName {
+        kw: _,
+        eq_token: _,
+        name,
+    }) = table_attrs.mod_name
+    {
+        name
+    } else {
+        let mut enum_lower = enum_ident.to_string();
+        enum_lower = enum_lower.to_case(Case::Snake);
+        Ident::new(&format!("{enum_lower}_variant_type_set"), Span::call_site())
+    };
+
+    let set_ident = if let _Some(SettingTypeName {
+        kw: _,
+        eq_token: _,
+        name,
+    }) = table_attrs.ty_name
+    {
+        name
+    } else {
+        format_ident!("{enum_ident}VariantTypeSet")
+    };
+
+    let (gen_params, gen_args, gen_lf_params, gen_lf_args) = generic_streams(&generics, &lf);
+
+    let set_ty = if !gen_args.is_empty() {
+        quote! { #set_ident::#gen_args }
+    } else {
+        set_ident.to_token_stream()
+    };
+
+    let field_idents = variants
+        .iter()
+        .map(|var| &var.table_field_ident)
+        .collect::<Vec<_>>();
+
+    let var_cfgs: Vec<&Any<Attribute<CfgMeta>>> =
+        variants.iter().map(|var| &var.cfg_attrs).collect();
+
+    let var_tys: Vec<&Type> = variants.iter().map(|var| &var.ty).collect();
+    let var_idents: Vec<&Ident> = variants.iter().map(|var| &var.ident).collect();
+
+    let (tags_def, tag_tys) = match &tags_mod {
+        Some(tags_mod) => {
+            let (tags_def, tag_tys) =
+                tokenize_tags_mod(tags_mod, &enum_ident, &var_cfgs, &var_idents);
+            (tags_def, Some(tag_tys))
+        }
+        None => (TokenStream::new(), None),
+    };
+
+    let tags_reexport = tags_mod.as_ref().map(|tags_mod| {
+        quote! {
+            #[allow(unused_imports)]
+            pub(crate) use #mod_ident::#tags_mod;
+        }
+    });
+
+    // The type returned by `get::<Var>()`
+    let var_value = if tags_mod.is_some() {
+        quote! { Var::Value }
+    } else {
+        quote! { Var }
+    };
+    let value_ident = Ident::new("__var", Span::call_site());
+    let skipped_arm = if has_skipped {
+        quote! {
+            #[allow(unreachable_patterns)]
+            __skipped => ::core::option::Option::Some(__skipped),
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let (var_patterns, var_values): (Vec<_>, Vec<_>) = variants
+        .iter()
+        .map(|var| {
+            let ident = &var.ident;
+            var.shape
+                .destructure(&quote! { #enum_ident::#ident }, &value_ident)
+        })
+        .unzip();
+
+    let var_constructs = variants
+        .iter()
+        .map(|var| {
+            let ident = &var.ident;
+            var.shape
+                .construct(&quote! { #enum_ident::#ident }, value_ident.to_token_stream())
+        })
+        .collect::<Vec<_>>();
+
+    const MACRO_LINK: &str = "spire_enum_macros::variant_type_set";
+    const DOCS_INTRO: &str = "This type was generated by an invocation of the macro [`variant_type_set`](spire_enum_macros::variant_type_set).";
+
+    let set_def = {
+        let attrs = table_attrs.syn_metas;
+
+        let docs = docs_tokens(format!(
+            "{DOCS_INTRO}\n\n\
+             A fixed-size collection that contains at most one value of each variant of [`{enum_ident}`].\n\
+             Variants can be accessed by calling [`get::<VarType>()`]({set_ident}::get) or [`get_mut`]({set_ident}::get_mut).\n\
+             For a full list of all methods generated for this type, see the [macro]({MACRO_LINK}) documentation.\n\n\
+             This type does not allocate heap memory (can be used in `no_std`)."
+        ));
+
+        quote! {
+            #docs
+            #(#[#attrs])*
+            #vis struct #set_ident #gen_params #where_clause {
+                #(
+                    #var_cfgs
+                    pub #field_idents: ::core::option::Option<#var_tys>
+                ),*
+            }
+        }
+    };
+
+    let (_enum_ref_ident, enum_ref_ty, enum_ref_def) = enum_refs::tokenize_enum_ref(
+        &vis,
+        &enum_ident,
+        &gen_lf_params,
+        &gen_lf_args,
+        where_clause,
+        &var_cfgs,
+        &var_tys,
+        &var_idents,
+        tags_mod.is_none(),
+    );
+
+    let (_enum_mut_ident, enum_mut_ty, enum_mut_def) = enum_refs::tokenize_enum_mut(
+        &vis,
+        &enum_ident,
+        &gen_lf_params,
+        &gen_lf_args,
+        where_clause,
+        &var_cfgs,
+        &var_tys,
+        &var_idents,
+        tags_mod.is_none(),
+    );
+
+    let key_trait_ident = Ident::new("__KeyInSet", Span::call_site());
+    let key_trait_ty = quote! { #key_trait_ident #gen_args };
+    let (key_trait_def, key_in_impls) = tokenize_key_trait(
+        &key_trait_ident,
+        &set_ty,
+        &if tags_mod.is_some() {
+            quote! { ::core::option::Option<Self::Value> }
+        } else {
+            quote! { ::core::option::Option<Self> }
+        },
+        &gen_params,
+        &gen_args,
+        where_clause,
+        &var_cfgs,
+        &var_tys,
+        tag_tys.as_deref(),
+        &field_idents,
+    );
+
+    let len_ident = {
+        let set_upper = set_ident.to_string().to_case(Case::Constant);
+        Ident::new(&format!("{}_LEN", set_upper), Span::call_site())
+    };
+
+    let len_def = length_definition(&len_ident, var_cfgs.iter().cloned());
+
+    let set_impls = {
+        let docs_get = docs_tokens(format!(
+            "Returns a reference to the value of the unique type `Key`, if it is present in this set.\n\n\
+             Note that [`{key_trait_ident}`] is only implemented for the variants of `[{enum_ident}]."
+        ));
+
+        let docs_get_mut = docs_tokens(format!(
+            "Returns a mutable reference to the value of the unique type `Key`, if it is present in this set.\n\n\
+             Note that [`{key_trait_ident}`] is only implemented for the variants of `[{enum_ident}]."
+        ));
+
+        let docs_insert = docs_tokens(format!(
+            "Inserts `value` in the slot of its variant.\n\n\
+             Returns the value that previously occupied that slot (if any), wrapped in [`{enum_ident}`].\n\n\
+             Variants marked with `#[table_skip]` have no slot, they are returned as is."
+        ));
+
+        let docs_into_iter = docs_tokens(format!(
+            "Convert this set into an iterator that yields all values(variants of [`{enum_ident}`]) that were inside this set.\n\
+             - It is guaranteed that each value yielded is a unique variant of the enum [`{enum_ident}`].\n\
+             - It is guaranteed that values will be yielded in the exact order they were declared in [`{enum_ident}`] (Top to bottom).\n\
+             - The iterator returned does not allocate heap memory, it is merely a fixed-size array with length known at compile time."
+        ));
+
+        let docs_iter = docs_tokens(format!(
+            "Iterates through references of all values(variants of [`{enum_ident}`]) present in this set.\n\
+             - It is guaranteed that each value yielded is a unique variant of the enum [`{enum_ident}`].\n\
+             - It is guaranteed that values will be yielded in the exact order they were declared in [`{enum_ident}`] (Top to bottom).\n\
+             - The iterator returned does not allocate heap memory, it is merely a fixed-size array with length known at compile time."
+        ));
+
+        let docs_iter_mut = docs_tokens(format!(
+            "Iterates through mutable references of all values(variants of [`{enum_ident}`]) present in this set.\n\
+             - It is guaranteed that each value yielded is a unique variant of the enum [`{enum_ident}`].\n\
+             - It is guaranteed that values will be yielded in the exact order they were declared in [`{enum_ident}`] (Top to bottom).\n\
+             - The iterator returned does not allocate heap memory, it is merely a fixed-size array with length known at compile time."
+        ));
+
+        quote! {
+            #[allow(unused)]
+            impl #gen_params #set_ty #where_clause {
+                #[doc = "Constructs a new, empty, instance of the type."]
+                pub const fn new() -> Self {
+                    Self {
+                        #(
+                            #var_cfgs
+                            #field_idents: ::core::option::Option::None
+                        ),*
+                    }
+                }
+
+                #docs_get
+                pub fn get<Var: #key_trait_ty>(&self) -> ::core::option::Option<&#var_value> {
+                    Var::__get_in_table(self).as_ref()
+                }
+
+                #docs_get_mut
+                pub fn get_mut<Var: #key_trait_ty>(&mut self) -> ::core::option::Option<&mut #var_value> {
+                    Var::__get_in_table_mut(self).as_mut()
+                }
+
+                #[doc = "Returns `true` if the variant of type `Var` is present in this set."]
+                pub fn contains<Var: #key_trait_ty>(&self) -> bool {
+                    Var::__get_in_table(self).is_some()
+                }
+
+                #docs_insert
+                pub fn insert(&mut self, value: #enum_ty) -> ::core::option::Option<#enum_ty> {
+                    match value {
+                        #(
+                            #var_cfgs
+                            #var_patterns => {
+                                self.#field_idents.replace(#var_values).map(|__var| #var_constructs)
+                            }
+                        )*
+                        #skipped_arm
+                    }
+                }
+
+                #[doc = "Removes the variant of type `Var` from this set, returning it if it was present."]
+                pub fn remove<Var: #key_trait_ty>(&mut self) -> ::core::option::Option<#var_value> {
+                    Var::__get_in_table_mut(self).take()
+                }
+
+                #[doc = "Removes all values from this set."]
+                pub fn clear(&mut self) {
+                    #(
+                        #var_cfgs
+                        {
+                            self.#field_idents = ::core::option::Option::None;
+                        }
+                    )*
+                }
+
+                #[doc = "Returns the amount of variants present in this set."]
+                pub const fn len(&self) -> usize {
+                    let mut count = 0;
+
+                    #(
+                        #var_cfgs
+                        {
+                            if self.#field_idents.is_some() {
+                                count += 1;
+                            }
+                        }
+                    )*
+
+                    count
+                }
+
+                #[doc = "Returns `true` if this set contains no values."]
+                pub const fn is_empty(&self) -> bool {
+                    self.len() == 0
+                }
+
+                #[allow(clippy::needless_lifetimes)]
+                #docs_iter
+                pub fn iter<#lf>(&#lf self) -> ::core::iter::Flatten<::core::array::IntoIter<::core::option::Option<#enum_ref_ty>, #len_ident>> {
+                    [
+                        #(
+                            #var_cfgs
+                            self.#field_idents.as_ref().map(#enum_ref_ty::#var_idents)
+                        ),*
+                    ].into_iter().flatten()
+                }
+
+                #[allow(clippy::needless_lifetimes)]
+                #docs_iter_mut
+                pub fn iter_mut<#lf>(&#lf mut self) -> ::core::iter::Flatten<::core::array::IntoIter<::core::option::Option<#enum_mut_ty>, #len_ident>> {
+                    [
+                        #(
+                            #var_cfgs
+                            self.#field_idents.as_mut().map(#enum_mut_ty::#var_idents)
+                        ),*
+                    ].into_iter().flatten()
+                }
+            }
+
+            impl #gen_params ::core::default::Default for #set_ty #where_clause {
+                #[doc = "See [`new`](Self::new)"]
+                fn default() -> Self { Self::new() }
+            }
+
+            impl #gen_params ::core::iter::IntoIterator for #set_ty #where_clause {
+                type Item = #enum_ty;
+                type IntoIter = ::core::iter::Flatten<::core::array::IntoIter<::core::option::Option<Self::Item>, #len_ident>>;
+
+                #docs_into_iter
+                fn into_iter(self) -> Self::IntoIter {
+                    [
+                        #(
+                            #var_cfgs
+                            self.#field_idents.map(|__var| #var_constructs)
+                        ),*
+                    ].into_iter().flatten()
+                }
+            }
+
+            impl #gen_lf_params ::core::iter::IntoIterator for &#lf #set_ty #where_clause {
+                type Item = #enum_ref_ty;
+                type IntoIter = ::core::iter::Flatten<::core::array::IntoIter<::core::option::Option<Self::Item>, #len_ident>>;
+
+                #[doc = "See [`iter`](Self::iter)"]
+                fn into_iter(self) -> Self::IntoIter { self.iter() }
+            }
+
+            impl #gen_lf_params ::core::iter::IntoIterator for &#lf mut #set_ty #where_clause {
+                type Item = #enum_mut_ty;
+                type IntoIter = ::core::iter::Flatten<::core::array::IntoIter<::core::option::Option<Self::Item>, #len_ident>>;
+
+                #[doc = "See [`iter_mut`](Self::iter_mut)"]
+                fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
+            }
+        }
+    };
+
+    Ok(quote! {
+        #enum_def
+
+        #[allow(unused_imports)]
+        pub(crate) use #mod_ident::#set_ident;
+        #tags_reexport
+
+        #[allow(unused_imports)]
+        mod #mod_ident {
+            use super::*;
+
+            #len_def
+            #tags_def
+            #key_trait_def
+            #set_def
+            #enum_ref_def
+            #enum_mut_def
+            #key_in_impls
+            #set_impls
+        }
+    })
+}
@@ -3,7 +3,47 @@ use super::*;
 pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<TokenStream> {
     let table_attrs = parse_table_metas(input_stream)?;
     let input_enum = syn::parse::<Enum<SynMeta, SynMeta>>(enum_stream)?;
-    let enum_def = input_enum.to_token_stream();
+    tokenize(table_attrs, input_enum, EnumOrigin::Attribute)
+}
+
+/// Derive form, which leaves the enum untouched, see [`parse_derive_table_metas`].
+pub fn run_derive(enum_stream: TokenStream1) -> Result<TokenStream> {
+    let input_enum = syn::parse::<Enum<SynMeta, SynMeta>>(enum_stream)?;
+    let table_attrs = parse_derive_table_metas(&input_enum, "VariantGenericTable")?;
+    tokenize(table_attrs, input_enum, EnumOrigin::Derive)
+}
+
+/// Function-like form, for enums defined elsewhere, see [`ForeignEnum`].
+pub fn run_foreign(input_stream: TokenStream1) -> Result<TokenStream> {
+    let foreign = syn::parse::<ForeignEnum<SynMeta, SynMeta>>(input_stream)?;
+    let module_items = tokenize_foreign_module_items(&foreign);
+    let ForeignEnum {
+        input_enum,
+        settings,
+        ..
+    } = foreign;
+
+    tokenize(parse_table_metas_syn(settings)?, input_enum, EnumOrigin::Foreign(module_items))
+}
+
+/// See [`EnumOrigin`] for the differences between the forms of the macro.
+fn tokenize(
+    table_attrs: SaneTableMetas,
+    mut input_enum: Enum<SynMeta, SynMeta>,
+    origin: EnumOrigin,
+) -> Result<TokenStream> {
+    table_attrs.reject_discriminant_generic_only()?;
+    table_attrs.reject_vecs_only()?;
+    let skips = take_table_skips(&mut input_enum);
+    let default_values = take_default_values(&mut input_enum)?
+        .into_iter()
+        .zip(&skips)
+        .filter_map(|(value, skip)| (!skip).then_some(value))
+        .collect::<Vec<_>>();
+    let enum_def = origin.enum_def(&input_enum);
+    let foreign_items = origin.foreign_items();
+    remove_skipped_variants(&mut input_enum, &skips);
+    let tags_mod = table_attrs.keys.as_ref().map(SettingKeys::tags_mod_ident);
 
     let SaneEnum {
         ident: enum_ident,
@@ -56,6 +96,22 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
         .map(|var| &var.cfg_attrs)
         .collect::<Vec<_>>();
 
+    let arbitrary_impls = arbitrary::tokenize_arbitrary_impls(
+        &table_attrs.arbitrary,
+        &table_attrs.proptest,
+        &table_ty,
+        arbitrary::TableGenerics::Value(&gen_t),
+        &var_cfgs,
+        &vec![gen_t.to_token_stream(); var_cfgs.len()],
+        |exprs| {
+            TableStorage::Fields(&fields).construct(
+                &table_ident.to_token_stream(),
+                &var_cfgs,
+                exprs,
+            )
+        },
+    );
+
     const MACRO_LINK: &str = "spire_enum_macros::variant_generic_table";
     const DOCS_INTRO: &str = "This type was generated by an invocation of the macro [`variant_generic_table`](spire_enum_macros::variant_generic_table).";
 
@@ -147,30 +203,70 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
         }
     };
 
+    let enum_key_ident = Ident::new(&format!("{enum_ident}Key"), Span::call_site());
+    let enum_key_def = {
+        let docs = docs_tokens(format!(
+            "{DOCS_INTRO}\n\n\
+			 An enum that mirrors the variant names of [`{enum_ident}`], without any fields.\n\n\
+			 This enum is used to identify variants by value in methods of [`{table_ident}`] \
+			 (such as [`from_fn`]({table_ident}::from_fn) and [`map`]({table_ident}::map))."
+        ));
+
+        quote! {
+            #docs
+            #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+            #vis enum #enum_key_ident {
+                #(
+                    #var_cfgs
+                    #var_idents
+                ),*
+            }
+        }
+    };
+
     let key_trait_ident = Ident::new("__KeyInTable", Span::call_site());
     let key_trait_ty = &key_trait_ident;
     let key_trait_def = quote! {
         #[doc(hidden)]
         pub trait #key_trait_ident {
+            const __KEY: #enum_key_ident;
             fn __get_in_table<'__a, #gen_t>(table: &'__a #table_ty) -> &'__a #gen_t where #gen_t: '__a;
             fn __get_in_table_mut<'__a, #gen_t>(table: &'__a mut #table_ty) -> &'__a mut #gen_t where #gen_t: '__a;
         }
     };
 
+    let (tags_def, tags_reexport) = match &tags_mod {
+        Some(tags_mod) => {
+            let var_idents = variants.iter().map(|var| &var.ident).collect::<Vec<_>>();
+            let (tags_def, _) = tokenize_tags_mod(tags_mod, &enum_ident, &var_cfgs, &var_idents);
+            let tags_reexport = quote! {
+                #[allow(unused_imports)]
+                pub(crate) use #mod_ident::#tags_mod;
+            };
+
+            (tags_def, tags_reexport)
+        }
+        None => (TokenStream::new(), TokenStream::new()),
+    };
+
     let key_in_impls = variants.iter().map(
         |SaneVariant {
             cfg_attrs,
-            ident: _,
+            ident,
             table_field_ident,
             ty,
             generics: var_generics,
         }| {
-            let var_params = var_generics.stream_params();
+            let (var_params, key_ty) = match &tags_mod {
+                Some(tags_mod) => (TokenStream::new(), quote! { #tags_mod::#ident }),
+                None => (var_generics.stream_params(), ty.to_token_stream()),
+            };
 
             quote! {
                 #cfg_attrs
                 #[doc(hidden)]
-				impl #var_params #key_trait_ty for #ty {
+				impl #var_params #key_trait_ty for #key_ty {
+                    const __KEY: #enum_key_ident = #enum_key_ident::#ident;
 					fn __get_in_table<'__a, #gen_t>(table: &'__a #table_ty) -> &'__a #gen_t where #gen_t: '__a {
 						&table.#table_field_ident
 					}
@@ -187,8 +283,30 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
         Ident::new(&format!("{}_LEN", table_upper), Span::call_site())
     };
 
+    let ops_impls = if table_attrs.impl_ops.is_some() {
+        ops::tokenize_ops_impls(&table_ident, &gen_t, &TableStorage::Fields(&fields), &var_cfgs)
+    } else {
+        TokenStream::new()
+    };
+
     let len_def = length_definition(&len_ident, var_cfgs.iter().cloned());
 
+    let defaults_impls = defaults::tokenize_defaults_impls(
+        &table_ident,
+        table_attrs.value_ty.as_ref(),
+        &enum_key_ident.to_token_stream(),
+        &quote! { var },
+        &var_idents
+            .iter()
+            .map(|var| quote! { #enum_key_ident::#var })
+            .collect::<Vec<_>>(),
+        &var_cfgs,
+        &default_values,
+        defaults::Reset::ByType {
+            key_trait: &key_trait_ident,
+        },
+    )?;
+
     let table_impls = {
         let docs_new = docs_tokens(format!(
             "Constructs a new instance of the type.\n\n\
@@ -226,6 +344,30 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
              - The iterator returned does not allocate heap memory, it is merely a fixed-size array with length known at compile time."
         ));
 
+        let docs_from_fn = docs_tokens(format!(
+            "Constructs a new instance of the type, invoking the closure `f` for every variant of [`{enum_ident}`].\n\n\
+             The closure is invoked in the exact order the variants were declared in [`{enum_ident}`] (Top to bottom)."
+        ));
+
+        let docs_map = docs_tokens(format!(
+            "Converts this table into a table of `Mapped`, by invoking the closure `f` for every variant of [`{enum_ident}`] \
+             and its associated value.\n\n\
+             The closure is invoked in the exact order the variants were declared in [`{enum_ident}`] (Top to bottom)."
+        ));
+
+        let docs_try_map = docs_tokens(format!(
+            "Fallible version of [`map`](Self::map).\n\n\
+             The closure is invoked in the exact order the variants were declared in [`{enum_ident}`] (Top to bottom), \
+             returning the first error encountered."
+        ));
+
+        let docs_try_from_iter = docs_tokens(format!(
+            "Constructs a new instance of the type from pairs of `({enum_key_ident}, {gen_t})`.\n\n\
+             If a variant appears more than once, the last value is kept.\n\n\
+             # Errors\n\
+             If any variant of [`{enum_ident}`] is not present in `iter`, the error lists all missing variants."
+        ));
+
         quote! {
             #[allow(unused)]
             #[allow(clippy::too_many_arguments)]
@@ -256,6 +398,16 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
                     }
                 }
 
+                #docs_from_fn
+                pub fn from_fn(mut f: impl FnMut(#enum_key_ident) -> #gen_t) -> Self {
+                    Self {
+                        #(
+                            #var_cfgs
+                            #fields: f(#enum_key_ident::#var_idents)
+                        ),*
+                    }
+                }
+
                 #docs_get
                 pub fn get<Key: #key_trait_ty>(&self) -> &#gen_t {
                     Key::__get_in_table::<#gen_t>(self)
@@ -295,6 +447,158 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
                         ),*
                     ].into_iter()
                 }
+
+                #[doc = "Iterates through the keys of all variants, in the exact order they were declared (Top to bottom)."]
+                pub fn keys(&self) -> ::core::array::IntoIter<#enum_key_ident, #len_ident> {
+                    [
+                        #(
+                            #var_cfgs
+                            #enum_key_ident::#var_idents
+                        ),*
+                    ].into_iter()
+                }
+
+                #[allow(clippy::needless_lifetimes)]
+                #[doc = "Iterates through references of all values, in the exact order their variants were declared (Top to bottom)."]
+                pub fn values<#lf>(&#lf self) -> ::core::array::IntoIter<&#lf #gen_t, #len_ident> {
+                    [
+                        #(
+                            #var_cfgs
+                            &self.#fields
+                        ),*
+                    ].into_iter()
+                }
+
+                #[allow(clippy::needless_lifetimes)]
+                #[doc = "Iterates through mutable references of all values, in the exact order their variants were declared (Top to bottom)."]
+                pub fn values_mut<#lf>(&#lf mut self) -> ::core::array::IntoIter<&#lf mut #gen_t, #len_ident> {
+                    [
+                        #(
+                            #var_cfgs
+                            &mut self.#fields
+                        ),*
+                    ].into_iter()
+                }
+
+                #[doc = "Converts this table into an iterator of its values, in the exact order their variants were declared (Top to bottom)."]
+                pub fn into_values(self) -> ::core::array::IntoIter<#gen_t, #len_ident> {
+                    [
+                        #(
+                            #var_cfgs
+                            self.#fields
+                        ),*
+                    ].into_iter()
+                }
+
+                #docs_map
+                pub fn map<Mapped>(self, mut f: impl FnMut(#enum_key_ident, #gen_t) -> Mapped) -> #table_ident<Mapped> {
+                    #table_ident {
+                        #(
+                            #var_cfgs
+                            #fields: f(#enum_key_ident::#var_idents, self.#fields)
+                        ),*
+                    }
+                }
+
+                #docs_try_map
+                pub fn try_map<Mapped, Error>(
+                    self,
+                    mut f: impl FnMut(#enum_key_ident, #gen_t) -> ::core::result::Result<Mapped, Error>,
+                ) -> ::core::result::Result<#table_ident<Mapped>, Error> {
+                    ::core::result::Result::Ok(#table_ident {
+                        #(
+                            #var_cfgs
+                            #fields: f(#enum_key_ident::#var_idents, self.#fields)?
+                        ),*
+                    })
+                }
+
+                #[doc = "Combines this table with `other`, pairing the values associated with each variant."]
+                pub fn zip<Other>(self, other: #table_ident<Other>) -> #table_ident<(#gen_t, Other)> {
+                    #table_ident {
+                        #(
+                            #var_cfgs
+                            #fields: (self.#fields, other.#fields)
+                        ),*
+                    }
+                }
+
+                #[doc = "Returns a table containing references to the values of this table."]
+                pub const fn as_ref(&self) -> #table_ident<&#gen_t> {
+                    #table_ident {
+                        #(
+                            #var_cfgs
+                            #fields: &self.#fields
+                        ),*
+                    }
+                }
+
+                #[doc = "Returns a table containing mutable references to the values of this table."]
+                pub const fn as_mut(&mut self) -> #table_ident<&mut #gen_t> {
+                    #table_ident {
+                        #(
+                            #var_cfgs
+                            #fields: &mut self.#fields
+                        ),*
+                    }
+                }
+
+                #docs_try_from_iter
+                pub fn try_from_iter(
+                    iter: impl ::core::iter::IntoIterator<Item = (#enum_key_ident, #gen_t)>,
+                ) -> ::core::result::Result<Self, ::spire_enum::prelude::MissingVariants<#len_ident>> {
+                    let mut slots = #table_ident::<::core::option::Option<#gen_t>> {
+                        #(
+                            #var_cfgs
+                            #fields: ::core::option::Option::None
+                        ),*
+                    };
+
+                    for (key, value) in iter {
+                        match key {
+                            #(
+                                #var_cfgs
+                                #enum_key_ident::#var_idents => {
+                                    slots.#fields = ::core::option::Option::Some(value);
+                                }
+                            )*
+                        }
+                    }
+
+                    let mut missing = ::spire_enum::prelude::MissingVariants::new();
+
+                    #(
+                        #var_cfgs
+                        {
+                            if slots.#fields.is_none() {
+                                missing.push(::core::stringify!(#var_idents));
+                            }
+                        }
+                    )*
+
+                    if !missing.is_empty() {
+                        return ::core::result::Result::Err(missing);
+                    }
+
+                    ::core::result::Result::Ok(slots.map(|_, value| {
+                        match value {
+                            ::core::option::Option::Some(value) => value,
+                            ::core::option::Option::None => ::core::unreachable!(),
+                        }
+                    }))
+                }
+            }
+
+            impl #gen_params ::core::iter::FromIterator<(#enum_key_ident, #gen_t)> for #table_ty {
+                #[doc = "See [`try_from_iter`](Self::try_from_iter).\n\n\
+                         # Panics\n\
+                         If any variant is not present in `iter`."]
+                fn from_iter<I: ::core::iter::IntoIterator<Item = (#enum_key_ident, #gen_t)>>(iter: I) -> Self {
+                    match Self::try_from_iter(iter) {
+                        ::core::result::Result::Ok(table) => table,
+                        ::core::result::Result::Err(missing) => ::core::panic!("{missing}"),
+                    }
+                }
             }
 
             impl #gen_params ::core::iter::IntoIterator for #table_ty {
@@ -334,19 +638,27 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
         #enum_def
         #[allow(unused_imports)]
         pub(crate) use #mod_ident::#table_ident;
+        #tags_reexport
 
         #[allow(unused_imports)]
         mod #mod_ident {
             use super::*;
 
+            #foreign_items
+
             #len_def
+            #tags_def
             #key_trait_def
             #table_def
             #enum_ref_def
             #enum_mut_def
             #enum_own_def
+            #enum_key_def
             #(#key_in_impls)*
             #table_impls
+            #ops_impls
+            #defaults_impls
+            #arbitrary_impls
         }
     })
 }
@@ -388,46 +700,20 @@ fn sanitize_enum(input: Enum<SynMeta, SynMeta>) -> Result<SaneEnum> {
                  fields,
                  discriminant: _,
              }| {
-                const HELP: &str =
-                    "A variant table can only be generated if all variants have exactly one field.";
-
                 let generics = generics_needed_by_variant(&fields, &sane_enum_generics);
                 let cfg_attrs = parse_cfg_attrs(attrs);
-
-                match fields {
-                    VarFields::Named(named) => {
-                        if named.len() == 1 {
-                            let ty = named.into_inner().inner.into_iter().next().unwrap().ty;
-                            Ok(SaneVariant {
-                                cfg_attrs,
-                                table_field_ident: var_to_field_ident(&ident),
-                                ident,
-                                ty,
-                                generics,
-                            })
-                        } else {
-                            bail!(named => HELP)
-                        }
-                    }
-                    VarFields::Unnamed(unnamed) => {
-                        if unnamed.len() == 1 {
-                            let ty = unnamed.into_inner().inner.into_iter().next().unwrap().ty;
-                            Ok(SaneVariant {
-                                cfg_attrs,
-                                table_field_ident: var_to_field_ident(&ident),
-                                ident,
-                                ty,
-                                generics,
-                            })
-                        } else {
-                            bail!(unnamed => HELP)
-                        }
-                    }
-                    VarFields::Unit => bail!(fields => HELP),
+                let (_, ty) = VarShape::new(fields);
+
+                SaneVariant {
+                    cfg_attrs,
+                    table_field_ident: var_to_field_ident(&ident),
+                    ident,
+                    ty,
+                    generics,
                 }
             },
         )
-        .try_collect()?;
+        .collect();
 
     Ok(SaneEnum { ident, variants })
 }
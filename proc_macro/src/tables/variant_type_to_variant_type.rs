@@ -1,11 +1,46 @@
-use quote::TokenStreamExt;
-
 use super::*;
 
 pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<TokenStream> {
     let table_attrs = parse_table_metas(input_stream)?;
     let input_enum = syn::parse::<Enum<SynMeta, SynMeta>>(enum_stream)?;
-    let enum_def = input_enum.to_token_stream();
+    tokenize(table_attrs, input_enum, EnumOrigin::Attribute)
+}
+
+/// Derive form, which leaves the enum untouched, see [`parse_derive_table_metas`].
+pub fn run_derive(enum_stream: TokenStream1) -> Result<TokenStream> {
+    let input_enum = syn::parse::<Enum<SynMeta, SynMeta>>(enum_stream)?;
+    let table_attrs = parse_derive_table_metas(&input_enum, "VariantTypeTable")?;
+    tokenize(table_attrs, input_enum, EnumOrigin::Derive)
+}
+
+/// Function-like form, for enums defined elsewhere, see [`ForeignEnum`].
+pub fn run_foreign(input_stream: TokenStream1) -> Result<TokenStream> {
+    let foreign = syn::parse::<ForeignEnum<SynMeta, SynMeta>>(input_stream)?;
+    let module_items = tokenize_foreign_module_items(&foreign);
+    let ForeignEnum {
+        input_enum,
+        settings,
+        ..
+    } = foreign;
+
+    tokenize(parse_table_metas_syn(settings)?, input_enum, EnumOrigin::Foreign(module_items))
+}
+
+/// See [`EnumOrigin`] for the differences between the forms of the macro.
+fn tokenize(
+    table_attrs: SaneTableMetas,
+    mut input_enum: Enum<SynMeta, SynMeta>,
+    origin: EnumOrigin,
+) -> Result<TokenStream> {
+    table_attrs.reject_generic_only()?;
+    table_attrs.reject_discriminant_generic_only()?;
+    table_attrs.reject_vecs_only()?;
+    let skips = take_table_skips(&mut input_enum);
+    let enum_def = origin.enum_def(&input_enum);
+    let foreign_items = origin.foreign_items();
+    remove_skipped_variants(&mut input_enum, &skips);
+    let has_skipped = skips.contains(&true);
+    let tags_mod = table_attrs.keys.as_ref().map(SettingKeys::tags_mod_ident);
 
     let SaneEnum {
         enum_token: _,
@@ -43,51 +78,7 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
         format_ident!("{enum_ident}VariantTypeTable")
     };
 
-    let (gen_params, gen_args, gen_lf_params, gen_lf_args) = {
-        let gen_params_list = {
-            let mut tokens = generics.stream_params_list();
-            if !tokens.is_empty() {
-                tokens.append_terminated(std::iter::empty::<TokenStream>(), <Token![,]>::default());
-            }
-
-            tokens
-        };
-
-        let gen_args_list = {
-            let mut tokens = generics.stream_args_list();
-            if !tokens.is_empty() {
-                tokens.append_terminated(std::iter::empty::<TokenStream>(), <Token![,]>::default());
-            }
-            tokens
-        };
-
-        let (lb, rb) = match &generics {
-            _Some(SaneGenerics {
-                input:
-                    InputGenerics {
-                        lb_token,
-                        params: _,
-                        rb_token,
-                    },
-                where_clause: _,
-            }) => (lb_token, rb_token),
-            _None => (&Default::default(), &Default::default()),
-        };
-
-        let (gen_params, gen_lf_params) = if !gen_params_list.is_empty() {
-            (quote! { #lb #gen_params_list #rb }, quote! { #lb #lf, #gen_params_list #rb })
-        } else {
-            (Default::default(), quote! { #lb #lf #rb })
-        };
-
-        let (gen_args, gen_lf_args) = if !gen_args_list.is_empty() {
-            (quote! { #lb #gen_args_list #rb }, quote! { #lb #lf, #gen_args_list #rb })
-        } else {
-            (Default::default(), quote! { #lb #lf #rb })
-        };
-
-        (gen_params, gen_args, gen_lf_params, gen_lf_args)
-    };
+    let (gen_params, gen_args, gen_lf_params, gen_lf_args) = generic_streams(&generics, &lf);
 
     let table_ty = if !gen_args.is_empty() {
         quote! { #table_ident::#gen_args }
@@ -106,6 +97,76 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
     let var_tys: Vec<&Type> = variants.iter().map(|var| &var.ty).collect();
     let var_idents: Vec<&Ident> = variants.iter().map(|var| &var.ident).collect();
 
+    let (tags_def, tag_tys) = match &tags_mod {
+        Some(tags_mod) => {
+            let (tags_def, tag_tys) =
+                tokenize_tags_mod(tags_mod, &enum_ident, &var_cfgs, &var_idents);
+            (tags_def, Some(tag_tys))
+        }
+        None => (TokenStream::new(), None),
+    };
+
+    let tags_reexport = tags_mod.as_ref().map(|tags_mod| {
+        quote! {
+            #[allow(unused_imports)]
+            pub(crate) use #mod_ident::#tags_mod;
+        }
+    });
+
+    // The type returned by `get::<Var>()`
+    let var_value = if tags_mod.is_some() {
+        quote! { Var::Value }
+    } else {
+        quote! { Var }
+    };
+    let value_ident = Ident::new("__var", Span::call_site());
+    let skipped_arm = if has_skipped {
+        quote! {
+            #[allow(unreachable_patterns)]
+            _ => {}
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let (var_patterns, var_values): (Vec<_>, Vec<_>) = variants
+        .iter()
+        .map(|var| {
+            let ident = &var.ident;
+            var.shape
+                .destructure(&quote! { #enum_ident::#ident }, &value_ident)
+        })
+        .unzip();
+
+    let var_constructs = variants
+        .iter()
+        .map(|var| {
+            let ident = &var.ident;
+            let field = &var.table_field_ident;
+            var.shape
+                .construct(&quote! { #enum_ident::#ident }, quote! { self.#field })
+        })
+        .collect::<Vec<_>>();
+
+    let arbitrary_impls = arbitrary::tokenize_arbitrary_impls(
+        &table_attrs.arbitrary,
+        &table_attrs.proptest,
+        &table_ty,
+        arbitrary::TableGenerics::Enum(&generics),
+        &var_cfgs,
+        &var_tys
+            .iter()
+            .map(ToTokens::to_token_stream)
+            .collect::<Vec<_>>(),
+        |exprs| {
+            TableStorage::Fields(&field_idents).construct(
+                &table_ident.to_token_stream(),
+                &var_cfgs,
+                exprs,
+            )
+        },
+    );
+
     const MACRO_LINK: &str = "spire_enum_macros::variant_type_table";
     const DOCS_INTRO: &str = "This type was generated by an invocation of the macro [`variant_type_table`](spire_enum_macros::variant_type_table).";
 
@@ -140,6 +201,7 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
         &var_cfgs,
         &var_tys,
         &var_idents,
+        tags_mod.is_none(),
     );
 
     let (_enum_mut_ident, enum_mut_ty, enum_mut_def) = enum_refs::tokenize_enum_mut(
@@ -151,42 +213,187 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
         &var_cfgs,
         &var_tys,
         &var_idents,
+        tags_mod.is_none(),
     );
 
     let key_trait_ident = Ident::new("__KeyInTable", Span::call_site());
     let key_trait_ty = quote! { #key_trait_ident #gen_args };
-    let key_trait_def = {
+    let (key_trait_def, key_in_impls) = tokenize_key_trait(
+        &key_trait_ident,
+        &table_ty,
+        &if tags_mod.is_some() {
+            quote! { Self::Value }
+        } else {
+            quote! { Self }
+        },
+        &gen_params,
+        &gen_args,
+        where_clause,
+        &var_cfgs,
+        &var_tys,
+        tag_tys.as_deref(),
+        &field_idents,
+    );
+
+    let len_ident = {
+        let table_upper = table_ident.to_string().to_case(Case::Constant);
+        Ident::new(&format!("{}_LEN", table_upper), Span::call_site())
+    };
+
+    let len_def = length_definition(&len_ident, var_cfgs.iter().cloned());
+
+    let builder_ident = format_ident!("{table_ident}Builder");
+    let builder_ty = if !gen_args.is_empty() {
+        quote! { #builder_ident::#gen_args }
+    } else {
+        builder_ident.to_token_stream()
+    };
+
+    let builder_key_trait_ident = Ident::new("__KeyInBuilder", Span::call_site());
+    let builder_key_trait_ty = quote! { #builder_key_trait_ident #gen_args };
+    let (builder_key_trait_def, builder_key_in_impls) = tokenize_key_trait(
+        &builder_key_trait_ident,
+        &builder_ty,
+        &if tags_mod.is_some() {
+            quote! { ::core::option::Option<Self::Value> }
+        } else {
+            quote! { ::core::option::Option<Self> }
+        },
+        &gen_params,
+        &gen_args,
+        where_clause,
+        &var_cfgs,
+        &var_tys,
+        tag_tys.as_deref(),
+        &field_idents,
+    );
+
+    let builder_def = {
+        let docs = docs_tokens(format!(
+            "{DOCS_INTRO}\n\n\
+             Builds a [`{table_ident}`] from values provided in any order, \
+             useful when the values come from partial sources (e.g. save files, registrations).\n\n\
+             Values can be provided with [`with::<VarType>()`]({builder_ident}::with), \
+             [`insert_enum`]({builder_ident}::insert_enum) or by collecting an iterator of [`{enum_ident}`], \
+             then the table is constructed with [`build`]({builder_ident}::build).\n\n\
+             This type does not allocate heap memory (can be used in `no_std`)."
+        ));
+
         quote! {
-            #[doc(hidden)]
-            pub trait #key_trait_ident #gen_params #where_clause {
-                fn __get_in_table<'__a>(table: &'__a #table_ty) -> &'__a Self where Self: '__a;
-                fn __get_in_table_mut<'__a>(table: &'__a mut #table_ty) -> &'__a mut Self where Self: '__a;
+            #docs
+            #vis struct #builder_ident #gen_params #where_clause {
+                #(
+                    #var_cfgs
+                    #field_idents: ::core::option::Option<#var_tys>
+                ),*
             }
         }
     };
 
-    let key_in_impls = quote! {
-        #(
-            #var_cfgs
-            #[doc(hidden)]
-            impl #gen_params #key_trait_ty for #var_tys #where_clause {
-                fn __get_in_table<'__a>(table: &'__a #table_ty) -> &'__a Self where Self: '__a {
-                    &table.#field_idents
+    let builder_impls = quote! {
+        #[allow(unused)]
+        impl #gen_params #builder_ty #where_clause {
+            #[doc = "Constructs a builder that has no values."]
+            pub const fn new() -> Self {
+                Self {
+                    #(
+                        #var_cfgs
+                        #field_idents: ::core::option::Option::None
+                    ),*
+                }
+            }
+
+            #[doc = "Sets the value of the variant of type `Var`, replacing the previous one (if any)."]
+            pub fn with<Var: #builder_key_trait_ty>(mut self, value: #var_value) -> Self {
+                *Var::__get_in_table_mut(&mut self) = ::core::option::Option::Some(value);
+                self
+            }
+
+            #[doc = "Sets the value of the variant contained in `value`.\n\n\
+                     Returns an error containing `value` if that variant already has a value, \
+                     in which case the builder is left unchanged.\n\n\
+                     Variants marked with `#[table_skip]` are ignored."]
+            pub fn insert_enum(&mut self, value: #enum_ty) -> ::core::result::Result<(), ::spire_enum::prelude::Duplicate<#enum_ty>> {
+                let duplicate = match &value {
+                    #(
+                        #var_cfgs
+                        #enum_ident::#var_idents { .. } => {
+                            self.#field_idents.is_some().then_some(::core::stringify!(#var_idents))
+                        }
+                    )*
+                    #[allow(unreachable_patterns)]
+                    _ => ::core::option::Option::None,
+                };
+
+                if let ::core::option::Option::Some(name) = duplicate {
+                    return ::core::result::Result::Err(::spire_enum::prelude::Duplicate::new(name, value));
                 }
 
-                fn __get_in_table_mut<'__a>(table: &'__a mut #table_ty) -> &'__a mut Self where Self: '__a {
-                    &mut table.#field_idents
+                self.replace_enum(value);
+                ::core::result::Result::Ok(())
+            }
+
+            fn replace_enum(&mut self, value: #enum_ty) {
+                match value {
+                    #(
+                        #var_cfgs
+                        #var_patterns => {
+                            self.#field_idents = ::core::option::Option::Some(#var_values);
+                        }
+                    )*
+                    #skipped_arm
                 }
             }
-        )*
-    };
 
-    let len_ident = {
-        let table_upper = table_ident.to_string().to_case(Case::Constant);
-        Ident::new(&format!("{}_LEN", table_upper), Span::call_site())
-    };
+            #[doc = "Constructs the table, if every variant has a value.\n\n\
+                     Otherwise, returns an error listing the variants that have no value."]
+            pub fn build(self) -> ::core::result::Result<#table_ty, ::spire_enum::prelude::MissingVariants<#len_ident>> {
+                let mut missing = ::spire_enum::prelude::MissingVariants::new();
 
-    let len_def = length_definition(&len_ident, var_cfgs.iter().cloned());
+                #(
+                    #var_cfgs
+                    {
+                        if self.#field_idents.is_none() {
+                            missing.push(::core::stringify!(#var_idents));
+                        }
+                    }
+                )*
+
+                if !missing.is_empty() {
+                    return ::core::result::Result::Err(missing);
+                }
+
+                ::core::result::Result::Ok(#table_ident {
+                    #(
+                        #var_cfgs
+                        #field_idents: match self.#field_idents {
+                            ::core::option::Option::Some(value) => value,
+                            ::core::option::Option::None => ::core::unreachable!(),
+                        }
+                    ),*
+                })
+            }
+        }
+
+        impl #gen_params ::core::default::Default for #builder_ty #where_clause {
+            fn default() -> Self { Self::new() }
+        }
+
+        impl #gen_params ::core::iter::FromIterator<#enum_ty> for #builder_ty #where_clause {
+            #[doc = "Sets the value of each variant yielded by `iter`.\n\n\
+                     If a variant is yielded more than once, the last value is kept.\n\n\
+                     Variants marked with `#[table_skip]` are ignored."]
+            fn from_iter<I: ::core::iter::IntoIterator<Item = #enum_ty>>(iter: I) -> Self {
+                let mut builder = Self::new();
+
+                for value in iter {
+                    builder.replace_enum(value);
+                }
+
+                builder
+            }
+        }
+    };
 
     let table_impls = {
         let docs_new = docs_tokens(format!(
@@ -195,6 +402,9 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
 			 all fields(variants) must be initialized during construction."
         ));
 
+        let docs_builder =
+            docs_tokens(format!("Returns a builder that has no values, see [`{builder_ident}`]."));
+
         let docs_get = docs_tokens(format!(
             "Returns a reference to the value of the unique type `Key` that is always present in this table.\n\n\
              Note that [`{key_trait_ident}`] is only implemented for the variants of `[{enum_ident}].\n\n\
@@ -248,13 +458,18 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
                     }
                 }
 
+                #docs_builder
+                pub const fn builder() -> #builder_ty {
+                    #builder_ident::new()
+                }
+
                 #docs_get
-                pub fn get<Var: #key_trait_ty>(&self) -> &Var {
+                pub fn get<Var: #key_trait_ty>(&self) -> &#var_value {
                     Var::__get_in_table(self)
                 }
 
                 #docs_get_mut
-                pub fn get_mut<Var: #key_trait_ty>(&mut self) -> &mut Var {
+                pub fn get_mut<Var: #key_trait_ty>(&mut self) -> &mut #var_value {
                     Var::__get_in_table_mut(self)
                 }
 
@@ -262,21 +477,23 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
                          This is shorthand for `*self.get_mut::<Var>() = some_variant;`.\n\
                          This method is provided merely as \"sugar\" for those who aren't \
                          very familiar with dereferencing."]
-                pub fn set<Var: #key_trait_ty>(&mut self, value: Var) {
+                pub fn set<Var: #key_trait_ty>(&mut self, value: #var_value) {
                     *self.get_mut::<Var>() = value;
                 }
 
                 #[doc = "Replaces one of the table's fields in a \"untyped\" way.\n\
                          Use this when you want to replace a variable in the table without \
-                         having to match on each case of the enum."]
+                         having to match on each case of the enum.\n\n\
+                         Variants marked with `#[table_skip]` are ignored."]
                 pub fn set_enum(&mut self, value: #enum_ty) {
                     match value {
                         #(
                             #var_cfgs
-                            #enum_ident::#var_idents(__var) => {
-                                self.#field_idents = __var;
+                            #var_patterns => {
+                                self.#field_idents = #var_values;
                             }
                         )*
+                        #skipped_arm
                     }
                 }
 
@@ -312,7 +529,7 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
                     [
                         #(
                             #var_cfgs
-                            <#enum_ty>::#var_idents(self.#field_idents)
+                            #var_constructs
                         ),*
                     ].into_iter()
                 }
@@ -340,40 +557,130 @@ pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<Toke
         #enum_def
 
         #[allow(unused_imports)]
-        pub(crate) use #mod_ident::#table_ident;
+        pub(crate) use #mod_ident::{#table_ident, #builder_ident};
+        #tags_reexport
 
         #[allow(unused_imports)]
         mod #mod_ident {
             use super::*;
 
+            #foreign_items
+
             #len_def
+            #tags_def
             #key_trait_def
             #table_def
             #enum_ref_def
             #enum_mut_def
             #key_in_impls
             #table_impls
+            #builder_key_trait_def
+            #builder_def
+            #builder_key_in_impls
+            #builder_impls
+            #arbitrary_impls
         }
     })
 }
 
-struct SaneEnum {
+/// Generates the hidden trait used to implement `Table::get::<Var>()`,
+/// along with its implementations for each variant's type.
+///
+/// `slot_ty` is the type of the table's field, relative to `Self` (e.g. `Self` or `Option<Self>`).
+///
+/// If `tag_tys` is provided (setting `keys = tags`), the trait is implemented for the tags instead,
+/// the variant's type becomes the associated type `Value`, and `slot_ty` should be relative to `Self::Value`.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn tokenize_key_trait(
+    key_trait_ident: &Ident,
+    table_ty: &TokenStream,
+    slot_ty: &TokenStream,
+    gen_params: &TokenStream,
+    gen_args: &TokenStream,
+    where_clause: Optional<&WhereClause>,
+    var_cfgs: &[&Any<Attribute<CfgMeta>>],
+    var_tys: &[&Type],
+    tag_tys: Option<&[TokenStream]>,
+    field_idents: &[&Ident],
+) -> (TokenStream, TokenStream) {
+    let key_trait_ty = quote! { #key_trait_ident #gen_args };
+
+    let (value_def, bounded, key_tys, value_impls) = match tag_tys {
+        Some(tag_tys) => {
+            (
+                quote! { type Value; },
+                quote! { Self::Value },
+                tag_tys
+                    .iter()
+                    .map(ToTokens::to_token_stream)
+                    .collect::<Vec<_>>(),
+                var_tys
+                    .iter()
+                    .map(|ty| quote! { type Value = #ty; })
+                    .collect(),
+            )
+        }
+        None => {
+            (
+                TokenStream::new(),
+                quote! { Self },
+                var_tys.iter().map(ToTokens::to_token_stream).collect(),
+                var_tys
+                    .iter()
+                    .map(|_| TokenStream::new())
+                    .collect::<Vec<_>>(),
+            )
+        }
+    };
+
+    let key_trait_def = quote! {
+        #[doc(hidden)]
+        pub trait #key_trait_ident #gen_params: Sized #where_clause {
+            #value_def
+            fn __get_in_table<'__a>(table: &'__a #table_ty) -> &'__a #slot_ty where #bounded: '__a;
+            fn __get_in_table_mut<'__a>(table: &'__a mut #table_ty) -> &'__a mut #slot_ty where #bounded: '__a;
+        }
+    };
+
+    let key_in_impls = quote! {
+        #(
+            #var_cfgs
+            #[doc(hidden)]
+            impl #gen_params #key_trait_ty for #key_tys #where_clause {
+                #value_impls
+
+                fn __get_in_table<'__a>(table: &'__a #table_ty) -> &'__a #slot_ty where #bounded: '__a {
+                    &table.#field_idents
+                }
+
+                fn __get_in_table_mut<'__a>(table: &'__a mut #table_ty) -> &'__a mut #slot_ty where #bounded: '__a {
+                    &mut table.#field_idents
+                }
+            }
+        )*
+    };
+
+    (key_trait_def, key_in_impls)
+}
+
+pub(super) struct SaneEnum {
     #[allow(unused)]
-    enum_token: Token![enum],
-    ident: Ident,
-    ty: Type,
-    generics: Optional<SaneGenerics>,
-    variants: Vec<SaneVariant>,
+    pub enum_token: Token![enum],
+    pub ident: Ident,
+    pub ty: Type,
+    pub generics: Optional<SaneGenerics>,
+    pub variants: Vec<SaneVariant>,
 }
 
-struct SaneVariant {
-    cfg_attrs: Any<Attribute<CfgMeta>>,
-    ident: Ident,
-    table_field_ident: Ident,
-    ty: Type,
+pub(super) struct SaneVariant {
+    pub cfg_attrs: Any<Attribute<CfgMeta>>,
+    pub ident: Ident,
+    pub table_field_ident: Ident,
+    pub ty: Type,
+    pub shape: VarShape,
 }
 
-fn sanitize_enum(input: Enum<SynMeta, SynMeta>) -> Result<SaneEnum> {
+pub(super) fn sanitize_enum(input: Enum<SynMeta, SynMeta>) -> Result<SaneEnum> {
     let Enum {
         attrs: _,
         vis: _,
@@ -398,43 +705,19 @@ fn sanitize_enum(input: Enum<SynMeta, SynMeta>) -> Result<SaneEnum> {
                  fields,
                  discriminant: _,
              }| {
-                const HELP: &str =
-                    "A variant table can only be generated if all variants have exactly one field.";
-
                 let cfg_attrs = parse_cfg_attrs(attrs);
-
-                match fields {
-                    VarFields::Named(named) => {
-                        if named.len() == 1 {
-                            let ty = named.into_inner().inner.into_iter().next().unwrap().ty;
-                            Ok(SaneVariant {
-                                cfg_attrs,
-                                table_field_ident: var_to_field_ident(&ident),
-                                ident,
-                                ty,
-                            })
-                        } else {
-                            bail!(named => HELP)
-                        }
-                    }
-                    VarFields::Unnamed(unnamed) => {
-                        if unnamed.len() == 1 {
-                            let ty = unnamed.into_inner().inner.into_iter().next().unwrap().ty;
-                            Ok(SaneVariant {
-                                cfg_attrs,
-                                table_field_ident: var_to_field_ident(&ident),
-                                ident,
-                                ty,
-                            })
-                        } else {
-                            bail!(unnamed => HELP)
-                        }
-                    }
-                    VarFields::Unit => bail!(fields => HELP),
+                let (shape, ty) = VarShape::new(fields);
+
+                SaneVariant {
+                    cfg_attrs,
+                    table_field_ident: var_to_field_ident(&ident),
+                    ident,
+                    ty,
+                    shape,
                 }
             },
         )
-        .try_collect()?;
+        .collect();
 
     Ok(SaneEnum {
         enum_token,
@@ -0,0 +1,503 @@
+use super::{
+    variant_type_to_variant_type::{SaneEnum, sanitize_enum, tokenize_key_trait},
+    *,
+};
+
+pub fn run(input_stream: TokenStream1, enum_stream: TokenStream1) -> Result<TokenStream> {
+    let table_attrs = parse_table_metas(input_stream)?;
+    table_attrs.reject_generic_only()?;
+    table_attrs.reject_discriminant_generic_only()?;
+
+    if let _Some(kw) = &table_attrs.arbitrary {
+        bail!(kw => "the setting `arbitrary` is not supported by `variant_vecs`");
+    }
+
+    if let _Some(kw) = &table_attrs.proptest {
+        bail!(kw => "the setting `proptest` is not supported by `variant_vecs`");
+    }
+
+    let input_enum = syn::parse::<Enum<SynMeta, SynMeta>>(enum_stream)?;
+
+    reject_table_skips(&input_enum, "variant_vecs")?;
+
+    if table_attrs.capacity.is_none() && !cfg!(feature = "alloc") {
+        bail!(input_enum.ident => "`#[variant_vecs]` requires the feature `alloc` of `spire_enum`, unless a capacity is provided.\n\
+            Help: Enable it in your `Cargo.toml`: `spire_enum = { version = \"..\", features = [\"alloc\"] }`, \
+            or use fixed-capacity storage: `#[variant_vecs(capacity = N)]`");
+    }
+
+    let enum_def = input_enum.to_token_stream();
+    let tags_mod = table_attrs.keys.as_ref().map(SettingKeys::tags_mod_ident);
+
+    let SaneEnum {
+        enum_token: _,
+        ident: enum_ident,
+        ty: enum_ty,
+        generics,
+        variants,
+    } = sanitize_enum(input_enum)?;
+
+    let vis = Visibility::Public(Default::default());
+    let lf = Lifetime::new("'_r", Span::call_site());
+    let where_clause = generics.as_pair().1;
+
+    let mod_ident = if let _Some(SettingModName {
+        kw: _,
+        eq_token: _,
+        name,
+    }) = table_attrs.mod_name
+    {
+        name
+    } else {
+        let mut enum_lower = enum_ident.to_string();
+        enum_lower = enum_lower.to_case(Case::Snake);
+        Ident::new(&format!("{enum_lower}_variant_vecs"), Span::call_site())
+    };
+
+    let vecs_ident = if let _Some(SettingTypeName {
+        kw: _,
+        eq_token: _,
+        name,
+    }) = table_attrs.ty_name
+    {
+        name
+    } else {
+        format_ident!("{enum_ident}VariantVecs")
+    };
+
+    let macro_ident =
+        format_ident!("for_each_delegated_{}", vecs_ident.to_string().to_case(Case::Snake));
+
+    let (gen_params, gen_args, ..) = generic_streams(&generics, &lf);
+
+    let vecs_ty = if !gen_args.is_empty() {
+        quote! { #vecs_ident::#gen_args }
+    } else {
+        vecs_ident.to_token_stream()
+    };
+
+    let field_idents = variants
+        .iter()
+        .map(|var| &var.table_field_ident)
+        .collect::<Vec<_>>();
+
+    let var_cfgs: Vec<&Any<Attribute<CfgMeta>>> =
+        variants.iter().map(|var| &var.cfg_attrs).collect();
+
+    let var_tys: Vec<&Type> = variants.iter().map(|var| &var.ty).collect();
+    let var_idents: Vec<&Ident> = variants.iter().map(|var| &var.ident).collect();
+
+    let (tags_def, tag_tys) = match &tags_mod {
+        Some(tags_mod) => {
+            let (tags_def, tag_tys) =
+                tokenize_tags_mod(tags_mod, &enum_ident, &var_cfgs, &var_idents);
+            (tags_def, Some(tag_tys))
+        }
+        None => (TokenStream::new(), None),
+    };
+
+    let tags_reexport = tags_mod.as_ref().map(|tags_mod| {
+        quote! {
+            #[allow(unused_imports)]
+            pub(crate) use #mod_ident::#tags_mod;
+        }
+    });
+
+    // The type of the values of `get::<Var>()`
+    let var_value = if tags_mod.is_some() {
+        quote! { Var::Value }
+    } else {
+        quote! { Var }
+    };
+
+    let capacity_ident = {
+        let vecs_upper = vecs_ident.to_string().to_case(Case::Constant);
+        Ident::new(&format!("{vecs_upper}_CAPACITY"), Span::call_site())
+    };
+
+    // The storage of each variant, and the tokens that differ between `Vec` and `FixedVec`.
+    let storage_of = |ty: &TokenStream| -> TokenStream {
+        match &table_attrs.capacity {
+            _Some(_) => quote! { ::spire_enum::prelude::FixedVec<#ty, #capacity_ident> },
+            _None => quote! { ::spire_enum::alloc::vec::Vec<#ty> },
+        }
+    };
+
+    let (capacity_def, capacity_reexport, const_token, drain_args, retain_where) =
+        match &table_attrs.capacity {
+            _Some(SettingCapacity { len, .. }) => {
+                let docs = docs_tokens(format!(
+                    "The maximum amount of values of each variant that [`{vecs_ident}`] can contain."
+                ));
+
+                (
+                    quote! { #docs pub const #capacity_ident: usize = #len; },
+                    Some(
+                        quote! { #[allow(unused_imports)] pub(crate) use #mod_ident::#capacity_ident; },
+                    ),
+                    None,
+                    TokenStream::new(),
+                    // `FixedVec` keeps default values in its unused slots.
+                    Some(quote! { where #var_value: ::core::default::Default }),
+                )
+            }
+            _None => (TokenStream::new(), None, Some(quote! { const }), quote! { .. }, None),
+        };
+
+    let var_storages = var_tys
+        .iter()
+        .map(|ty| storage_of(&ty.to_token_stream()))
+        .collect::<Vec<_>>();
+
+    let value_ident = Ident::new("__var", Span::call_site());
+
+    let (var_patterns, var_values): (Vec<_>, Vec<_>) = variants
+        .iter()
+        .map(|var| {
+            let ident = &var.ident;
+            var.shape
+                .destructure(&quote! { #enum_ident::#ident }, &value_ident)
+        })
+        .unzip();
+
+    let var_constructs = variants
+        .iter()
+        .map(|var| {
+            let ident = &var.ident;
+            var.shape
+                .construct(&quote! { #enum_ident::#ident }, value_ident.to_token_stream())
+        })
+        .collect::<Vec<_>>();
+
+    const MACRO_LINK: &str = "spire_enum_macros::variant_vecs";
+    const DOCS_INTRO: &str = "This type was generated by an invocation of the macro [`variant_vecs`](spire_enum_macros::variant_vecs).";
+
+    let vecs_def = {
+        let attrs = table_attrs.syn_metas;
+
+        let storage_docs = match &table_attrs.capacity {
+            _Some(_) => {
+                format!(
+                    "Each variant is stored in a [`FixedVec`](::spire_enum::prelude::FixedVec) of capacity \
+                     [`{capacity_ident}`], this type does not allocate heap memory (can be used in `no_std`)."
+                )
+            }
+            _None => "Each variant is stored in a `Vec`.".to_string(),
+        };
+
+        let docs = docs_tokens(format!(
+            "{DOCS_INTRO}\n\n\
+             A collection of values of [`{enum_ident}`], which stores each variant in a separate vector, \
+             so that the values of a variant can be processed in batches, without branching on the variant.\n\
+             Variants can be accessed by calling [`iter::<VarType>()`]({vecs_ident}::iter) or [`iter_mut`]({vecs_ident}::iter_mut).\n\
+             For a full list of all methods generated for this type, see the [macro]({MACRO_LINK}) documentation.\n\n\
+             {storage_docs}"
+        ));
+
+        quote! {
+            #docs
+            #(#[#attrs])*
+            #vis struct #vecs_ident #gen_params #where_clause {
+                #(
+                    #var_cfgs
+                    pub #field_idents: #var_storages
+                ),*
+            }
+        }
+    };
+
+    let key_trait_ident = Ident::new("__KeyInVecs", Span::call_site());
+    let key_trait_ty = quote! { #key_trait_ident #gen_args };
+    let (key_trait_def, key_in_impls) = tokenize_key_trait(
+        &key_trait_ident,
+        &vecs_ty,
+        &storage_of(&if tags_mod.is_some() {
+            quote! { Self::Value }
+        } else {
+            quote! { Self }
+        }),
+        &gen_params,
+        &gen_args,
+        where_clause,
+        &var_cfgs,
+        &var_tys,
+        tag_tys.as_deref(),
+        &field_idents,
+    );
+
+    let var_storage = storage_of(&var_value);
+
+    let (push_ret, push_exprs, push_docs) = match &table_attrs.capacity {
+        _Some(_) => {
+            (
+                quote! { -> ::core::result::Result<(), #enum_ty> },
+                field_idents
+                    .iter()
+                    .zip(&var_values)
+                    .zip(&var_constructs)
+                    .map(|((field, value), construct)| {
+                        quote! { self.#field.push(#value).map_err(|__var| #construct) }
+                    })
+                    .collect::<Vec<_>>(),
+                format!(
+                    "Appends `value` to the vector of its variant.\n\n\
+                     Returns an error containing `value` if that vector already contains [`{capacity_ident}`] values."
+                ),
+            )
+        }
+        _None => {
+            (
+                TokenStream::new(),
+                field_idents
+                    .iter()
+                    .zip(&var_values)
+                    .map(|(field, value)| quote! { self.#field.push(#value) })
+                    .collect::<Vec<_>>(),
+                "Appends `value` to the vector of its variant.".to_string(),
+            )
+        }
+    };
+
+    let docs_drain = docs_tokens(format!(
+        "Removes all values, returning them (wrapped in [`{enum_ident}`]) in an iterator.\n\n\
+         The values are yielded grouped by variant, in the order the variants were declared in [`{enum_ident}`] \
+         (Top to bottom), the order in which the values of different variants were pushed is not preserved."
+    ));
+
+    let vecs_impls = quote! {
+        #[allow(unused)]
+        impl #gen_params #vecs_ty #where_clause {
+            #[doc = "Constructs a new, empty, instance of the type."]
+            pub #const_token fn new() -> Self {
+                Self {
+                    #(
+                        #var_cfgs
+                        #field_idents: <#var_storages>::new()
+                    ),*
+                }
+            }
+
+            #[doc = #push_docs]
+            pub fn push(&mut self, value: #enum_ty) #push_ret {
+                match value {
+                    #(
+                        #var_cfgs
+                        #var_patterns => #push_exprs,
+                    )*
+                }
+            }
+
+            #[doc = "Returns a reference to the vector of the variant of type `Var`."]
+            pub fn get<Var: #key_trait_ty>(&self) -> &#var_storage {
+                Var::__get_in_table(self)
+            }
+
+            #[doc = "Returns a mutable reference to the vector of the variant of type `Var`."]
+            pub fn get_mut<Var: #key_trait_ty>(&mut self) -> &mut #var_storage {
+                Var::__get_in_table_mut(self)
+            }
+
+            #[doc = "Iterates through references of the values of the variant of type `Var`, in the order they were pushed."]
+            pub fn iter<Var: #key_trait_ty>(&self) -> ::core::slice::Iter<'_, #var_value> {
+                Var::__get_in_table(self).iter()
+            }
+
+            #[doc = "Iterates through mutable references of the values of the variant of type `Var`, in the order they were pushed."]
+            pub fn iter_mut<Var: #key_trait_ty>(&mut self) -> ::core::slice::IterMut<'_, #var_value> {
+                Var::__get_in_table_mut(self).iter_mut()
+            }
+
+            #[doc = "Returns the amount of values of the variant of type `Var`."]
+            pub fn len_of<Var: #key_trait_ty>(&self) -> usize {
+                Var::__get_in_table(self).len()
+            }
+
+            #[doc = "Retains only the values of the variant of type `Var` for which `f` returns `true`, preserving their order."]
+            pub fn retain<Var: #key_trait_ty>(&mut self, f: impl FnMut(&#var_value) -> bool) #retain_where {
+                Var::__get_in_table_mut(self).retain(f)
+            }
+
+            #[doc = "Returns the amount of values of all variants."]
+            pub fn len(&self) -> usize {
+                let mut count = 0;
+
+                #(
+                    #var_cfgs
+                    {
+                        count += self.#field_idents.len();
+                    }
+                )*
+
+                count
+            }
+
+            #[doc = "Returns `true` if this collection contains no values."]
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            #[doc = "Removes all values of all variants."]
+            pub fn clear(&mut self) {
+                #(
+                    #var_cfgs
+                    self.#field_idents.clear();
+                )*
+            }
+
+            #docs_drain
+            pub fn drain(&mut self) -> impl ::core::iter::Iterator<Item = #enum_ty> + '_ {
+                let __iter = ::core::iter::empty();
+
+                #(
+                    #var_cfgs
+                    let __iter = __iter.chain(self.#field_idents.drain(#drain_args).map(|__var| #var_constructs));
+                )*
+
+                __iter
+            }
+        }
+
+        impl #gen_params ::core::default::Default for #vecs_ty #where_clause {
+            #[doc = "See [`new`](Self::new)"]
+            fn default() -> Self { Self::new() }
+        }
+    };
+
+    // `push` is infallible without a capacity.
+    let collect_impls = table_attrs.capacity.is_none().then(|| {
+        quote! {
+            impl #gen_params ::core::iter::Extend<#enum_ty> for #vecs_ty #where_clause {
+                fn extend<__I: ::core::iter::IntoIterator<Item = #enum_ty>>(&mut self, iter: __I) {
+                    for value in iter {
+                        self.push(value);
+                    }
+                }
+            }
+
+            impl #gen_params ::core::iter::FromIterator<#enum_ty> for #vecs_ty #where_clause {
+                fn from_iter<__I: ::core::iter::IntoIterator<Item = #enum_ty>>(iter: __I) -> Self {
+                    let mut vecs = Self::new();
+                    vecs.extend(iter);
+                    vecs
+                }
+            }
+        }
+    });
+
+    let macro_def = {
+        let delegate_macro = crate::delegate_macro_ident(&enum_ident);
+
+        // Wraps each value back into its variant, so that the delegation macro of the enum applies the same
+        // delegation as it does for the enum (e.g. `#[delegator]` fields, `#[delegate_via(..)]`).
+        let delegate_fns = var_constructs
+            .iter()
+            .zip(&var_patterns)
+            .zip(&var_values)
+            .map(|((construct, pattern), value)| {
+                quote! {
+                    |__var| {
+                        let mut __enum = #construct;
+                        #delegate_macro!((&mut __enum) => |$arg| { $($body)* });
+
+                        #[allow(unreachable_patterns)]
+                        match __enum {
+                            #pattern => #value,
+                            _ => ::core::unreachable!(),
+                        }
+                    }
+                }
+            });
+
+        let for_each_stmts = field_idents
+            .iter()
+            .zip(delegate_fns)
+            .map(|(field, delegate_fn)| {
+                match &table_attrs.capacity {
+                    // Reuses the allocation of the vector.
+                    _None => {
+                        quote! {
+                            __vecs.#field = ::core::mem::take(&mut __vecs.#field)
+                                .into_iter()
+                                .map(#delegate_fn)
+                                .collect();
+                        }
+                    }
+                    // The values are pushed back into the same vector, which cannot be full.
+                    _Some(_) => {
+                        quote! {
+                            let mut __storage = ::core::mem::take(&mut __vecs.#field);
+                            let mut __delegate = #delegate_fn;
+                            for __var in __storage.drain() {
+                                let _ = __vecs.#field.push(__delegate(__var));
+                            }
+                        }
+                    }
+                }
+            });
+
+        let delegate_macro_name = delegate_macro.to_string();
+        let docs = docs_tokens(format!(
+            "\n\
+             This macro was generated by an invocation of [`variant_vecs`]({MACRO_LINK}).\n\
+             \n\
+             Runs the same expression on each value of [`{vecs_ident}`], with one loop per variant, \
+             so the expression is never branched on the variant.\n\
+             \n\
+             Each value is delegated through `{delegate_macro_name}!`, the macro generated by \
+             [`delegated_enum`](spire_enum_macros::delegated_enum), which must be in scope, and accepts the same \
+             closure syntax: the argument is a mutable reference to the delegated value.\n\
+             \n\
+             `vecs` must be a place expression of type [`{vecs_ident}`] (e.g. `particles` or `self.particles`), \
+             it is evaluated only once.\n\
+             If the expression panics, the values of the variant being processed are dropped.\n\
+             \n\
+             ## Example:\n\
+             ```rust ignore\n\
+             {macro_ident}!(vecs => |value| value.tick(delta_time));\n\
+             ```\n"
+        ));
+
+        quote! {
+            #docs
+            #[allow(unused)]
+            macro_rules! #macro_ident {
+                ( $vecs: expr => |$arg: ident| $($body: tt)* ) => {{
+                    let __vecs = &mut $vecs;
+
+                    #(
+                        #var_cfgs
+                        {
+                            #for_each_stmts
+                        }
+                    )*
+                }};
+            }
+
+            #[allow(unused_imports)]
+            pub(crate) use #macro_ident;
+        }
+    };
+
+    Ok(quote! {
+        #enum_def
+
+        #[allow(unused_imports)]
+        pub(crate) use #mod_ident::{#vecs_ident, #macro_ident};
+        #tags_reexport
+        #capacity_reexport
+
+        #[allow(unused_imports)]
+        mod #mod_ident {
+            use super::*;
+
+            #capacity_def
+            #tags_def
+            #key_trait_def
+            #vecs_def
+            #key_in_impls
+            #vecs_impls
+            #collect_impls
+            #macro_def
+        }
+    })
+}
@@ -0,0 +1,262 @@
+use syn::{MetaNameValue, parse::Parser, punctuated::Punctuated};
+
+use super::*;
+
+mod kw {
+    use super::*;
+    custom_keyword!(docs);
+}
+
+use kw::docs as kw_docs;
+
+#[derive(Parse, ToTokens)]
+enum Setting {
+    Struct(SettingStruct),
+    Docs(SettingDocs),
+}
+
+#[derive(Parse, ToTokens)]
+struct SettingStruct {
+    kw: Token![struct],
+    eq_token: Token![=],
+    path: Path,
+}
+
+#[derive(Parse, ToTokens)]
+struct SettingDocs {
+    kw: kw_docs,
+    eq_token: Token![=],
+    field: Ident,
+}
+
+struct SaneVariant {
+    cfg_attrs: Any<Attribute<CfgMeta>>,
+    ident: Ident,
+    /// The fields assigned by the variant's `#[meta(..)]` attributes, in the order they were written.
+    fields: Vec<(Ident, Expr)>,
+    /// The text of the variant's doc comments, if any.
+    docs: Option<String>,
+}
+
+pub fn run(settings_stream: TokenStream1, enum_stream: TokenStream1) -> Result<TokenStream> {
+    let settings = syn::parse::<InputPunctuated<Setting, Token![,]>>(settings_stream)?;
+    let mut input_enum = syn::parse::<Enum<SynMeta, SynMeta>>(enum_stream)?;
+
+    let mut meta_struct = Optional::<SettingStruct>::_None;
+    let mut docs_field = Optional::<SettingDocs>::_None;
+
+    for setting in settings.inner {
+        match setting {
+            Setting::Struct(setting) => assign_unique_or_panic!(meta_struct, setting),
+            Setting::Docs(setting) => assign_unique_or_panic!(docs_field, setting),
+        }
+    }
+
+    let _Some(SettingStruct {
+        path: meta_path, ..
+    }) = meta_struct
+    else {
+        return Err(Error::new(
+            Span::call_site(),
+            "expected the setting `struct = MetaType`, the type that holds each variant's metadata",
+        ));
+    };
+
+    let docs_field = docs_field.map(|SettingDocs { field, .. }| field);
+    let variants = take_variant_metas(&mut input_enum)?;
+
+    // All variants must assign the same fields, so each one is checked against the fields assigned by any variant.
+    let mut field_idents = Vec::<&Ident>::new();
+    if let Some(docs_field) = &docs_field {
+        field_idents.push(docs_field);
+    }
+
+    for (field, _) in variants.iter().flat_map(|var| &var.fields) {
+        if !field_idents.contains(&field) {
+            field_idents.push(field);
+        }
+    }
+
+    let mut errors = None::<Error>;
+    let mut push_error = |err: Error| {
+        match &mut errors {
+            Some(errors) => errors.combine(err),
+            None => errors = Some(err),
+        }
+    };
+
+    let meta_values = variants
+        .iter()
+        .map(|var| {
+            let fields = field_idents
+                .iter()
+                .filter_map(|&field_ident| {
+                    if let Some((_, expr)) = var.fields.iter().find(|(ident, _)| ident == field_ident) {
+                        return Some(quote! { #field_ident: #expr });
+                    }
+
+                    if docs_field.as_ref().is_some_and(|docs_field| docs_field == field_ident) {
+                        if let Some(docs) = &var.docs {
+                            return Some(quote! { #field_ident: #docs });
+                        }
+
+                        push_error(Error::new(
+                            var.ident.span(),
+                            format!(
+                                "variant `{}` has no doc comment to fill the field `{field_ident}`.\n\
+                                 Help: Add a doc comment to the variant, or assign the field with `#[meta({field_ident} = ..)]`",
+                                var.ident
+                            ),
+                        ));
+                    } else {
+                        push_error(Error::new(
+                            var.ident.span(),
+                            format!(
+                                "variant `{}` does not assign the field `{field_ident}`, which is assigned by other variants.\n\
+                                 Help: Assign the field with `#[meta({field_ident} = ..)]`",
+                                var.ident
+                            ),
+                        ));
+                    }
+
+                    None
+                })
+                .collect::<Vec<_>>();
+
+            quote! {
+                #meta_path {
+                    #( #fields ),*
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    let generics = sanitize_generics(input_enum.generics.clone(), input_enum.where_clause.clone())?;
+    let enum_ty = new_ty_maybe_generic(&input_enum.ident, &generics);
+    let gen_params = generics.stream_params();
+    let where_clause = generics.as_pair().1;
+
+    let var_cfgs = variants
+        .iter()
+        .map(|var| &var.cfg_attrs)
+        .collect::<Vec<_>>();
+    let var_idents = variants.iter().map(|var| &var.ident);
+
+    // The index of each variant in `METAS` is its position among the variants that survived `#[cfg]` evaluation.
+    let var_indexes = (0..variants.len()).map(|idx| {
+        let previous_cfgs = &var_cfgs[..idx];
+        quote! {{
+            let mut count = 0;
+
+            #(
+                #previous_cfgs
+                {
+                    count += 1;
+                }
+            )*
+
+            count
+        }}
+    });
+
+    let len = quote! {{
+        let mut count = 0;
+
+        #(
+            #var_cfgs
+            {
+                count += 1;
+            }
+        )*
+
+        count
+    }};
+
+    let enum_ident = &input_enum.ident;
+    let meta_name = meta_path.to_token_stream().to_string().replace(' ', "");
+
+    let docs_metas = docs_tokens(format!(
+        "The metadata of each variant of [`{enum_ident}`], in the order they were declared (Top to bottom).\n\n\
+         Variants disabled by `#[cfg]` attributes are not included.\n\n\
+         This constant was generated by an invocation of the macro [`variant_meta`](spire_enum_macros::variant_meta)."
+    ));
+
+    let docs_meta = docs_tokens(format!(
+        "Returns the metadata of this variant, declared with the attribute `#[meta(..)]`.\n\n\
+         The reference points to the variant's [`{meta_name}`] in [`METAS`](Self::METAS)."
+    ));
+
+    Ok(quote! {
+        #input_enum
+
+        #[allow(unused)]
+        impl #gen_params #enum_ty #where_clause {
+            #docs_metas
+            pub const METAS: [#meta_path; #len] = [
+                #(
+                    #var_cfgs
+                    #meta_values
+                ),*
+            ];
+
+            #docs_meta
+            pub const fn meta(&self) -> &'static #meta_path {
+                match self {
+                    #(
+                        #var_cfgs
+                        Self::#var_idents { .. } => const { &Self::METAS[#var_indexes] },
+                    )*
+                }
+            }
+        }
+    })
+}
+
+/// Removes the helper attributes `#[meta(..)]` from the variants of `input`, returning the fields they assign.
+fn take_variant_metas(input: &mut Enum<SynMeta, SynMeta>) -> Result<Vec<SaneVariant>> {
+    input
+        .variants
+        .iter_mut()
+        .map(|var| {
+            let mut fields = Vec::<(Ident, Expr)>::new();
+
+            for attr in var.attrs.iter() {
+                if !attr.inner.path().is_ident("meta") {
+                    continue;
+                }
+
+                let SynMeta::List(list) = &*attr.inner else {
+                    bail!(attr => "expected `#[meta(field = value, ..)]`");
+                };
+
+                let assignments = Punctuated::<MetaNameValue, Token![,]>::parse_terminated
+                    .parse2(list.tokens.clone())?;
+
+                for MetaNameValue { path, value, .. } in assignments {
+                    let Some(ident) = path.get_ident() else {
+                        bail!(path => "expected the name of a field");
+                    };
+
+                    if let Some((first, _)) = fields.iter().find(|(field, _)| field == ident) {
+                        err_expected_only_one!(first, ident);
+                    }
+
+                    fields.push((ident.clone(), value));
+                }
+            }
+
+            var.attrs.retain(|attr| !attr.inner.path().is_ident("meta"));
+
+            Ok(SaneVariant {
+                cfg_attrs: parse_cfg_attrs(var.attrs.clone()),
+                ident: var.ident.clone(),
+                fields,
+                docs: docs_from_metas(var.attrs.iter().map(|attr| &*attr.inner)),
+            })
+        })
+        .collect()
+}
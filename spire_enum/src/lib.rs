@@ -1,13 +1,57 @@
 #![doc = include_str!("../../README.md")]
 #![no_std]
 
+mod repr;
+mod state_machine;
+mod tables;
 mod traits;
+mod vecs;
+mod wire;
+
+// Used by the code generated with `#[variant_vecs]`, which stores the variants in `Vec`s.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub extern crate alloc;
+
+// Used by the code generated with the settings `arbitrary` and `proptest`,
+// so that users don't need to depend on the crates directly.
+#[cfg(feature = "arbitrary")]
+#[doc(hidden)]
+pub use arbitrary;
+#[cfg(feature = "proptest")]
+#[doc(hidden)]
+pub use proptest;
+
+/// Compatibility shims for the syntax of other crates, to ease migrating to `spire_enum`.
+pub mod compat {
+    #[doc(hidden)]
+    pub use spire_enum_macros::__enum_dispatch_impl;
+    pub use spire_enum_macros::enum_dispatch;
+}
 
 pub mod prelude {
     pub use spire_enum_macros::{
-        delegate_impl, delegated_enum, discriminant_generic_table, variant_generic_table,
+        DiscriminantTable,
+        VariantGenericTable,
+        VariantTypeTable,
+        delegate_impl,
+        delegated_enum,
+        discriminant_generic_table,
+        discriminant_matrix,
+        discriminant_set,
+        foreign_delegated_enum,
+        foreign_discriminant_generic_table,
+        foreign_variant_generic_table,
+        foreign_variant_type_table,
+        ordinal,
+        repr_conversions,
+        union,
+        variant_generic_table,
+        variant_meta,
+        variant_type_set,
         variant_type_table,
+        variant_vecs,
     };
 
-    pub use crate::traits::*;
+    pub use crate::{repr::*, state_machine::*, tables::*, traits::*, vecs::*, wire::*};
 }
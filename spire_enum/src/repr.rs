@@ -0,0 +1,35 @@
+use core::fmt::{Debug, Display, Formatter};
+
+/// Error returned when converting an integer into an enum, if the integer is not the discriminant of any variant.
+///
+/// Contains the rejected value, `R` is the integer type in the enum's `#[repr(..)]` attribute.
+///
+/// Values of this type are (usually) created by the `TryFrom` implementations generated by
+/// [`repr_conversions`](crate::prelude::repr_conversions).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidRepr<R> {
+    enum_name: &'static str,
+    value: R,
+}
+
+impl<R> InvalidRepr<R> {
+    /// Constructs a new instance, `enum_name` is the name of the enum `value` was converted into.
+    pub const fn new(enum_name: &'static str, value: R) -> Self { Self { enum_name, value } }
+
+    /// Returns the name of the enum the value was converted into.
+    pub const fn enum_name(&self) -> &'static str { self.enum_name }
+
+    /// Returns a reference to the rejected value.
+    pub const fn value(&self) -> &R { &self.value }
+
+    /// Returns the rejected value.
+    pub fn into_value(self) -> R { self.value }
+}
+
+impl<R: Display> Display for InvalidRepr<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "`{}` is not the discriminant of any variant of `{}`", self.value, self.enum_name)
+    }
+}
+
+impl<R: Debug + Display> core::error::Error for InvalidRepr<R> {}
@@ -0,0 +1,289 @@
+use core::fmt::{Debug, Display, Formatter};
+
+/// Implemented by the code generated with the setting `state_machine(context = Type)` of
+/// [`delegated_enum`](crate::prelude::delegated_enum).
+///
+/// Describes the transition graph of the enum, which is enforced by [`Machine`].
+pub trait StateMachine: Sized {
+    /// The type passed to the hooks of [`StateHooks`], such as the entity that owns the state.
+    type Context: ?Sized;
+
+    /// A fieldless enum with one variant per state, generated by the setting (named `[EnumName]StateKind`).
+    type Kind: Copy + Eq + Debug;
+
+    /// Returns the kind of the current state.
+    fn kind(&self) -> Self::Kind;
+
+    /// Returns `true` if the state `from` may transition into the state `to`,
+    /// as declared by the variant attributes `#[transitions(..)]`.
+    fn can_transition(from: Self::Kind, to: Self::Kind) -> bool;
+}
+
+/// Hooks invoked by [`Machine`] whenever its state changes.
+///
+/// Usually implemented for each variant's type, then for the enum with [`delegate_impl`](crate::prelude::delegate_impl).
+pub trait StateHooks<Context: ?Sized> {
+    /// Invoked after the machine transitions into this state.
+    fn enter(&mut self, _context: &mut Context) {}
+
+    /// Invoked before the machine transitions out of this state.
+    fn exit(&mut self, _context: &mut Context) {}
+}
+
+/// Invoked by [`Machine`] after each transition, see [`Machine::with_hook`].
+///
+/// Implemented for closures that take the kinds of the previous and the new state.
+pub trait TransitionHook<Kind> {
+    fn on_transition(&mut self, from: Kind, to: Kind);
+}
+
+impl<Kind, F: FnMut(Kind, Kind)> TransitionHook<Kind> for F {
+    fn on_transition(&mut self, from: Kind, to: Kind) { self(from, to) }
+}
+
+/// The default [`TransitionHook`] of [`Machine`], which does nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct NoHook;
+
+impl<Kind> TransitionHook<Kind> for NoHook {
+    fn on_transition(&mut self, _from: Kind, _to: Kind) {}
+}
+
+/// Error returned by [`Machine::transition`] and [`Machine::transition_with`] when the transition is not declared
+/// by the current state's `#[transitions(..)]` attribute.
+///
+/// The machine's state is left unchanged, and no hooks are called.
+pub struct InvalidTransition<S: StateMachine> {
+    from: S::Kind,
+    to: S::Kind,
+    rejected: Option<S>,
+}
+
+impl<S: StateMachine> InvalidTransition<S> {
+    /// Returns the kind of the machine's current state.
+    pub fn from(&self) -> S::Kind { self.from }
+
+    /// Returns the kind of the rejected state.
+    pub fn to(&self) -> S::Kind { self.to }
+
+    /// Returns the rejected state, if it was constructed.
+    ///
+    /// Always `None` when returned by [`Machine::transition_with`], since its closure is only called after the
+    /// transition is validated.
+    pub fn into_rejected(self) -> Option<S> { self.rejected }
+}
+
+impl<S: StateMachine> Debug for InvalidTransition<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InvalidTransition")
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: StateMachine> Display for InvalidTransition<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "the state `{:?}` cannot transition into `{:?}`", self.from, self.to)
+    }
+}
+
+impl<S: StateMachine> core::error::Error for InvalidTransition<S> {}
+
+/// Drives the state machine `S`: each transition checks the transition graph, calls [`StateHooks::exit`] on the
+/// previous state, [`StateHooks::enter`] on the new one, then notifies the [`TransitionHook`] `H`.
+///
+/// The context is passed to each call that may invoke the hooks, so that the machine can be stored alongside
+/// (or inside) it.
+pub struct Machine<S, H = NoHook> {
+    /// Only `None` if the closure of [`Machine::transition_with`] panicked (or broke its contract).
+    state: Option<S>,
+    hook:  H,
+}
+
+impl<S> Machine<S>
+where S: StateMachine + StateHooks<S::Context>
+{
+    /// Constructs a new machine, calling [`StateHooks::enter`] on the initial state.
+    pub fn new(mut initial: S, context: &mut S::Context) -> Self {
+        initial.enter(context);
+        Self::from_state(initial)
+    }
+
+    /// Constructs a new machine without calling any hooks, such as when restoring a machine that was saved.
+    pub fn from_state(state: S) -> Self {
+        Self {
+            state: Some(state),
+            hook:  NoHook,
+        }
+    }
+}
+
+impl<S, H> Machine<S, H>
+where
+    S: StateMachine + StateHooks<S::Context>,
+    H: TransitionHook<S::Kind>,
+{
+    /// Replaces the [`TransitionHook`] of the machine, which is notified after each transition.
+    ///
+    /// ```rust ignore
+    /// let mut history = Vec::new();
+    /// let mut machine = Machine::new(State::Idle, &mut body).with_hook(|from, to| history.push((from, to)));
+    /// ```
+    pub fn with_hook<Hook: TransitionHook<S::Kind>>(self, hook: Hook) -> Machine<S, Hook> {
+        Machine {
+            state: self.state,
+            hook,
+        }
+    }
+
+    /// Returns a reference to the current state.
+    ///
+    /// # Panics
+    /// If the machine is [poisoned](Self::is_poisoned).
+    pub fn state(&self) -> &S { self.state.as_ref().unwrap_or_else(|| poisoned()) }
+
+    /// Returns a mutable reference to the current state.
+    ///
+    /// Mutating the state does not invoke any hooks, even if its kind changes.
+    ///
+    /// # Panics
+    /// If the machine is [poisoned](Self::is_poisoned).
+    pub fn state_mut(&mut self) -> &mut S { self.state.as_mut().unwrap_or_else(|| poisoned()) }
+
+    /// Returns the kind of the current state.
+    ///
+    /// # Panics
+    /// If the machine is [poisoned](Self::is_poisoned).
+    pub fn kind(&self) -> S::Kind { self.state().kind() }
+
+    /// Returns `true` if the current state may transition into a state of kind `to`.
+    ///
+    /// # Panics
+    /// If the machine is [poisoned](Self::is_poisoned).
+    pub fn can_transition_to(&self, to: S::Kind) -> bool { S::can_transition(self.kind(), to) }
+
+    /// Returns `true` if the closure of [`transition_with`](Self::transition_with) panicked (or returned a state
+    /// of the wrong kind), in which case the machine no longer has a state, and all methods that access the state
+    /// panic.
+    pub fn is_poisoned(&self) -> bool { self.state.is_none() }
+
+    /// Returns a reference to the [`TransitionHook`].
+    pub fn hook(&self) -> &H { &self.hook }
+
+    /// Returns a mutable reference to the [`TransitionHook`].
+    pub fn hook_mut(&mut self) -> &mut H { &mut self.hook }
+
+    /// Consumes the machine, returning the current state without calling any hooks.
+    ///
+    /// # Panics
+    /// If the machine is [poisoned](Self::is_poisoned).
+    pub fn into_state(self) -> S { self.state.unwrap_or_else(|| poisoned()) }
+
+    /// Transitions into `next`, calling [`exit`](StateHooks::exit) on the current state and
+    /// [`enter`](StateHooks::enter) on `next`, then notifying the [`TransitionHook`].
+    ///
+    /// Returns the previous state.
+    ///
+    /// # Errors
+    /// If the current state cannot transition into `next`, in which case no hooks are called.
+    ///
+    /// # Panics
+    /// If the machine is [poisoned](Self::is_poisoned).
+    pub fn transition(
+        &mut self,
+        context: &mut S::Context,
+        next: S,
+    ) -> Result<S, InvalidTransition<S>> {
+        let from = self.kind();
+        let to = next.kind();
+
+        if !S::can_transition(from, to) {
+            return Err(InvalidTransition {
+                from,
+                to,
+                rejected: Some(next),
+            });
+        }
+
+        let state = self.state_mut();
+        state.exit(context);
+        let previous = core::mem::replace(state, next);
+        state.enter(context);
+        self.hook.on_transition(from, to);
+
+        Ok(previous)
+    }
+
+    /// Transitions into the state returned by `f`, which takes ownership of the current state
+    /// (e.g. to move its fields into the next state).
+    ///
+    /// Since the next state doesn't exist yet, its kind `to` is provided upfront, and validated before any hooks are
+    /// called. Then [`exit`](StateHooks::exit) is called on the current state, followed by `f`,
+    /// [`enter`](StateHooks::enter) on the next state, and finally the [`TransitionHook`] is notified.
+    ///
+    /// ```rust ignore
+    /// machine.transition_with(&mut body, TurretStateKind::ChargingShot, |old| match old {
+    ///     Turret::Aiming(Aiming { target_id }) => Turret::ChargingShot(ChargingShot::new(target_id)),
+    ///     _ => unreachable!("only `Aiming` may transition into `ChargingShot`"),
+    /// })?;
+    /// ```
+    ///
+    /// # Errors
+    /// If the current state cannot transition into `to`, in which case neither the hooks nor `f` are called.
+    ///
+    /// # Panics
+    /// - If the machine is [poisoned](Self::is_poisoned).
+    /// - If `f` panics, the panic is propagated. The machine is poisoned, but remains safe to drop.
+    /// - If `f` returns a state whose kind is not `to`, since the current state was consumed.
+    ///   The machine is poisoned.
+    pub fn transition_with(
+        &mut self,
+        context: &mut S::Context,
+        to: S::Kind,
+        f: impl FnOnce(S) -> S,
+    ) -> Result<(), InvalidTransition<S>> {
+        let from = self.kind();
+
+        if !S::can_transition(from, to) {
+            return Err(InvalidTransition {
+                from,
+                to,
+                rejected: None,
+            });
+        }
+
+        self.state_mut().exit(context);
+
+        // Left as `None` if `f` panics.
+        let current = self.state.take().unwrap_or_else(|| poisoned());
+        let mut next = f(current);
+
+        assert!(
+            next.kind() == to,
+            "the closure of `Machine::transition_with` returned a state of kind `{:?}`, expected `{to:?}`",
+            next.kind()
+        );
+
+        next.enter(context);
+        self.state = Some(next);
+        self.hook.on_transition(from, to);
+
+        Ok(())
+    }
+}
+
+impl<S: Debug, H> Debug for Machine<S, H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match &self.state {
+            Some(state) => f.debug_tuple("Machine").field(state).finish(),
+            None => f.write_str("Machine(<poisoned>)"),
+        }
+    }
+}
+
+#[cold]
+#[track_caller]
+fn poisoned() -> ! {
+    panic!("the state machine is poisoned, a previous call to `Machine::transition_with` panicked")
+}
@@ -0,0 +1,116 @@
+use core::fmt::{Display, Formatter};
+
+/// Error returned when a table is built from a source that did not provide a value for every variant.
+///
+/// Lists the names of the variants that were absent, in the order they were declared in the enum.
+///
+/// This type does not allocate heap memory, `N` is the amount of variants in the table.
+///
+/// Values of this type are (usually) created by the table types generated by the macros of [`spire_enum`](crate).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MissingVariants<const N: usize> {
+    names: [&'static str; N],
+    len:   usize,
+}
+
+impl<const N: usize> MissingVariants<N> {
+    /// Constructs a new instance, with no missing variants.
+    pub const fn new() -> Self {
+        Self {
+            names: [""; N],
+            len:   0,
+        }
+    }
+
+    /// Registers the variant named `name` as missing.
+    ///
+    /// # Panics
+    /// If more than `N` variants are registered.
+    pub const fn push(&mut self, name: &'static str) {
+        assert!(self.len < N, "MissingVariants is full");
+        self.names[self.len] = name;
+        self.len += 1;
+    }
+
+    /// Returns the names of the missing variants.
+    pub fn names(&self) -> &[&'static str] { &self.names[..self.len] }
+
+    /// Returns the amount of missing variants.
+    pub const fn len(&self) -> usize { self.len }
+
+    /// Returns `true` if no variants are missing.
+    pub const fn is_empty(&self) -> bool { self.len == 0 }
+}
+
+impl<const N: usize> Default for MissingVariants<N> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<const N: usize> Display for MissingVariants<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("missing values for variants: ")?;
+
+        for (idx, name) in self.names().iter().enumerate() {
+            if idx > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "`{name}`")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<const N: usize> core::error::Error for MissingVariants<N> {}
+
+/// Error returned when inserting a value for a variant that already has one, such as in the builders generated by
+/// [`variant_type_table`](crate::prelude::variant_type_table).
+///
+/// Contains the rejected value, which can be recovered with [`into_value`](Self::into_value).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Duplicate<T> {
+    name:  &'static str,
+    value: T,
+}
+
+impl<T> Duplicate<T> {
+    /// Constructs a new instance, `name` is the name of the variant `value` belongs to.
+    pub const fn new(name: &'static str, value: T) -> Self { Self { name, value } }
+
+    /// Returns the name of the variant that already had a value.
+    pub const fn name(&self) -> &'static str { self.name }
+
+    /// Returns a reference to the rejected value.
+    pub const fn value(&self) -> &T { &self.value }
+
+    /// Returns the rejected value.
+    pub fn into_value(self) -> T { self.value }
+}
+
+impl<T> Display for Duplicate<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "variant `{}` already has a value", self.name)
+    }
+}
+
+impl<T: core::fmt::Debug> core::error::Error for Duplicate<T> {}
+
+/// Fieldless types whose values can be mapped to a position (ordinal) in `0..LEN`.
+///
+/// Used to index tables that are keyed by more than one enum, such as the ones generated by
+/// [`discriminant_matrix`](crate::prelude::discriminant_matrix).
+///
+/// This trait is implemented for the key type of each table generated by
+/// [`discriminant_generic_table`](crate::prelude::discriminant_generic_table):
+/// the enum itself, or the generated `[EnumName]Kind` if the enum has data-carrying variants.
+pub trait DiscriminantKey: Sized {
+    /// The amount of values this type has.
+    const LEN: usize;
+
+    /// Returns the position of `self` in the order the values were declared (Top to bottom), in the range `0..LEN`.
+    fn ordinal(&self) -> usize;
+
+    /// Inverse of [`ordinal`](Self::ordinal), returns `None` if `ordinal` is not in the range `0..LEN`.
+    fn from_ordinal(ordinal: usize) -> Option<Self>;
+}
@@ -0,0 +1,168 @@
+use core::{
+    fmt::{Debug, Formatter},
+    ops::{Deref, DerefMut},
+};
+
+/// A vector with a fixed capacity `N`, which does not allocate heap memory.
+///
+/// Used by the collections generated by [`variant_vecs`](crate::prelude::variant_vecs) with the setting
+/// `capacity = N`, but can also be used on its own.
+///
+/// The slots beyond the length hold default values, which keeps this type free of unsafe code,
+/// at the cost of requiring `T: Default` for most operations.
+#[derive(Clone)]
+pub struct FixedVec<T, const N: usize> {
+    items: [T; N],
+    len:   usize,
+}
+
+impl<T: Default, const N: usize> FixedVec<T, N> {
+    /// Constructs a new, empty, vector.
+    pub fn new() -> Self {
+        Self {
+            items: core::array::from_fn(|_| T::default()),
+            len:   0,
+        }
+    }
+
+    /// Removes the last value, returning it if the vector was not empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.len = self.len.checked_sub(1)?;
+        Some(core::mem::take(&mut self.items[self.len]))
+    }
+
+    /// Removes all values, dropping them.
+    pub fn clear(&mut self) { self.truncate(0); }
+
+    /// Shortens the vector to `len` values, dropping the rest.
+    ///
+    /// Does nothing if `len` is greater or equal to the vector's current length.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            self.items[len..self.len].fill_with(T::default);
+            self.len = len;
+        }
+    }
+
+    /// Retains only the values for which `f` returns `true`, preserving their order.
+    pub fn retain(&mut self, mut f: impl FnMut(&T) -> bool) {
+        let mut kept = 0;
+
+        for idx in 0..self.len {
+            if f(&self.items[idx]) {
+                self.items.swap(kept, idx);
+                kept += 1;
+            }
+        }
+
+        self.truncate(kept);
+    }
+
+    /// Removes all values, returning them in an iterator.
+    ///
+    /// The values that are not consumed by the iterator are dropped when the iterator is dropped.
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        let len = core::mem::take(&mut self.len);
+        Drain {
+            iter: self.items[..len].iter_mut(),
+        }
+    }
+}
+
+impl<T, const N: usize> FixedVec<T, N> {
+    /// Appends `value` to the end of the vector.
+    ///
+    /// # Errors
+    /// If the vector is full, returning `value`.
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        match self.items.get_mut(self.len) {
+            Some(slot) => {
+                *slot = value;
+                self.len += 1;
+                Ok(())
+            }
+            None => Err(value),
+        }
+    }
+
+    /// Returns the amount of values in the vector.
+    pub const fn len(&self) -> usize { self.len }
+
+    /// Returns `true` if the vector contains no values.
+    pub const fn is_empty(&self) -> bool { self.len == 0 }
+
+    /// Returns `true` if the vector contains `N` values.
+    pub const fn is_full(&self) -> bool { self.len == N }
+
+    /// Returns the maximum amount of values the vector can contain, `N`.
+    pub const fn capacity(&self) -> usize { N }
+
+    /// Returns a slice containing the values of the vector.
+    pub fn as_slice(&self) -> &[T] { &self.items[..self.len] }
+
+    /// Returns a mutable slice containing the values of the vector.
+    pub fn as_mut_slice(&mut self) -> &mut [T] { &mut self.items[..self.len] }
+}
+
+impl<T: Default, const N: usize> Default for FixedVec<T, N> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<T, const N: usize> Deref for FixedVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] { self.as_slice() }
+}
+
+impl<T, const N: usize> DerefMut for FixedVec<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] { self.as_mut_slice() }
+}
+
+impl<T: Debug, const N: usize> Debug for FixedVec<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for FixedVec<T, N> {
+    fn eq(&self, other: &Self) -> bool { self.as_slice() == other.as_slice() }
+}
+
+impl<T: Eq, const N: usize> Eq for FixedVec<T, N> {}
+
+impl<'a, T, const N: usize> IntoIterator for &'a FixedVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter { self.as_slice().iter() }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut FixedVec<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter { self.as_mut_slice().iter_mut() }
+}
+
+/// Iterator returned by [`FixedVec::drain`].
+pub struct Drain<'a, T: Default> {
+    iter: core::slice::IterMut<'a, T>,
+}
+
+impl<T: Default> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> { self.iter.next().map(core::mem::take) }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.iter.size_hint() }
+}
+
+impl<T: Default> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T: Default> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        self.iter
+            .by_ref()
+            .for_each(|item| drop(core::mem::take(item)));
+    }
+}
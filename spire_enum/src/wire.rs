@@ -0,0 +1,250 @@
+use core::fmt::{Display, Formatter};
+
+/// Error returned when encoding or decoding values with [`WireEncode`] and [`WireDecode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WireError {
+    /// The writer has no space left for the encoded bytes.
+    BufferFull,
+    /// The reader ran out of bytes before the value was fully decoded.
+    UnexpectedEnd,
+    /// The decoded tag does not belong to any variant of the enum named `type_name`.
+    InvalidTag { type_name: &'static str, tag: u32 },
+    /// The decoded bytes are not a valid value of the type named `type_name`,
+    /// such as a `bool` that is neither `0` nor `1`.
+    InvalidValue { type_name: &'static str },
+}
+
+impl Display for WireError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            WireError::BufferFull => f.write_str("the writer has no space left"),
+            WireError::UnexpectedEnd => {
+                f.write_str("the reader ended before the value was fully decoded")
+            }
+            WireError::InvalidTag { type_name, tag } => {
+                write!(f, "`{tag}` is not the tag of any variant of `{type_name}`")
+            }
+            WireError::InvalidValue { type_name } => {
+                write!(f, "invalid bytes for a value of `{type_name}`")
+            }
+        }
+    }
+}
+
+impl core::error::Error for WireError {}
+
+/// The output of [`WireEncode`].
+///
+/// Implemented for `&mut [u8]`, which advances past the written bytes (like `std::io::Write`).
+pub trait WireWriter {
+    /// Appends `bytes` to the output.
+    ///
+    /// # Errors
+    /// [`WireError::BufferFull`] if there is not enough space for all of `bytes`, in which case nothing is written
+    /// by this call.
+    ///
+    /// Values are encoded with several calls (e.g. one per field), so a failed [`WireEncode::encode`] may still
+    /// have written the bytes of the calls that preceded the failing one.
+    fn write(&mut self, bytes: &[u8]) -> Result<(), WireError>;
+}
+
+/// The input of [`WireDecode`].
+///
+/// Implemented for `&[u8]`, which advances past the read bytes (like `std::io::Read`).
+pub trait WireReader {
+    /// Fills `buf` with the next bytes of the input.
+    ///
+    /// # Errors
+    /// [`WireError::UnexpectedEnd`] if the input does not have enough bytes to fill `buf`.
+    fn read(&mut self, buf: &mut [u8]) -> Result<(), WireError>;
+}
+
+impl WireWriter for &mut [u8] {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), WireError> {
+        if bytes.len() > self.len() {
+            return Err(WireError::BufferFull);
+        }
+
+        let (head, tail) = core::mem::take(self).split_at_mut(bytes.len());
+        head.copy_from_slice(bytes);
+        *self = tail;
+        Ok(())
+    }
+}
+
+impl WireReader for &[u8] {
+    fn read(&mut self, buf: &mut [u8]) -> Result<(), WireError> {
+        if buf.len() > self.len() {
+            return Err(WireError::UnexpectedEnd);
+        }
+
+        let (head, tail) = self.split_at(buf.len());
+        buf.copy_from_slice(head);
+        *self = tail;
+        Ok(())
+    }
+}
+
+/// Types that can be encoded in a compact binary format, without heap allocations.
+///
+/// The format has no padding or type information, values must be decoded as the same type they were encoded as:
+/// - Integers and floats: fixed size, little-endian. `usize` and `isize` are encoded as `u64` and `i64`.
+/// - `bool`: one byte, `0` or `1`.
+/// - `char`: as `u32`.
+/// - Arrays and tuples: each element, in order.
+/// - Enums with the setting `wire` of [`delegated_enum`](crate::prelude::delegated_enum):
+///   the variant's tag, followed by each of the variant's fields, in order.
+pub trait WireEncode {
+    /// Writes the encoding of `self` to `writer`.
+    ///
+    /// # Errors
+    /// Forwards the errors of [`WireWriter::write`]. The encoding is not atomic: on error, the writer may already
+    /// contain the start of the encoding, which should be discarded.
+    fn encode<W: WireWriter + ?Sized>(&self, writer: &mut W) -> Result<(), WireError>;
+}
+
+/// Types that can be decoded from the format written by [`WireEncode`].
+pub trait WireDecode: Sized {
+    /// Reads a value from `reader`.
+    fn decode<R: WireReader + ?Sized>(reader: &mut R) -> Result<Self, WireError>;
+}
+
+macro_rules! impl_wire_le_bytes {
+    ($($Ty: ty),* $(,)?) => {
+        $(
+            impl WireEncode for $Ty {
+                fn encode<W: WireWriter + ?Sized>(&self, writer: &mut W) -> Result<(), WireError> {
+                    writer.write(&self.to_le_bytes())
+                }
+            }
+
+            impl WireDecode for $Ty {
+                fn decode<R: WireReader + ?Sized>(reader: &mut R) -> Result<Self, WireError> {
+                    let mut bytes = [0; core::mem::size_of::<$Ty>()];
+                    reader.read(&mut bytes)?;
+                    Ok(<$Ty>::from_le_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_wire_le_bytes!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl WireEncode for char {
+    fn encode<W: WireWriter + ?Sized>(&self, writer: &mut W) -> Result<(), WireError> {
+        u32::from(*self).encode(writer)
+    }
+}
+
+impl WireDecode for char {
+    fn decode<R: WireReader + ?Sized>(reader: &mut R) -> Result<Self, WireError> {
+        char::from_u32(u32::decode(reader)?).ok_or(WireError::InvalidValue { type_name: "char" })
+    }
+}
+
+impl WireEncode for usize {
+    fn encode<W: WireWriter + ?Sized>(&self, writer: &mut W) -> Result<(), WireError> {
+        (*self as u64).encode(writer)
+    }
+}
+
+impl WireDecode for usize {
+    fn decode<R: WireReader + ?Sized>(reader: &mut R) -> Result<Self, WireError> {
+        usize::try_from(u64::decode(reader)?)
+            .map_err(|_| WireError::InvalidValue { type_name: "usize" })
+    }
+}
+
+impl WireEncode for isize {
+    fn encode<W: WireWriter + ?Sized>(&self, writer: &mut W) -> Result<(), WireError> {
+        (*self as i64).encode(writer)
+    }
+}
+
+impl WireDecode for isize {
+    fn decode<R: WireReader + ?Sized>(reader: &mut R) -> Result<Self, WireError> {
+        isize::try_from(i64::decode(reader)?)
+            .map_err(|_| WireError::InvalidValue { type_name: "isize" })
+    }
+}
+
+impl WireEncode for bool {
+    fn encode<W: WireWriter + ?Sized>(&self, writer: &mut W) -> Result<(), WireError> {
+        u8::from(*self).encode(writer)
+    }
+}
+
+impl WireDecode for bool {
+    fn decode<R: WireReader + ?Sized>(reader: &mut R) -> Result<Self, WireError> {
+        match u8::decode(reader)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(WireError::InvalidValue { type_name: "bool" }),
+        }
+    }
+}
+
+impl<T: WireEncode, const N: usize> WireEncode for [T; N] {
+    fn encode<W: WireWriter + ?Sized>(&self, writer: &mut W) -> Result<(), WireError> {
+        self.iter().try_for_each(|value| value.encode(writer))
+    }
+}
+
+impl<T: WireDecode, const N: usize> WireDecode for [T; N] {
+    fn decode<R: WireReader + ?Sized>(reader: &mut R) -> Result<Self, WireError> {
+        // Elements after the first error are not read.
+        let mut error = None;
+        let results = core::array::from_fn::<_, N, _>(|_| {
+            match error {
+                Some(err) => Err(err),
+                None => T::decode(reader).inspect_err(|err| error = Some(*err)),
+            }
+        });
+
+        if let Some(err) = error {
+            return Err(err);
+        }
+
+        Ok(results.map(|result| {
+            match result {
+                Ok(value) => value,
+                Err(_) => unreachable!(),
+            }
+        }))
+    }
+}
+
+macro_rules! impl_wire_tuple {
+    ($($T: ident),*) => {
+        impl<$($T: WireEncode),*> WireEncode for ($($T,)*) {
+            #[allow(non_snake_case, unused_variables)]
+            fn encode<W: WireWriter + ?Sized>(&self, writer: &mut W) -> Result<(), WireError> {
+                let ($($T,)*) = self;
+                $( $T.encode(writer)?; )*
+                Ok(())
+            }
+        }
+
+        impl<$($T: WireDecode),*> WireDecode for ($($T,)*) {
+            #[allow(unused_variables)]
+            fn decode<R: WireReader + ?Sized>(reader: &mut R) -> Result<Self, WireError> {
+                Ok(($($T::decode(reader)?,)*))
+            }
+        }
+    };
+}
+
+impl_wire_tuple!();
+impl_wire_tuple!(A);
+impl_wire_tuple!(A, B);
+impl_wire_tuple!(A, B, C);
+impl_wire_tuple!(A, B, C, D);
+impl_wire_tuple!(A, B, C, D, E);
+impl_wire_tuple!(A, B, C, D, E, F);
+impl_wire_tuple!(A, B, C, D, E, F, G);
+impl_wire_tuple!(A, B, C, D, E, F, G, H);
+impl_wire_tuple!(A, B, C, D, E, F, G, H, I);
+impl_wire_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_wire_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_wire_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
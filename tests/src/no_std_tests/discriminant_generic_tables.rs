@@ -22,6 +22,73 @@ pub enum ExplicitDiscriminants {
     Third = 13,
 }
 
+#[discriminant_generic_table(
+    ty_name = Resistances,
+    mod_name = resistances,
+    derive(Clone, Copy, Debug, PartialEq),
+    impl_ops,
+)]
+#[derive(Clone, Copy)]
+pub enum DamageType {
+    Physical,
+    Fire,
+    Frost,
+}
+
+#[discriminant_generic_table(ty_name = StateTimers, derive(Debug, PartialEq))]
+#[allow(unused)]
+pub enum PlayerState {
+    Idle,
+    Walking(f32),
+    Attacking {
+        target: u32,
+        combo:  u8,
+    },
+    #[cfg(feature = "cond_comp")]
+    Dashing(f32, f32),
+}
+
+#[discriminant_generic_table(
+    ty_name = Counters,
+    storage = array,
+    derive(Clone, Copy, Debug, PartialEq),
+    impl_ops,
+)]
+#[derive(Clone, Copy)]
+pub enum Counter {
+    Frames,
+    #[cfg(feature = "cond_comp")]
+    Draws,
+    Events,
+}
+
+#[discriminant_generic_table(ty_name = InputTimers, storage = array)]
+#[allow(unused)]
+pub enum Input {
+    Move { x: f32, y: f32 },
+    Jump,
+    Fire(u8),
+}
+
+#[discriminant_generic_table(ty_name = Cooldowns, storage = array, value_ty = u32)]
+#[allow(unused)]
+pub enum Ability {
+    #[default_value(30)]
+    Dash,
+    Block,
+    #[cfg(feature = "cond_comp")]
+    #[default_value(5 * 60)]
+    Ultimate,
+    #[default_value(Ability::FIREBALL_COOLDOWN)]
+    Fireball {
+        power: u8,
+    },
+}
+
+impl Ability {
+    const FIREBALL_COOLDOWN: u32 = 90;
+}
+
 #[test]
 fn test() {
     let mut table = CustomTable::new(5, 10, 3);
@@ -43,3 +110,240 @@ fn test() {
     table.set(ExplicitDiscriminants::First, 9);
     assert_eq!(table.get(ExplicitDiscriminants::First), &9);
 }
+
+#[test]
+fn test_combinators() {
+    let table = CustomTable::new(5, 10, 3);
+
+    assert!(table.keys().map(|key| key as i32).eq([5, 10, 13]));
+    assert!(table.values().copied().eq([5, 10, 3]));
+
+    let offsets = table.clone().map(|key, value| key as i32 + value);
+    assert!(offsets.into_values().eq([10, 20, 16]));
+
+    let zipped = table.clone().zip(CustomTable::new('a', 'b', 'c'));
+    assert_eq!(zipped[ExplicitDiscriminants::Second], (10, 'b'));
+
+    let checked = table.clone().try_map(|_, value| u8::try_from(value - 4));
+    assert!(checked.is_err());
+
+    let mut table = table;
+    *table.as_mut()[ExplicitDiscriminants::Third] = 30;
+    assert_eq!(*table.as_ref()[ExplicitDiscriminants::Third], 30);
+
+    for value in table.values_mut() {
+        *value *= 2;
+    }
+    assert!(table.into_values().eq([10, 20, 60]));
+}
+
+#[test]
+fn test_from_iter() {
+    let table: TestDiscriminantsDiscriminantTable<&str> = [
+        (TestDiscriminants::Third, "3"),
+        (TestDiscriminants::First, "1"),
+        (TestDiscriminants::Second, "2"),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(table[TestDiscriminants::First], "1");
+    assert_eq!(table[TestDiscriminants::Third], "3");
+
+    let missing =
+        TestDiscriminantsDiscriminantTable::try_from_iter([(TestDiscriminants::Second, 0)])
+            .err()
+            .unwrap();
+    assert_eq!(missing.names(), ["First", "Third"]);
+    assert_eq!(missing.len(), 2);
+}
+
+#[test]
+fn test_ops() {
+    let armor = Resistances::new(0.5, 0.25, 0.0);
+    let ring = Resistances::new(0.0, 0.25, 0.5);
+
+    let total = armor + ring;
+    assert_eq!(total, Resistances::new(0.5, 0.5, 0.5));
+    assert_eq!(total - ring, armor);
+    assert_eq!(total * 2.0, Resistances::new(1.0, 1.0, 1.0));
+    assert_eq!(total / total, Resistances::filled_with(1.0));
+    assert_eq!(-ring, Resistances::new(-0.0, -0.25, -0.5));
+
+    let mut scaled = armor;
+    scaled *= 4.0;
+    scaled += ring;
+    scaled /= Resistances::filled_with(2.0);
+    assert_eq!(scaled[DamageType::Physical], 1.0);
+    assert_eq!(scaled[DamageType::Fire], 0.625);
+    assert_eq!(scaled[DamageType::Frost], 0.25);
+
+    let sum: Resistances<f32> = [armor, ring, armor].into_iter().sum();
+    assert_eq!(sum, Resistances::new(1.0, 0.75, 0.5));
+}
+
+#[test]
+fn test_data_enum() {
+    let mut timers = StateTimers::from_fn(|kind| {
+        match kind {
+            PlayerStateKind::Idle => 0,
+            PlayerStateKind::Walking => 1,
+            PlayerStateKind::Attacking => 2,
+            #[cfg(feature = "cond_comp")]
+            PlayerStateKind::Dashing => 3,
+        }
+    });
+
+    let walking = PlayerState::Walking(2.5);
+    let attacking = PlayerState::Attacking {
+        target: 7,
+        combo:  2,
+    };
+
+    assert_eq!(PlayerStateKind::from(&walking), PlayerStateKind::Walking);
+    assert_eq!(timers[&walking], 1);
+    assert_eq!(timers.get(&attacking), &2);
+    assert_eq!(timers[PlayerStateKind::Idle], 0);
+
+    timers[&PlayerState::Walking(0.0)] += 10;
+    timers.set(&attacking, 20);
+    *timers.get_mut(PlayerStateKind::Idle) = 30;
+    assert_eq!(timers.get(&walking), &11);
+    assert_eq!(
+        timers[&PlayerState::Attacking {
+            target: 0,
+            combo:  0,
+        }],
+        20
+    );
+
+    #[cfg(not(feature = "cond_comp"))]
+    {
+        assert_eq!(StateTimers::new(30, 11, 20), timers);
+        assert!(timers.keys().eq([
+            PlayerStateKind::Idle,
+            PlayerStateKind::Walking,
+            PlayerStateKind::Attacking,
+        ]));
+    }
+
+    #[cfg(feature = "cond_comp")]
+    assert_eq!(timers[&PlayerState::Dashing(1.0, 0.0)], 3);
+}
+
+#[test]
+fn test_array_storage() {
+    const COUNTERS: Counters<u32> = Counters::from_array([
+        1,
+        #[cfg(feature = "cond_comp")]
+        2,
+        3,
+    ]);
+    const EVENTS: u32 = *COUNTERS.get(Counter::Events);
+    assert_eq!(EVENTS, 3);
+
+    let mut counters = COUNTERS;
+    counters[Counter::Frames] += 10;
+    *counters.get_mut(Counter::Events) += 20;
+
+    #[cfg(not(feature = "cond_comp"))]
+    {
+        assert_eq!(counters.as_slice(), &[11, 23]);
+        assert_eq!(counters[1], 23);
+        assert_eq!(counters.into_array(), [11, 23]);
+        assert_eq!(Counters::from_array([1, 2]), Counters::new(1, 2));
+    }
+
+    #[cfg(feature = "cond_comp")]
+    {
+        assert_eq!(counters.as_slice(), &[11, 2, 23]);
+        assert_eq!(counters[2], 23);
+        assert_eq!(counters.into_array(), [11, 2, 23]);
+        assert_eq!(Counters::from_array([1, 2, 3]), Counters::new(1, 2, 3));
+    }
+
+    for value in counters.as_mut_slice() {
+        *value = 0;
+    }
+    assert_eq!(counters, Counters::filled_with(0));
+
+    let mut counters = Counters::from_fn(|counter| counter as u32);
+    for (counter, value) in counters.iter_mut() {
+        *value += counter as u32;
+    }
+    let doubled = counters.map(|_, value| value * 2);
+    assert!(
+        counters
+            .zip(doubled)
+            .values()
+            .all(|(value, double)| value * 2 == *double)
+    );
+    assert!(
+        counters
+            .keys()
+            .map(|key| key as u32 * 2)
+            .eq(counters.into_values())
+    );
+
+    let summed: Counters<u32> = [counters, counters].into_iter().sum();
+    assert_eq!(summed, counters * 2);
+    assert_eq!(-Counters::filled_with(1), Counters::filled_with(-1));
+
+    let array: [u32; Counter::Events as usize + 1] = (counters + 1).into();
+    assert_eq!(Counters::from(array), counters + 1);
+}
+
+#[test]
+fn test_array_storage_data_enum() {
+    let mut timers = InputTimers::filled_with(0.0);
+    timers[&Input::Move { x: 1.0, y: 0.0 }] = 0.5;
+    timers[&Input::Fire(3)] += 2.0;
+
+    assert_eq!(timers.as_slice(), &[0.5, 0.0, 2.0]);
+    assert_eq!(timers[InputKind::Jump], 0.0);
+    assert_eq!(timers[2], 2.0);
+
+    let timers: InputTimers<f32> = timers
+        .into_iter()
+        .map(|(kind, value)| (kind, value + 1.0))
+        .collect();
+    assert_eq!(timers.into_array(), [1.5, 1.0, 3.0]);
+}
+
+#[test]
+fn test_default_values() {
+    let mut cooldowns = Cooldowns::default();
+    assert_eq!(cooldowns[AbilityKind::Dash], 30);
+    assert_eq!(cooldowns[AbilityKind::Block], 0);
+    assert_eq!(cooldowns[&Ability::Fireball { power: 1 }], 90);
+
+    #[cfg(feature = "cond_comp")]
+    assert_eq!(Cooldowns::default_value(AbilityKind::Ultimate), 300);
+
+    cooldowns.values_mut().for_each(|value| *value = 1);
+    cooldowns.reset(AbilityKind::Fireball);
+    assert_eq!(cooldowns[AbilityKind::Fireball], 90);
+    assert_eq!(cooldowns[AbilityKind::Dash], 1);
+
+    cooldowns.reset_all();
+    assert!(cooldowns.values().eq(Cooldowns::defaults().values()));
+}
+
+// `#[table_skip]` is meant for `variant_type_table`, the discriminant table still needs every variant.
+#[discriminant_generic_table(ty_name = PickupTimers)]
+#[variant_type_table(ty_name = PickupTable)]
+pub enum Pickup {
+    Coin(u32),
+    Gem(u8),
+    #[table_skip]
+    Key(char),
+}
+
+#[test]
+fn test_table_skip_of_other_table() {
+    let timers = PickupTimers::new(1, 2, 3);
+    assert_eq!(timers[PickupKind::Key], 3);
+
+    let table = PickupTable::new(10, 20);
+    assert_eq!(table.get::<u8>(), &20);
+}
@@ -0,0 +1,124 @@
+use super::*;
+
+#[discriminant_generic_table]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Element {
+    Physical,
+    Fire,
+    #[cfg(feature = "cond_comp")]
+    Lightning,
+}
+
+#[discriminant_generic_table]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Armor {
+    Plate,
+    Cloth,
+    Scales,
+}
+
+discriminant_matrix!(Element, Armor);
+
+discriminant_matrix!(
+    Armor,
+    Armor,
+    ty_name = ArmorConversions,
+    mod_name = armor_conversions,
+    derive(Clone, Debug, PartialEq),
+);
+
+#[test]
+fn test_discriminant_key() {
+    assert_eq!(<Armor as DiscriminantKey>::LEN, 3);
+    assert_eq!(Armor::Scales.ordinal(), 2);
+    assert_eq!(Armor::from_ordinal(1), Some(Armor::Cloth));
+    assert_eq!(Armor::from_ordinal(3), None);
+
+    #[cfg(not(feature = "cond_comp"))]
+    assert_eq!(<Element as DiscriminantKey>::LEN, 2);
+
+    #[cfg(feature = "cond_comp")]
+    assert_eq!(<Element as DiscriminantKey>::LEN, 3);
+}
+
+#[test]
+fn test_matrix() {
+    fn multiplier(element: Element, armor: Armor) -> f32 {
+        match (element, armor) {
+            (Element::Physical, Armor::Plate) => 0.5,
+            (Element::Fire, Armor::Cloth) => 2.0,
+            (Element::Fire, Armor::Scales) => 0.25,
+            _ => 1.0,
+        }
+    }
+
+    let mut matrix = ElementArmorMatrix::from_fn(multiplier);
+
+    assert_eq!(ElementArmorMatrix::<f32>::COLUMNS, 3);
+    assert_eq!(matrix[(Element::Physical, Armor::Plate)], 0.5);
+    assert_eq!(matrix.get(Element::Fire, Armor::Scales), &0.25);
+    assert_eq!(matrix.row(Element::Fire), &[1.0, 2.0, 0.25]);
+
+    assert!(
+        matrix
+            .iter()
+            .all(|(element, armor, value)| *value == multiplier(element, armor))
+    );
+    assert!(
+        matrix
+            .column(Armor::Cloth)
+            .map(|(_, value)| *value)
+            .take(2)
+            .eq([1.0, 2.0])
+    );
+
+    matrix[(Element::Physical, Armor::Cloth)] = 3.0;
+    matrix.set(Element::Fire, Armor::Plate, 4.0);
+    for (_, value) in matrix.column_mut(Armor::Scales) {
+        *value *= 2.0;
+    }
+    matrix.row_mut(Element::Physical)[0] += 1.0;
+
+    assert_eq!(matrix.row(Element::Physical), &[1.5, 3.0, 2.0]);
+    assert_eq!(matrix.row(Element::Fire), &[4.0, 2.0, 0.5]);
+
+    let transposed = matrix.transpose();
+    assert_eq!(transposed[(Armor::Cloth, Element::Physical)], 3.0);
+    assert_eq!(transposed.row(Armor::Scales)[..2], [2.0, 0.5]);
+
+    let matrix = transposed.transpose();
+    assert_eq!(matrix.row(Element::Fire), &[4.0, 2.0, 0.5]);
+
+    let scaled = matrix.map(|_, _, value| (value * 2.0) as u32);
+    assert!(
+        scaled
+            .iter()
+            .map(|(_, _, value)| *value)
+            .take(6)
+            .eq([3, 6, 4, 8, 4, 1])
+    );
+}
+
+#[test]
+fn test_matrix_settings() {
+    let mut conversions = ArmorConversions::filled_with(false);
+    for (from, to, value) in conversions.iter_mut() {
+        *value = from.ordinal() <= to.ordinal();
+    }
+
+    assert!(conversions[(Armor::Plate, Armor::Scales)]);
+    assert!(!conversions[(Armor::Scales, Armor::Plate)]);
+    assert_eq!((&conversions).into_iter().filter(|value| **value).count(), 6);
+
+    for value in &mut conversions {
+        *value = !*value;
+    }
+
+    let transposed: ArmorConversionsTransposed<bool> = conversions.clone().transpose();
+    assert!(transposed[(Armor::Plate, Armor::Scales)]);
+    assert_eq!(
+        transposed.into_array(),
+        ArmorConversions::from_fn(|from, to| from.ordinal() < to.ordinal()).into_array()
+    );
+    assert_ne!(conversions, ArmorConversions::filled_with(false));
+}
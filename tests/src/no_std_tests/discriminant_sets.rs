@@ -0,0 +1,71 @@
+use super::*;
+
+#[discriminant_set(ty_name = InputSet, mod_name = input_set, derive(Debug))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Input {
+    Jump,
+    Crouch,
+    Sprint,
+    Interact,
+}
+
+#[test]
+fn test() {
+    const MOVEMENT: InputSet = input_set![Crouch, Sprint];
+
+    let mut pressed = InputSet::new();
+    assert!(pressed.is_empty());
+    assert!(pressed.insert(Input::Jump));
+    assert!(!pressed.insert(Input::Jump));
+    assert!(pressed.contains(Input::Jump));
+    assert!(!pressed.contains(Input::Crouch));
+
+    pressed |= MOVEMENT;
+    assert_eq!(pressed.len(), 3);
+    assert_eq!(pressed.bits(), 0b0111);
+
+    assert!(MOVEMENT.is_subset(&pressed));
+    assert!(pressed.is_superset(&MOVEMENT));
+    assert!((pressed - MOVEMENT).contains(Input::Jump));
+    assert_eq!((pressed - MOVEMENT).len(), 1);
+    assert_eq!(pressed & MOVEMENT, MOVEMENT);
+    assert_eq!(!pressed, input_set![Interact]);
+    assert_eq!(pressed ^ InputSet::all(), input_set![Interact]);
+    assert!((!pressed).is_disjoint(&pressed));
+    assert_eq!(InputSet::all().len(), 4);
+    assert_eq!(InputSet::from_bits_truncate(u8::MAX), InputSet::all());
+
+    assert!(pressed.remove(Input::Crouch));
+    assert!(!pressed.remove(Input::Crouch));
+
+    let mut iter = pressed.iter();
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.next(), Some(Input::Jump));
+    assert_eq!(iter.next(), Some(Input::Sprint));
+    assert_eq!(iter.next(), None);
+    assert_eq!(pressed.iter().next_back(), Some(Input::Sprint));
+
+    let collected: InputSet = [Input::Interact, Input::Jump].into_iter().collect();
+    assert_eq!(collected, InputSet::from(Input::Interact).with(Input::Jump));
+    assert_eq!(collected.without(Input::Jump), input_set![Interact]);
+
+    pressed.clear();
+    assert_eq!(pressed, InputSet::default());
+
+    // Ensure debug is implemented
+    let _ = <InputSet as Debug>::fmt;
+}
+
+#[cfg(test)]
+mod other_module {
+    // The constructor macro names the set type and the enum without a path, so both must be in scope.
+    use super::{Input, InputSet, input_set};
+
+    #[test]
+    fn test_macro_from_other_module() {
+        const JUMPS: InputSet = input_set![Jump];
+
+        assert!(JUMPS.contains(Input::Jump));
+        assert_eq!(input_set![Jump, Interact].len(), 2);
+    }
+}
@@ -0,0 +1,105 @@
+#![allow(unused)]
+use spire_enum::compat::enum_dispatch;
+
+use super::*;
+
+mod shapes {
+    use super::*;
+
+    #[enum_dispatch]
+    pub trait Shape {
+        fn area(&self) -> i32;
+        fn scale(&mut self, factor: i32);
+        fn describe(&self) -> &'static str { "shape" }
+    }
+}
+
+use shapes::Shape;
+
+#[enum_dispatch]
+trait Convert<T, const N: usize> {
+    fn convert(&self) -> [T; N];
+}
+
+#[enum_dispatch(Figure)]
+trait Named {
+    fn name(&self) -> &'static str;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Circle {
+    radius: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Square(i32);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tagged<T>(T);
+
+impl Shape for Circle {
+    fn area(&self) -> i32 { 3 * self.radius * self.radius }
+    fn scale(&mut self, factor: i32) { self.radius *= factor; }
+    fn describe(&self) -> &'static str { "circle" }
+}
+
+impl Shape for Square {
+    fn area(&self) -> i32 { self.0 * self.0 }
+    fn scale(&mut self, factor: i32) { self.0 *= factor; }
+}
+
+impl Convert<i32, 2> for Circle {
+    fn convert(&self) -> [i32; 2] { [self.radius; 2] }
+}
+
+impl Convert<i32, 2> for Square {
+    fn convert(&self) -> [i32; 2] { [self.0, -self.0] }
+}
+
+impl Named for Circle {
+    fn name(&self) -> &'static str { "Circle" }
+}
+
+impl Named for Square {
+    fn name(&self) -> &'static str { "Square" }
+}
+
+#[enum_dispatch(shapes::Shape, Convert<i32, 2>)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Figure {
+    Circle,
+    Square(Square),
+}
+
+impl<T> Shape for Tagged<T> {
+    fn area(&self) -> i32 { 0 }
+    fn scale(&mut self, _factor: i32) {}
+}
+
+#[enum_dispatch(Shape)]
+pub enum Labeled<T> {
+    Square,
+    Tagged(Tagged<T>),
+}
+
+#[test]
+fn test_enum_dispatch_compat() {
+    let mut figure = Figure::from(Circle { radius: 2 });
+    assert_eq!(figure.area(), 12);
+    assert_eq!(figure.describe(), "circle");
+    assert_eq!(figure.name(), "Circle");
+    assert_eq!(figure.convert(), [2, 2]);
+
+    figure.scale(2);
+    assert_eq!(Circle::try_from(figure), Ok(Circle { radius: 4 }));
+
+    let square = Figure::Square(Square(3));
+    assert_eq!(square.area(), 9);
+    assert_eq!(square.describe(), "shape");
+    assert_eq!(square.name(), "Square");
+    assert_eq!(square.convert(), [3, -3]);
+
+    let labeled = Labeled::<u8>::Tagged(Tagged(5));
+    assert_eq!(labeled.area(), 0);
+    assert_eq!(Labeled::<u8>::from(Square(2)).area(), 4);
+}
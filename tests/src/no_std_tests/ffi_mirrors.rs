@@ -0,0 +1,178 @@
+#![allow(unused)]
+use super::*;
+
+#[delegated_enum(ffi_mirror(name = StateFfi))]
+#[derive(Debug, PartialEq)]
+pub enum State {
+    #[delegate_via(|| -> f32 { 0.0 })]
+    Idle,
+    Running(f32),
+    #[cfg(feature = "cond_comp")]
+    Paused(u32),
+    Stopped([u8; 2]),
+}
+
+#[test]
+fn test_ffi_mirror_conversions() {
+    let ffi = StateFfi::from(State::Running(2.5));
+    assert_eq!(ffi.tag, StateFfiTag::Running as u32);
+    assert_eq!(ffi.variant_tag(), Some(StateFfiTag::Running));
+    assert_eq!(unsafe { *ffi.payload.running }, 2.5);
+    assert_eq!(unsafe { ffi.into_enum() }, Ok(State::Running(2.5)));
+
+    let ffi = StateFfi::from(State::Idle);
+    assert_eq!(ffi.tag, 0);
+    assert_eq!(unsafe { ffi.into_enum() }, Ok(State::Idle));
+
+    // Tags count the variants disabled by `#[cfg]`.
+    let ffi = StateFfi::from(State::Stopped([1, 2]));
+    assert_eq!(ffi.tag, 3);
+    assert_eq!(unsafe { ffi.into_enum() }, Ok(State::Stopped([1, 2])));
+
+    let invalid = StateFfi {
+        tag: 9,
+        payload: StateFfiPayload { __unit: () },
+    };
+    assert_eq!(invalid.variant_tag(), None);
+    assert_eq!(unsafe { invalid.into_enum() }.map_err(|err| *err.value()), Err(9));
+}
+
+pub trait Area {
+    fn area(&self) -> f32;
+    fn scale(&mut self, factor: f32);
+}
+
+#[delegated_enum(
+    extract_variants(attrs(repr(C)), derive(Debug, Clone, Copy, PartialEq)),
+    ffi_mirror(name = ShapeFfi)
+)]
+#[derive(Debug, PartialEq)]
+pub enum Shape {
+    #[variant_id = 100]
+    Circle { radius: f32 },
+    #[variant_id = 200]
+    Rect { w: f32, h: f32 },
+}
+
+impl Area for Circle {
+    fn area(&self) -> f32 { 3.0 * self.radius * self.radius }
+    fn scale(&mut self, factor: f32) { self.radius *= factor; }
+}
+
+impl Area for Rect {
+    fn area(&self) -> f32 { self.w * self.h }
+    fn scale(&mut self, factor: f32) {
+        self.w *= factor;
+        self.h *= factor;
+    }
+}
+
+impl Circle {
+    fn sides(&self) -> u32 { 0 }
+}
+
+impl Rect {
+    fn sides(&self) -> u32 { 4 }
+}
+
+#[delegate_impl(ffi_mirror = ShapeFfi)]
+impl Area for Shape {
+    fn area(&self) -> f32;
+    fn scale(&mut self, factor: f32);
+}
+
+#[delegate_impl(ffi_mirror = ShapeFfi)]
+impl Shape {
+    fn sides(&self) -> u32;
+}
+
+pub trait Named {
+    fn name(&self) -> u32;
+}
+
+impl Named for Circle {
+    fn name(&self) -> u32 { 1 }
+}
+
+impl Named for Rect {
+    fn name(&self) -> u32 { 2 }
+}
+
+#[delegate_impl(ffi_mirror = ShapeFfi)]
+impl Named for Shape {
+    fn name(&self) -> u32;
+}
+
+// In its own module, so that calling `name` on the variants is not ambiguous with `Named::name`.
+mod labeled {
+    use spire_enum::prelude::delegate_impl;
+
+    use super::{Circle, Rect, Shape, ShapeFfi};
+
+    pub trait Labeled {
+        fn name(&self) -> u32;
+    }
+
+    impl Labeled for Circle {
+        fn name(&self) -> u32 { 10 }
+    }
+
+    impl Labeled for Rect {
+        fn name(&self) -> u32 { 20 }
+    }
+
+    // Shares the method name with `Named`, the shims must not collide.
+    #[delegate_impl(ffi_mirror = ShapeFfi)]
+    impl Labeled for Shape {
+        fn name(&self) -> u32;
+    }
+}
+
+#[test]
+fn test_ffi_mirror_shims() {
+    let mut ffi = ShapeFfi::from(Shape::Rect(Rect { w: 2.0, h: 3.0 }));
+    assert_eq!(ffi.tag, 200);
+    assert_eq!(ffi.variant_tag(), Some(ShapeFfiTag::Rect));
+
+    let mut area = 0.0;
+    let mut sides = 0;
+
+    unsafe {
+        assert_eq!(shape_ffi_area_area(&ffi, &mut area), 0);
+        assert_eq!(area, 6.0);
+        assert_eq!(shape_ffi_sides(&ffi, &mut sides), 0);
+        assert_eq!(sides, 4);
+
+        assert_eq!(shape_ffi_area_scale(&mut ffi, 2.0), 0);
+        assert_eq!(shape_ffi_area_area(&ffi, &mut area), 0);
+        assert_eq!(area, 24.0);
+    }
+
+    assert_eq!(unsafe { ffi.into_enum() }, Ok(Shape::Rect(Rect { w: 4.0, h: 6.0 })));
+
+    let ffi = ShapeFfi::from(Shape::Circle(Circle { radius: 1.0 }));
+    assert_eq!(ffi.tag, 100);
+    assert_eq!(unsafe { shape_ffi_sides(&ffi, &mut sides) }, 0);
+    assert_eq!(sides, 0);
+
+    // Invalid tags are reported with an error code, instead of panicking across the FFI boundary.
+    let mut invalid = ShapeFfi {
+        tag: 7,
+        payload: ShapeFfiPayload { __unit: () },
+    };
+    assert_eq!(unsafe { shape_ffi_sides(&invalid, &mut sides) }, -1);
+    assert_eq!(unsafe { shape_ffi_area_scale(&mut invalid, 2.0) }, -1);
+}
+
+#[test]
+fn test_ffi_mirror_shims_shared_method_name() {
+    let ffi = ShapeFfi::from(Shape::Rect(Rect { w: 1.0, h: 1.0 }));
+    let mut name = 0;
+
+    unsafe {
+        assert_eq!(shape_ffi_named_name(&ffi, &mut name), 0);
+        assert_eq!(name, 2);
+        assert_eq!(labeled::shape_ffi_labeled_name(&ffi, &mut name), 0);
+        assert_eq!(name, 20);
+    }
+}
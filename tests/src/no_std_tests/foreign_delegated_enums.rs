@@ -0,0 +1,111 @@
+#![allow(unused)]
+use super::*;
+
+mod other {
+    #[derive(Debug, PartialEq)]
+    pub struct Circle {
+        pub radius: i32,
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub struct Square(pub i32);
+
+    #[derive(Debug, PartialEq)]
+    pub struct Hexagon(pub i32);
+
+    #[derive(Debug, PartialEq)]
+    pub enum Figure {
+        Circle(Circle),
+        Square(Square),
+        Rect {
+            id: u32,
+            sides: (i32, i32),
+        },
+        #[cfg(feature = "cond_comp")]
+        Hexagon(Hexagon),
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub enum Either<L, R> {
+        Left(L),
+        Right(R),
+    }
+}
+
+trait Area {
+    fn area(&self) -> i32;
+}
+
+impl Area for other::Circle {
+    fn area(&self) -> i32 { 3 * self.radius * self.radius }
+}
+
+impl Area for other::Square {
+    fn area(&self) -> i32 { self.0 * self.0 }
+}
+
+impl Area for (i32, i32) {
+    fn area(&self) -> i32 { self.0 * self.1 }
+}
+
+impl Area for other::Hexagon {
+    fn area(&self) -> i32 { 2 * self.0 * self.0 }
+}
+
+foreign_delegated_enum!(
+    other::Figure {
+        Circle(other::Circle),
+        Square(other::Square),
+        #[dont_impl_conversions]
+        Rect { id: u32, #[delegator] sides: (i32, i32) },
+        #[cfg(feature = "cond_comp")]
+        Hexagon(other::Hexagon),
+    },
+    impl_conversions,
+);
+
+#[delegate_impl]
+impl Area for other::Figure {
+    fn area(&self) -> i32;
+}
+
+foreign_delegated_enum!(
+    other::Either<L, R> {
+        Left(L),
+        Right(R),
+    },
+);
+
+#[delegate_impl]
+impl<L: Area, R: Area> Area for other::Either<L, R> {
+    fn area(&self) -> i32;
+}
+
+#[test]
+fn test_foreign_delegation() {
+    let circle = other::Figure::Circle(other::Circle { radius: 2 });
+    assert_eq!(circle.area(), 12);
+
+    let rect = other::Figure::Rect {
+        id: 0,
+        sides: (2, 5),
+    };
+    assert_eq!(rect.area(), 10);
+
+    let either = other::Either::<other::Square, other::Circle>::Left(other::Square(3));
+    assert_eq!(either.area(), 9);
+    assert_eq!(delegate_either! { either => |shape| shape.area() + 1 }, 10);
+}
+
+#[test]
+fn test_foreign_conversions() {
+    let figure = other::Figure::from(other::Square(4));
+    assert_eq!(figure, other::Figure::Square(other::Square(4)));
+
+    let square = <&other::Square>::try_from(&figure);
+    assert_eq!(square, Ok(&other::Square(4)));
+    assert_eq!(other::Circle::from_enum_ref(&figure), None);
+
+    let figure = other::Figure::Circle(other::Circle { radius: 1 });
+    assert_eq!(other::Circle::try_from(figure), Ok(other::Circle { radius: 1 }));
+}
@@ -0,0 +1,115 @@
+use super::*;
+
+mod other {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum KeyCode {
+        Up,
+        Down,
+        #[cfg(feature = "cond_comp")]
+        Tab,
+        Escape,
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub struct Click {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub struct Scroll(pub f32);
+
+    #[allow(unused)]
+    #[derive(Debug, PartialEq)]
+    pub enum Event {
+        Click(Click),
+        Scroll(Scroll),
+        Resize { width: u32, height: u32 },
+    }
+}
+
+foreign_discriminant_generic_table!(
+    other::KeyCode {
+        Up,
+        Down,
+        #[cfg(feature = "cond_comp")]
+        Tab,
+        Escape,
+    },
+    ty_name = KeyBindings,
+    derive(Clone, Debug, PartialEq),
+);
+
+foreign_discriminant_generic_table!(
+    self::other::Event {
+        Click(Click),
+        Scroll(Scroll),
+        Resize { width: u32, height: u32 },
+    },
+    ty_name = EventCounters,
+    mod_name = event_counters,
+);
+
+foreign_variant_type_table!(
+    other::Event {
+        Click(other::Click),
+        Scroll(other::Scroll),
+        Resize { width: u32, height: u32 },
+    },
+    ty_name = LastEvents,
+);
+
+foreign_variant_generic_table!(
+    other::Event {
+        Click(other::Click),
+        Scroll(other::Scroll),
+        #[table_skip]
+        Resize { width: u32, height: u32 },
+    },
+    ty_name = EventHandlers,
+    mod_name = event_handlers,
+);
+
+#[test]
+fn test_foreign_discriminant_table() {
+    let mut bindings = KeyBindings::from_fn(|key| key != other::KeyCode::Escape);
+    assert!(bindings[other::KeyCode::Up]);
+    assert!(!bindings[other::KeyCode::Escape]);
+
+    bindings.set(other::KeyCode::Escape, true);
+    assert!(bindings.values().all(|value| *value));
+}
+
+#[test]
+fn test_foreign_data_discriminant_table() {
+    let mut counters = EventCounters::filled_with(0);
+    counters[&other::Event::Scroll(other::Scroll(1.0))] += 1;
+    counters[&other::Event::Resize {
+        width:  1,
+        height: 2,
+    }] += 2;
+
+    assert!(counters.into_values().eq([0, 1, 2]));
+    assert_eq!(<EventKind as DiscriminantKey>::from_ordinal(1), Some(EventKind::Scroll));
+}
+
+#[test]
+fn test_foreign_variant_tables() {
+    let mut last = LastEvents::new(other::Click { x: 1, y: 2 }, other::Scroll(0.5), (800, 600));
+
+    last.set_enum(other::Event::Resize {
+        width:  1920,
+        height: 1080,
+    });
+    assert_eq!(last.get::<other::Click>(), &other::Click { x: 1, y: 2 });
+    assert!(last.into_iter().any(|event| {
+        event
+            == other::Event::Resize {
+                width:  1920,
+                height: 1080,
+            }
+    }));
+
+    let handlers = EventHandlers::new("on_click", "on_scroll");
+    assert_eq!(handlers.get::<other::Scroll>(), &"on_scroll");
+}
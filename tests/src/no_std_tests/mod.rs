@@ -2,7 +2,26 @@ use super::*;
 
 mod basic_enum_test;
 mod discriminant_generic_tables;
+mod discriminant_matrices;
+mod discriminant_sets;
+mod enum_dispatch_compat;
+#[cfg(feature = "ffi")]
+mod ffi_mirrors;
+mod foreign_delegated_enums;
+mod foreign_tables;
+mod ordinals;
+mod repr_conversions;
 mod settings_enum;
 mod state_machine_test;
+mod state_machines;
+mod table_derives;
+mod tag_keyed_tables;
+mod unions;
 mod variant_generic_tables;
+mod variant_ids;
+mod variant_metas;
+mod variant_table_shapes;
+mod variant_type_sets;
 mod variant_type_tables;
+mod variant_vecs;
+mod wire_enums;
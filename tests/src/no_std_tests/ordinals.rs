@@ -0,0 +1,93 @@
+#![allow(unused)]
+use super::*;
+
+#[ordinal]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Quality {
+    Low  = 10,
+    #[cfg(feature = "cond_comp")]
+    Medium = 5,
+    High = 0,
+    Ultra = 20,
+}
+
+#[discriminant_generic_table(ty_name = StateTimers, ordinal)]
+pub enum PlayerState {
+    Idle,
+    Walking(f32),
+    Attacking { target: u32 },
+}
+
+#[test]
+fn test_ordinal_attribute() {
+    assert_eq!(Quality::first(), Quality::Low);
+    assert_eq!(Quality::last(), Quality::Ultra);
+    assert_eq!(Quality::Low.prev(), None);
+    assert_eq!(Quality::Ultra.next(), None);
+    assert_eq!(Quality::Ultra.cycle_next(), Quality::Low);
+    assert_eq!(Quality::Low.cycle_prev(), Quality::Ultra);
+    assert_eq!(Quality::from_ordinal(Quality::LEN), None);
+
+    #[cfg(feature = "cond_comp")]
+    {
+        assert_eq!(Quality::LEN, 4);
+        assert_eq!(Quality::Low.next(), Some(Quality::Medium));
+        assert_eq!(Quality::High.ordinal(), 2);
+    }
+
+    #[cfg(not(feature = "cond_comp"))]
+    {
+        assert_eq!(Quality::LEN, 3);
+        assert_eq!(Quality::Low.next(), Some(Quality::High));
+        assert_eq!(Quality::High.ordinal(), 1);
+    }
+
+    let mut range = Quality::range(Quality::High..=Quality::Ultra);
+    assert_eq!(range.len(), 2);
+    assert_eq!(range.next_back(), Some(Quality::Ultra));
+    assert_eq!(range.next(), Some(Quality::High));
+    assert_eq!(range.next(), None);
+
+    assert_eq!(Quality::range(Quality::Ultra..=Quality::Low).count(), 0);
+    assert_eq!(Quality::all().count(), Quality::LEN);
+
+    const SECOND: Option<Quality> = Quality::first().next();
+    assert!(SECOND.is_some());
+}
+
+// Without the setting `ordinal`, the table leaves the enum's inherent namespace alone.
+#[discriminant_generic_table(ty_name = MenuTimers)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Menu {
+    Play,
+    Options,
+    Quit,
+}
+
+impl Menu {
+    fn next(self) -> Self {
+        match self {
+            Menu::Play => Menu::Options,
+            Menu::Options | Menu::Quit => Menu::Quit,
+        }
+    }
+}
+
+#[test]
+fn test_table_ordinals() {
+    assert_eq!(PlayerStateKind::LEN, 3);
+    assert_eq!(PlayerStateKind::Idle.next(), Some(PlayerStateKind::Walking));
+    assert_eq!(PlayerStateKind::Attacking.cycle_next(), PlayerStateKind::Idle);
+    assert_eq!(PlayerStateKind::from(&PlayerState::Attacking { target: 0 }).ordinal(), 2);
+
+    let mut kinds = PlayerStateKind::all();
+    assert_eq!(kinds.next(), Some(PlayerStateKind::Idle));
+    assert_eq!(kinds.next_back(), Some(PlayerStateKind::Attacking));
+}
+
+#[test]
+fn test_table_without_ordinals() {
+    assert_eq!(Menu::Play.next(), Menu::Options);
+    assert_eq!(<Menu as DiscriminantKey>::LEN, 3);
+    assert_eq!(<Menu as DiscriminantKey>::from_ordinal(2), Some(Menu::Quit));
+}
@@ -0,0 +1,46 @@
+#![allow(unused)]
+use super::*;
+
+#[repr_conversions]
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PacketKind {
+    Handshake = 1,
+    Ping,
+    #[cfg(feature = "cond_comp")]
+    Compressed = 5,
+    #[cfg(not(feature = "cond_comp"))]
+    Uncompressed = 5,
+    Disconnect = 10,
+}
+
+#[repr_conversions]
+#[repr(i16)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Offset {
+    Back = -1,
+    Stay,
+    Forward,
+}
+
+#[test]
+fn test_repr_conversions() {
+    assert_eq!(u8::from(PacketKind::Handshake), 1);
+    assert_eq!(u8::from(PacketKind::Ping), 2);
+    assert_eq!(PacketKind::try_from(10), Ok(PacketKind::Disconnect));
+    assert_eq!(PacketKind::try_from(3), Err(InvalidRepr::new("PacketKind", 3)));
+    assert_eq!(PacketKind::try_from(3).unwrap_err().into_value(), 3);
+    assert_eq!(PacketKind::DISCRIMINANTS, [1, 2, 5, 10]);
+
+    #[cfg(feature = "cond_comp")]
+    assert_eq!(PacketKind::from_repr(5), Some(PacketKind::Compressed));
+    #[cfg(not(feature = "cond_comp"))]
+    assert_eq!(PacketKind::from_repr(5), Some(PacketKind::Uncompressed));
+
+    assert_eq!(Offset::DISCRIMINANTS, [-1, 0, 1]);
+    assert_eq!(Offset::from_repr(0), Some(Offset::Stay));
+    assert_eq!(i16::from(Offset::Forward), 1);
+
+    const PING: Option<PacketKind> = PacketKind::from_repr(2);
+    assert_eq!(PING, Some(PacketKind::Ping));
+}
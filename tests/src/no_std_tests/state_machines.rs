@@ -0,0 +1,169 @@
+#![allow(unused)]
+use super::*;
+
+#[derive(Default)]
+pub struct TurretBody {
+    entered: u32,
+    exited: u32,
+    ammo: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Idle;
+
+#[derive(Debug, PartialEq)]
+pub struct Aiming {
+    target_id: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ChargingShot {
+    target_id: usize,
+    progress:  f32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CoolingDown {
+    time_remaining: f32,
+}
+
+impl StateHooks<TurretBody> for Idle {}
+
+impl StateHooks<TurretBody> for Aiming {
+    fn enter(&mut self, body: &mut TurretBody) { body.entered += 1; }
+    fn exit(&mut self, body: &mut TurretBody) { body.exited += 1; }
+}
+
+impl StateHooks<TurretBody> for ChargingShot {
+    fn exit(&mut self, body: &mut TurretBody) { body.ammo -= 1; }
+}
+
+impl StateHooks<TurretBody> for CoolingDown {}
+
+#[delegated_enum(state_machine(context = TurretBody))]
+#[derive(Debug, PartialEq)]
+pub enum Turret {
+    #[transitions(Aiming)]
+    Idle(Idle),
+    #[transitions(Idle, ChargingShot)]
+    Aiming(Aiming),
+    #[transitions(CoolingDown)]
+    ChargingShot(ChargingShot),
+    #[cfg(feature = "cond_comp")]
+    #[transitions(Idle)]
+    Overheated(CoolingDown),
+    CoolingDown(CoolingDown),
+}
+
+#[delegate_impl]
+impl StateHooks<TurretBody> for Turret {
+    fn enter(&mut self, body: &mut TurretBody);
+    fn exit(&mut self, body: &mut TurretBody);
+}
+
+#[test]
+fn test_state_machine_transitions() {
+    let mut body = TurretBody {
+        ammo: 3,
+        ..TurretBody::default()
+    };
+
+    let mut machine = Machine::new(Turret::Idle(Idle), &mut body);
+    assert_eq!(machine.kind(), TurretStateKind::Idle);
+    assert!(machine.can_transition_to(TurretStateKind::Aiming));
+    assert!(!machine.can_transition_to(TurretStateKind::CoolingDown));
+
+    let previous = machine.transition(&mut body, Turret::Aiming(Aiming { target_id: 7 }));
+    assert_eq!(previous.ok(), Some(Turret::Idle(Idle)));
+    assert_eq!(body.entered, 1);
+
+    // Undeclared transitions are rejected, without calling any hooks.
+    let err = machine
+        .transition(
+            &mut body,
+            Turret::CoolingDown(CoolingDown {
+                time_remaining: 1.0,
+            }),
+        )
+        .unwrap_err();
+    assert_eq!((err.from(), err.to()), (TurretStateKind::Aiming, TurretStateKind::CoolingDown));
+    assert_eq!(body.exited, 0);
+    assert_eq!(machine.state(), &Turret::Aiming(Aiming { target_id: 7 }));
+
+    // Moves the target into the next state.
+    machine
+        .transition_with(&mut body, TurretStateKind::ChargingShot, |old| {
+            match old {
+                Turret::Aiming(Aiming { target_id }) => {
+                    Turret::ChargingShot(ChargingShot {
+                        target_id,
+                        progress: 0.0,
+                    })
+                }
+                _ => unreachable!(),
+            }
+        })
+        .unwrap();
+    assert_eq!(body.exited, 1);
+    assert_eq!(
+        machine.state(),
+        &Turret::ChargingShot(ChargingShot {
+            target_id: 7,
+            progress:  0.0,
+        })
+    );
+
+    // Undeclared transitions are rejected before calling `exit` or the closure, the machine remains usable.
+    let err = machine
+        .transition_with(&mut body, TurretStateKind::Idle, |_| unreachable!())
+        .unwrap_err();
+    assert_eq!((err.from(), err.to()), (TurretStateKind::ChargingShot, TurretStateKind::Idle));
+    assert!(err.into_rejected().is_none());
+    assert_eq!(body.ammo, 3);
+    assert!(!machine.is_poisoned());
+    assert_eq!(machine.kind(), TurretStateKind::ChargingShot);
+
+    // Variants without `#[transitions(..)]` may transition into any variant.
+    assert!(Turret::can_transition(TurretStateKind::CoolingDown, TurretStateKind::ChargingShot));
+    assert!(!Turret::can_transition(TurretStateKind::ChargingShot, TurretStateKind::Idle));
+}
+
+#[test]
+fn test_state_machine_history() {
+    let mut body = TurretBody::default();
+    let mut history = [None; 4];
+    let mut len = 0;
+
+    let mut machine = Machine::new(Turret::Idle(Idle), &mut body).with_hook(|from, to| {
+        history[len] = Some((from, to));
+        len += 1;
+    });
+
+    machine
+        .transition(&mut body, Turret::Aiming(Aiming { target_id: 1 }))
+        .unwrap();
+    machine.transition(&mut body, Turret::Idle(Idle)).unwrap();
+
+    assert_eq!(len, 2);
+    assert_eq!(history[0], Some((TurretStateKind::Idle, TurretStateKind::Aiming)));
+    assert_eq!(history[1], Some((TurretStateKind::Aiming, TurretStateKind::Idle)));
+}
+
+#[cfg(not(feature = "no_std"))]
+#[test]
+fn test_state_machine_poisoning() {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+
+    let mut body = TurretBody::default();
+    let mut machine = Machine::new(Turret::Idle(Idle), &mut body);
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        machine.transition_with(&mut body, TurretStateKind::Aiming, |_| {
+            panic!("failed to build the next state")
+        })
+    }));
+
+    assert!(result.is_err());
+    assert!(machine.is_poisoned());
+    assert!(catch_unwind(AssertUnwindSafe(|| machine.kind())).is_err());
+}
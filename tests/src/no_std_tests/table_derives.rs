@@ -0,0 +1,98 @@
+#![allow(unused)]
+use super::*;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowSize(u32, u32);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaxFps(u32);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unsupported;
+
+#[delegated_enum(impl_conversions)]
+#[derive(Debug, Clone, PartialEq, VariantTypeTable, VariantGenericTable)]
+#[table(derive(Debug))]
+#[table(VariantTypeTable, ty_name = SettingsTable)]
+#[table(VariantGenericTable, ty_name = SettingsCounters, mod_name = settings_counters, impl_ops)]
+pub enum Setting {
+    WindowSize(WindowSize),
+    MaxFps(MaxFps),
+    #[table_skip]
+    Unsupported(Unsupported),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DiscriminantTable)]
+#[table(ty_name = VolumeTable, value_ty = f32, derive(Debug, PartialEq))]
+pub enum Channel {
+    #[default_value(1.0)]
+    Master,
+    #[default_value(0.5)]
+    Music,
+    Voice,
+}
+
+#[test]
+fn test_table_derives() {
+    let mut table = SettingsTable::new(WindowSize(800, 600), MaxFps(60));
+    assert_eq!(table.get::<MaxFps>(), &MaxFps(60));
+
+    table.set(WindowSize(1920, 1080));
+    assert_eq!(table.get::<WindowSize>(), &WindowSize(1920, 1080));
+
+    let mut counters = SettingsCounters::new(1, 2) + SettingsCounters::new(10, 20);
+    assert_eq!(counters.get::<WindowSize>(), &11);
+    assert_eq!(counters.get::<MaxFps>(), &22);
+
+    // The enum is left untouched by the derives.
+    let setting = Setting::from(Unsupported);
+    assert_eq!(Unsupported::try_from(setting), Ok(Unsupported));
+}
+
+#[test]
+fn test_discriminant_table_derive() {
+    let mut volumes = VolumeTable::default();
+    assert_eq!(volumes, VolumeTable::new(1.0, 0.5, 0.0));
+
+    volumes[Channel::Voice] = 0.25;
+    assert_eq!(volumes[Channel::Voice], 0.25);
+}
+
+#[variant_type_table(ty_name = ShapeTable)]
+#[derive(VariantGenericTable)]
+#[table(ty_name = ShapeCounters)]
+pub enum Shape {
+    Circle(u8),
+    Square(u16),
+    #[table_skip]
+    Hexagon(u32),
+}
+
+#[test]
+fn test_table_derives_with_attributes() {
+    let shapes = ShapeTable::new(1, 2);
+    let counters = ShapeCounters::new('a', 'b');
+
+    assert_eq!(shapes.get::<u16>(), &2);
+    assert_eq!(counters.get::<u8>(), &'a');
+}
+
+// `#[table_skip]` is registered by `VariantTypeTable`, `DiscriminantTable` needs every variant.
+#[derive(VariantTypeTable, DiscriminantTable)]
+#[table(VariantTypeTable, ty_name = ToolTable)]
+#[table(DiscriminantTable, ty_name = ToolWear)]
+pub enum Tool {
+    Axe(u8),
+    Pick(u16),
+    #[table_skip]
+    Hands(()),
+}
+
+#[test]
+fn test_table_skip_with_discriminant_table_derive() {
+    let tools = ToolTable::new(1, 2);
+    assert_eq!(tools.get::<u16>(), &2);
+
+    let wear = ToolWear::new(0.5, 0.25, 0.0);
+    assert_eq!(wear[ToolKind::Hands], 0.0);
+}
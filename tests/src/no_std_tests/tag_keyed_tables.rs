@@ -0,0 +1,63 @@
+use super::*;
+
+#[variant_type_table(
+    ty_name = ResourceTable,
+    mod_name = resource_table,
+    keys = tags,
+    derive(Debug, Clone),
+)]
+#[variant_type_set(ty_name = ResourceSet, mod_name = resource_set, keys = tags(set_tags))]
+#[variant_generic_table(ty_name = ResourceCaps, mod_name = resource_caps, keys = tags(cap_tags))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resource<T> {
+    Health(u32),
+    Mana(u32),
+    Custom(T),
+}
+
+#[test]
+fn test_tag_keyed_table() {
+    let mut table = ResourceTable::new(100, 50, 'x');
+
+    assert_eq!(table.get::<tags::Health>(), &100);
+    assert_eq!(table.get::<tags::Mana>(), &50);
+    assert_eq!(table.get::<tags::Custom>(), &'x');
+
+    *table.get_mut::<tags::Mana>() -= 20;
+    table.set::<tags::Custom>('y');
+    table.set_enum(Resource::Health(80));
+
+    assert!(table.into_iter().eq([
+        Resource::Health(80),
+        Resource::Mana(30),
+        Resource::Custom('y'),
+    ]));
+}
+
+#[test]
+fn test_tag_keyed_set() {
+    let mut set = ResourceSet::<&str>::new();
+    set.insert(Resource::Mana(10));
+    set.insert(Resource::Custom("shield"));
+
+    assert_eq!(set.get::<set_tags::Health>(), None);
+    assert_eq!(set.get::<set_tags::Mana>(), Some(&10));
+    assert!(set.contains::<set_tags::Custom>());
+
+    assert_eq!(set.remove::<set_tags::Custom>(), Some("shield"));
+    assert!(set.into_iter().eq([Resource::Mana(10)]));
+}
+
+#[test]
+fn test_tag_keyed_generic_table() {
+    let mut caps = ResourceCaps::filled_with(0_u16);
+    *caps.get_mut::<cap_tags::Health>() = 200;
+    caps.set::<cap_tags::Mana>(100);
+
+    assert_eq!(caps.get::<cap_tags::Health>(), &200);
+    assert_eq!(caps.get::<cap_tags::Mana>(), &100);
+    assert_eq!(caps.get::<cap_tags::Custom>(), &0);
+
+    // Tags are plain zero-sized types
+    assert_eq!(core::mem::size_of::<tags::Health>(), 0);
+}
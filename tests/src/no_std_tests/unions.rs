@@ -0,0 +1,74 @@
+#![allow(unused)]
+use super::*;
+
+mod shapes {
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Circle(pub i32);
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Polygon<T> {
+        pub sides: T,
+    }
+}
+
+use shapes::{Circle, Polygon};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Square(i32);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hexagon(i32);
+
+trait Sides {
+    fn sides(&self) -> usize;
+}
+
+impl Sides for Circle {
+    fn sides(&self) -> usize { 0 }
+}
+
+impl Sides for Square {
+    fn sides(&self) -> usize { 4 }
+}
+
+impl Sides for Hexagon {
+    fn sides(&self) -> usize { 6 }
+}
+
+impl<T: Copy + Into<usize>> Sides for Polygon<T> {
+    fn sides(&self) -> usize { self.sides.into() }
+}
+
+union! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Shape<T: Copy + Into<usize>> = shapes::Circle
+        | Square
+        | #[cfg(feature = "cond_comp")] Hexagon
+        | Polygon<T>;
+    settings(impl_conversions)
+}
+
+#[delegate_impl]
+impl<T: Copy + Into<usize>> Sides for Shape<T> {
+    fn sides(&self) -> usize;
+}
+
+union! {
+    enum Plain = Circle | Square
+}
+
+#[test]
+fn test_union() {
+    let circle = Shape::<u8>::from(Circle(2));
+    assert_eq!(circle, Shape::Circle(Circle(2)));
+    assert_eq!(circle.sides(), 0);
+
+    let polygon = Shape::Polygon(Polygon { sides: 5u8 });
+    assert_eq!(polygon.sides(), 5);
+    assert_eq!(Polygon::try_from(polygon), Ok(Polygon { sides: 5 }));
+
+    #[cfg(feature = "cond_comp")]
+    assert_eq!(Shape::<u8>::Hexagon(Hexagon(1)).sides(), 6);
+
+    assert!(matches!(Plain::Square(Square(1)), Plain::Square(_)));
+}
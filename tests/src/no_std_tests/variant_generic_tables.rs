@@ -6,6 +6,7 @@ use super::*;
     mod_name = actor_stats_table,
     attrs(derive(Debug), derive(Clone)),
     derive(Default),
+    impl_ops,
 )]
 enum Stat {
     Strength,
@@ -35,6 +36,31 @@ fn test() {
     assert_eq!(table.get::<Strength>(), &8);
 }
 
+#[variant_generic_table(ty_name = Volumes, value_ty = f32, derive(Debug, PartialEq))]
+#[allow(unused)]
+enum Channel {
+    #[default_value(1.0)]
+    Master(u8),
+    #[default_value(0.8)]
+    Music(u16),
+    Voice(u32),
+}
+
+#[test]
+fn test_default_values() {
+    let mut volumes = Volumes::default();
+    assert_eq!(volumes, Volumes::new(1.0, 0.8, 0.0));
+    assert_eq!(Volumes::default_value(channel_variant_generic_table::ChannelKey::Music), 0.8);
+
+    volumes.set::<u8>(0.5);
+    volumes.set::<u16>(0.2);
+    volumes.reset::<u16>();
+    assert_eq!(volumes, Volumes::new(0.5, 0.8, 0.0));
+
+    volumes.reset_all();
+    assert_eq!(volumes, Volumes::defaults());
+}
+
 // Define an enum with the variant_generic_table macro
 #[variant_generic_table]
 #[allow(unused)]
@@ -219,3 +245,91 @@ fn test_variant_generic_table_with_trait() {
         }
     }
 }
+
+#[test]
+fn test_variant_generic_table_combinators() {
+    use actor_stats_table::StatKey;
+
+    let table = ActorStats::from_fn(|key| {
+        match key {
+            StatKey::Strength => 1,
+            StatKey::Intelligence => 2,
+            StatKey::Constitution => 3,
+        }
+    });
+
+    assert!(table.keys().eq([
+        StatKey::Strength,
+        StatKey::Intelligence,
+        StatKey::Constitution
+    ]));
+    assert_eq!(table.values().copied().sum::<i32>(), 6);
+
+    let doubled = table.clone().map(|_, value| value * 2);
+    assert_eq!(doubled.get::<Intelligence>(), &4);
+
+    let zipped = table.clone().zip(doubled);
+    assert_eq!(zipped.get::<Constitution>(), &(3, 6));
+
+    let checked = table.clone().try_map(|key, value| {
+        if key == StatKey::Constitution {
+            Err(value)
+        } else {
+            Ok(value)
+        }
+    });
+    assert_eq!(checked.err().unwrap(), 3);
+
+    let mut table = table;
+    for value in table.as_mut().into_values() {
+        *value += 10;
+    }
+    assert!(table.as_ref().into_values().copied().eq([11, 12, 13]));
+}
+
+#[test]
+fn test_variant_generic_table_from_iter() {
+    use actor_stats_table::StatKey;
+
+    let table: ActorStats<u8> = [
+        (StatKey::Constitution, 3),
+        (StatKey::Strength, 1),
+        (StatKey::Intelligence, 2),
+    ]
+    .into_iter()
+    .collect();
+
+    assert!(table.into_values().eq([1, 2, 3]));
+
+    let missing = ActorStats::try_from_iter([(StatKey::Intelligence, 2)])
+        .err()
+        .unwrap();
+    assert_eq!(missing.names(), ["Strength", "Constitution"]);
+
+    #[cfg(not(feature = "no_std"))]
+    assert_eq!(missing.to_string(), "missing values for variants: `Strength`, `Constitution`");
+}
+
+#[test]
+fn test_variant_generic_table_ops() {
+    let base = ActorStats::new(10, 20, 30);
+    let bonus = ActorStats::new(1, 2, 3);
+
+    let total = base.clone() + bonus.clone();
+    assert!(total.clone().into_values().eq([11, 22, 33]));
+    assert!(
+        (total.clone() - bonus.clone())
+            .into_values()
+            .eq([10, 20, 30])
+    );
+    assert!((bonus.clone() * 3).into_values().eq([3, 6, 9]));
+    assert!((-bonus.clone()).into_values().eq([-1, -2, -3]));
+
+    let mut stats = base;
+    stats -= 5;
+    stats *= bonus.clone();
+    assert!(stats.into_values().eq([5, 30, 75]));
+
+    let summed: ActorStats<i32> = [bonus.clone(), bonus.clone(), bonus].into_iter().sum();
+    assert!(summed.into_values().eq([3, 6, 9]));
+}
@@ -0,0 +1,125 @@
+#![allow(unused)]
+use super::*;
+
+#[delegated_enum(wire)]
+#[derive(Debug, PartialEq)]
+pub enum Event {
+    #[variant_id = 10]
+    Login(u16),
+    #[cfg(feature = "cond_comp")]
+    #[variant_id = 30]
+    Debug(u8),
+    #[variant_id = 20]
+    #[former_names("Exit")]
+    #[former_names("Quit")]
+    Logout(u16),
+    #[variant_id = 70000]
+    Message { from: u16, len: u8 },
+}
+
+#[delegated_enum(wire)]
+#[derive(Debug, PartialEq)]
+pub enum Packet {
+    #[variant_id = 1]
+    Ping(u8),
+    #[cfg(feature = "cond_comp")]
+    #[variant_id = 2]
+    Trace(u32),
+    #[cfg(not(feature = "cond_comp"))]
+    #[variant_id = 2]
+    #[former_names("Trace")]
+    Log(u8),
+}
+
+#[test]
+fn test_variant_ids() {
+    assert_eq!(Event::Login(1).variant_id(), 10);
+    assert_eq!(Event::Logout(1).variant_id(), 20);
+    assert_eq!(Event::Message { from: 1, len: 2 }.variant_id(), 70000);
+
+    assert_eq!(Event::variant_id_from_name("Login"), Some(10));
+    assert_eq!(Event::variant_id_from_name("Logout"), Some(20));
+    assert_eq!(Event::variant_id_from_name("Exit"), Some(20));
+    assert_eq!(Event::variant_id_from_name("Quit"), Some(20));
+    assert_eq!(Event::variant_id_from_name("Unknown"), None);
+
+    assert_eq!(Event::variant_name_from_id(20), Some("Logout"));
+    assert_eq!(Event::variant_name_from_id(70000), Some("Message"));
+    assert_eq!(Event::variant_name_from_id(0), None);
+
+    #[cfg(feature = "cond_comp")]
+    {
+        assert_eq!(Event::Debug(0).variant_id(), 30);
+        assert_eq!(Event::variant_id_from_name("Debug"), Some(30));
+    }
+
+    #[cfg(not(feature = "cond_comp"))]
+    {
+        assert_eq!(Event::variant_id_from_name("Debug"), None);
+        assert_eq!(Event::variant_name_from_id(30), None);
+    }
+}
+
+#[test]
+fn test_variant_ids_wire() {
+    let mut buffer = [0_u8; 16];
+
+    let mut writer = &mut buffer[..];
+    Event::Logout(5).encode(&mut writer).unwrap();
+    let written = 16 - writer.len();
+    assert_eq!(buffer[..written], [20, 0, 0, 0, 5, 0]);
+
+    let mut reader = &buffer[..written];
+    assert_eq!(Event::decode(&mut reader), Ok(Event::Logout(5)));
+
+    let mut reader = &[0x70_u8, 0x11, 0x01, 0, 1, 0, 2][..];
+    assert_eq!(Event::decode(&mut reader), Ok(Event::Message { from: 1, len: 2 }));
+
+    let mut reader = &[0_u8, 0, 0, 0][..];
+    assert_eq!(
+        Event::decode(&mut reader),
+        Err(WireError::InvalidTag {
+            type_name: "Event",
+            tag: 0,
+        })
+    );
+}
+
+#[test]
+fn test_from_variant_id() {
+    let mut reader = &[5_u8, 0][..];
+    assert_eq!(Event::from_variant_id(20, &mut reader), Ok(Event::Logout(5)));
+    assert!(reader.is_empty());
+
+    let mut reader = &[1_u8, 0, 2][..];
+    assert_eq!(Event::from_variant_id(70000, &mut reader), Ok(Event::Message { from: 1, len: 2 }));
+
+    let mut reader = &[1_u8][..];
+    assert_eq!(
+        Event::from_variant_id(7, &mut reader),
+        Err(WireError::InvalidTag {
+            type_name: "Event",
+            tag: 7,
+        })
+    );
+}
+
+#[test]
+fn test_variant_ids_exclusive_cfgs() {
+    assert_eq!(Packet::Ping(0).variant_id(), 1);
+    assert_eq!(Packet::variant_id_from_name("Trace"), Some(2));
+
+    #[cfg(feature = "cond_comp")]
+    {
+        assert_eq!(Packet::variant_name_from_id(2), Some("Trace"));
+        let mut reader = &[9_u8, 0, 0, 0][..];
+        assert_eq!(Packet::from_variant_id(2, &mut reader), Ok(Packet::Trace(9)));
+    }
+
+    #[cfg(not(feature = "cond_comp"))]
+    {
+        assert_eq!(Packet::variant_name_from_id(2), Some("Log"));
+        let mut reader = &[9_u8][..];
+        assert_eq!(Packet::from_variant_id(2, &mut reader), Ok(Packet::Log(9)));
+    }
+}
@@ -0,0 +1,60 @@
+#![allow(unused)]
+use super::*;
+
+#[derive(Debug, PartialEq)]
+pub struct StateMeta {
+    pub label: &'static str,
+    pub icon: &'static str,
+    pub weight: u32,
+    pub tooltip: &'static str,
+}
+
+#[variant_meta(struct = StateMeta, docs = tooltip)]
+pub enum State {
+    /// Standing still.
+    #[meta(label = "Idle", icon = "idle.png", weight = 3)]
+    Idle,
+    /// Moving towards
+    /// the target.
+    #[meta(label = "Walking", icon = "walk.png")]
+    #[meta(weight = 1)]
+    Walking { speed: f32 },
+    #[cfg(feature = "cond_comp")]
+    /// Only with `cond_comp`.
+    #[meta(label = "Flying", icon = "fly.png", weight = 5)]
+    Flying(f32),
+    #[meta(label = "Dead", icon = "dead.png", weight = 0, tooltip = "Game over.")]
+    Dead,
+}
+
+#[test]
+fn test_variant_metas() {
+    assert_eq!(State::Idle.meta().label, "Idle");
+    assert_eq!(State::Idle.meta().tooltip, "Standing still.");
+    assert_eq!(
+        State::Walking { speed: 1.0 }.meta(),
+        &StateMeta {
+            label: "Walking",
+            icon: "walk.png",
+            weight: 1,
+            tooltip: "Moving towards\nthe target.",
+        }
+    );
+    assert_eq!(State::Dead.meta().tooltip, "Game over.");
+
+    #[cfg(feature = "cond_comp")]
+    {
+        assert_eq!(State::Flying(2.0).meta().weight, 5);
+        assert_eq!(State::METAS.len(), 4);
+        assert_eq!(State::METAS[3].label, "Dead");
+    }
+
+    #[cfg(not(feature = "cond_comp"))]
+    {
+        assert_eq!(State::METAS.len(), 3);
+        assert_eq!(State::METAS[2].label, "Dead");
+    }
+
+    const IDLE_WEIGHT: u32 = State::Idle.meta().weight;
+    assert_eq!(IDLE_WEIGHT, 3);
+}
@@ -0,0 +1,86 @@
+use super::*;
+
+#[variant_type_table(ty_name = ShapeTable, mod_name = shape_table, derive(Debug, Clone))]
+#[variant_type_set(ty_name = ShapeSet, mod_name = shape_set)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Point,
+    Circle {
+        radius: f32,
+    },
+    Rect(u32, u32),
+    Polygon {
+        sides: u8,
+        side_len: f64,
+    },
+    #[table_skip]
+    Empty,
+}
+
+#[test]
+fn test_variant_type_table() {
+    let mut table = ShapeTable::new((), 2.0, (3, 4), (5, 1.5));
+
+    assert_eq!(table.get::<()>(), &());
+    assert_eq!(table.get::<f32>(), &2.0);
+    assert_eq!(table.get::<(u32, u32)>(), &(3, 4));
+    assert_eq!(table.get::<(u8, f64)>(), &(5, 1.5));
+
+    table.set_enum(Shape::Circle { radius: 4.0 });
+    table.set_enum(Shape::Rect(6, 7));
+    table.set_enum(Shape::Polygon {
+        sides: 8,
+        side_len: 0.5,
+    });
+    table.set_enum(Shape::Point);
+    table.set_enum(Shape::Empty);
+
+    assert!(table.into_iter().eq([
+        Shape::Point,
+        Shape::Circle { radius: 4.0 },
+        Shape::Rect(6, 7),
+        Shape::Polygon {
+            sides: 8,
+            side_len: 0.5,
+        },
+    ]));
+}
+
+#[test]
+fn test_variant_type_set() {
+    let mut set = ShapeSet::new();
+    assert_eq!(set.insert(Shape::Rect(1, 2)), None);
+    assert_eq!(set.insert(Shape::Rect(3, 4)), Some(Shape::Rect(1, 2)));
+    assert_eq!(set.insert(Shape::Point), None);
+
+    assert_eq!(set.get::<(u32, u32)>(), Some(&(3, 4)));
+    assert!(set.contains::<()>());
+    assert!(!set.contains::<f32>());
+    assert_eq!(set.insert(Shape::Empty), Some(Shape::Empty));
+
+    assert!(set.into_iter().eq([Shape::Point, Shape::Rect(3, 4)]));
+}
+
+#[delegated_enum(extract_variants(derive(Debug, Clone, PartialEq)))]
+#[variant_generic_table(ty_name = SkillCooldowns, mod_name = skill_cooldowns)]
+pub enum Skill {
+    Dash {
+        distance: f32,
+    },
+    Fireball(u32, u32),
+    #[table_skip]
+    Wait,
+}
+
+#[test]
+fn test_extracted_with_skip() {
+    let mut cooldowns = SkillCooldowns::new(1.5, 3.0);
+    *cooldowns.get_mut::<Fireball>() += 1.0;
+
+    assert_eq!(cooldowns.get::<Dash>(), &1.5);
+    assert_eq!(cooldowns.get::<Fireball>(), &4.0);
+
+    // The skipped variant is still part of the enum.
+    let skill = Skill::Wait(Wait);
+    assert!(matches!(skill, Skill::Wait(Wait)));
+}
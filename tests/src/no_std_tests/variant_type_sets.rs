@@ -0,0 +1,67 @@
+use super::*;
+
+#[delegated_enum(extract_variants(derive(Debug, Clone, PartialEq)))]
+#[variant_type_set(
+    mod_name = effects_set,
+    ty_name = ActiveEffects,
+    derive(Clone, Debug),
+)]
+pub enum StatusEffect {
+    Poison(u32),
+    Stun(u8),
+    Haste(bool),
+}
+
+#[test]
+fn test() {
+    let mut effects = ActiveEffects::new();
+    assert!(effects.is_empty());
+    assert_eq!(effects.len(), 0);
+    assert_eq!(effects.get::<Poison>(), None);
+
+    assert!(effects.insert(StatusEffect::Poison(Poison(5))).is_none());
+    assert!(effects.insert(StatusEffect::Haste(Haste(true))).is_none());
+    assert_eq!(effects.len(), 2);
+    assert!(effects.contains::<Poison>());
+    assert!(!effects.contains::<Stun>());
+    assert_eq!(effects.get::<Poison>(), Some(&Poison(5)));
+
+    // Inserting a variant that is already present replaces it.
+    let old = effects.insert(StatusEffect::Poison(Poison(8)));
+    assert!(matches!(old, Some(StatusEffect::Poison(Poison(5)))));
+    assert_eq!(effects.len(), 2);
+
+    if let Some(poison) = effects.get_mut::<Poison>() {
+        poison.0 += 1;
+    }
+
+    // Ensure clone and debug work
+    let effects_clone = effects.clone();
+    let _ = <ActiveEffects as Debug>::fmt;
+
+    {
+        let mut iter = effects.iter();
+        assert!(matches!(iter.next(), Some(effects_set::StatusEffectRef::Poison(Poison(9)))));
+        assert!(matches!(iter.next(), Some(effects_set::StatusEffectRef::Haste(Haste(true)))));
+        assert!(iter.next().is_none());
+    }
+
+    for effect in &mut effects {
+        if let effects_set::StatusEffectMut::Haste(haste) = effect {
+            haste.0 = false;
+        }
+    }
+
+    assert_eq!(effects.remove::<Haste>(), Some(Haste(false)));
+    assert_eq!(effects.remove::<Haste>(), None);
+    assert_eq!(effects.len(), 1);
+
+    let mut owned = effects_clone.into_iter();
+    assert!(matches!(owned.next(), Some(StatusEffect::Poison(Poison(9)))));
+    assert!(matches!(owned.next(), Some(StatusEffect::Haste(Haste(true)))));
+    assert!(owned.next().is_none());
+
+    effects.clear();
+    assert!(effects.is_empty());
+    assert_eq!(ActiveEffects::default().len(), 0);
+}
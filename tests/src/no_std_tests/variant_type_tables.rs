@@ -43,3 +43,49 @@ fn test() {
     table.set_enum(SettingsEnum::MaxFps(MaxFps(25)));
     assert_eq!(*table.get::<MaxFps>(), MaxFps(25));
 }
+
+#[test]
+fn test_builder() {
+    let missing = SettingsTable::builder()
+        .with::<Vsync>(Vsync(true))
+        .with::<MaxFps>(MaxFps(30))
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        missing.names(),
+        [
+            "DialogueTextSpeed",
+            "MainVolume",
+            "MusicVolume",
+            "SfxVolume",
+            "VoiceVolume"
+        ]
+    );
+
+    let mut builder = [
+        SettingsEnum::MaxFps(MaxFps(30)),
+        SettingsEnum::DialogueTextSpeed(DialogueTextSpeed(2)),
+        SettingsEnum::MaxFps(MaxFps(60)),
+        SettingsEnum::MainVolume(MainVolume(5)),
+    ]
+    .into_iter()
+    .collect::<SettingsTableBuilder>();
+
+    let duplicate = builder
+        .insert_enum(SettingsEnum::MaxFps(MaxFps(144)))
+        .unwrap_err();
+    assert_eq!(duplicate.name(), "MaxFps");
+    assert!(matches!(duplicate.into_value(), SettingsEnum::MaxFps(MaxFps(144))));
+
+    for setting in [
+        SettingsEnum::Vsync(Vsync(false)),
+        SettingsEnum::MusicVolume(MusicVolume(6)),
+        SettingsEnum::SfxVolume(SfxVolume(7)),
+    ] {
+        assert!(builder.insert_enum(setting).is_ok());
+    }
+
+    let table = builder.with::<VoiceVolume>(VoiceVolume(8)).build().unwrap();
+    assert_eq!(*table.get::<MaxFps>(), MaxFps(60));
+    assert_eq!(*table.get::<VoiceVolume>(), VoiceVolume(8));
+}
@@ -0,0 +1,44 @@
+#![allow(unused)]
+use super::*;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Move {
+    dx: i32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Wait;
+
+#[delegated_enum]
+#[variant_vecs(capacity = 2, ty_name = Commands, keys = tags(command_tags))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Move(Move),
+    Wait(Wait),
+    Jump { height: u8 },
+}
+
+#[test]
+fn test_fixed_variant_vecs() {
+    let mut commands = Commands::new();
+    assert_eq!(COMMANDS_CAPACITY, 2);
+
+    assert_eq!(commands.push(Command::Move(Move { dx: 1 })), Ok(()));
+    assert_eq!(commands.push(Command::Move(Move { dx: -1 })), Ok(()));
+    assert_eq!(commands.push(Command::Move(Move { dx: 3 })), Err(Command::Move(Move { dx: 3 })));
+    assert_eq!(commands.push(Command::Jump { height: 4 }), Ok(()));
+
+    assert_eq!(commands.len(), 3);
+    assert!(commands.get::<command_tags::Move>().is_full());
+    assert_eq!(commands.len_of::<command_tags::Wait>(), 0);
+    assert_eq!(commands.iter::<command_tags::Jump>().next(), Some(&4));
+
+    for_each_delegated_commands!(commands => |command| *command = Default::default());
+    assert_eq!(commands.get::<command_tags::Move>().as_slice(), [Move::default(), Move::default()]);
+
+    commands.retain::<command_tags::Move>(|_| false);
+
+    let mut drained = commands.drain();
+    assert_eq!(drained.next(), Some(Command::Jump { height: 0 }));
+    assert_eq!(drained.next(), None);
+}
@@ -0,0 +1,76 @@
+#![allow(unused)]
+use super::*;
+
+#[delegated_enum(wire)]
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Move {
+        x: i16,
+        #[delegator]
+        y: i16,
+    },
+    Say([u8; 4]),
+    #[cfg(feature = "cond_comp")]
+    Jump(u32),
+    Toggle((bool, char)),
+}
+
+#[delegated_enum(wire, extract_variants(derive(Debug, PartialEq)))]
+#[derive(Debug, PartialEq)]
+pub enum Packet<T> {
+    Ping(u64),
+    Data { id: u8, payload: T },
+}
+
+#[test]
+fn test_wire_roundtrip() {
+    let mut buffer = [0_u8; 32];
+
+    let mut writer = &mut buffer[..];
+    Command::Move { x: 1, y: -1 }.encode(&mut writer).unwrap();
+    Command::Toggle((true, 'z')).encode(&mut writer).unwrap();
+    let written = 32 - writer.len();
+    assert_eq!(written, 5 + 6);
+    assert_eq!(buffer[..5], [0, 1, 0, 0xFF, 0xFF]);
+
+    // Tags count the variants disabled by `#[cfg]`.
+    assert_eq!(buffer[5], 3);
+
+    let mut reader = &buffer[..written];
+    assert_eq!(Command::decode(&mut reader), Ok(Command::Move { x: 1, y: -1 }));
+    assert_eq!(Command::decode(&mut reader), Ok(Command::Toggle((true, 'z'))));
+    assert_eq!(Command::decode(&mut reader), Err(WireError::UnexpectedEnd));
+
+    let mut reader = &[9_u8][..];
+    assert_eq!(
+        Command::decode(&mut reader),
+        Err(WireError::InvalidTag {
+            type_name: "Command",
+            tag: 9,
+        })
+    );
+
+    let mut reader = &[3_u8, 2, b'a', 0, 0, 0][..];
+    assert_eq!(Command::decode(&mut reader), Err(WireError::InvalidValue { type_name: "bool" }));
+
+    let mut small = [0_u8; 2];
+    let mut writer = &mut small[..];
+    assert_eq!(Command::Say(*b"spam").encode(&mut writer), Err(WireError::BufferFull));
+}
+
+#[test]
+fn test_wire_generic_extracted() {
+    let mut buffer = [0_u8; 16];
+
+    let packet = Packet::Data(Data {
+        id: 7,
+        payload: [1_u16, 2],
+    });
+
+    let mut writer = &mut buffer[..];
+    packet.encode(&mut writer).unwrap();
+    assert_eq!(16 - writer.len(), 6);
+
+    let mut reader = &buffer[..];
+    assert_eq!(Packet::<[u16; 2]>::decode(&mut reader), Ok(packet));
+}
@@ -0,0 +1,106 @@
+#![allow(unused)]
+use spire_enum::arbitrary::{Arbitrary, Unstructured};
+
+use super::*;
+
+#[delegated_enum(extract_variants(derive(Debug, PartialEq)), arbitrary)]
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    #[weight(3)]
+    Move {
+        x: i8,
+        y: i8,
+    },
+    Say(u8, bool),
+    #[cfg(feature = "cond_comp")]
+    Wait(u16),
+    #[arbitrary(skip)]
+    Connect(std::net::Ipv4Addr),
+}
+
+#[delegated_enum(arbitrary)]
+#[derive(Debug, PartialEq)]
+pub enum Event<T> {
+    #[delegate_via(|| -> u8 { 0 })]
+    Tick,
+    Payload(T),
+}
+
+#[variant_generic_table(ty_name = Budget, mod_name = budget, derive(Debug), arbitrary)]
+pub enum Resource {
+    Gold(u8),
+    Wood(u16),
+    #[cfg(feature = "cond_comp")]
+    Stone(u32),
+}
+
+#[discriminant_generic_table(
+    ty_name = Limits,
+    mod_name = limits,
+    storage = array,
+    derive(Debug),
+    arbitrary
+)]
+pub enum Stat {
+    Strength,
+    Agility,
+}
+
+fn unstructured(seed: u8, len: usize) -> Vec<u8> {
+    (0..len)
+        .map(|idx| (idx as u8).wrapping_mul(31).wrapping_add(seed))
+        .collect()
+}
+
+#[test]
+fn test_arbitrary_enum() {
+    let mut moves = 0;
+    let mut says = 0;
+
+    for seed in 0..=255 {
+        let bytes = unstructured(seed, 16);
+        match Command::arbitrary(&mut Unstructured::new(&bytes)).unwrap() {
+            Command::Move(_) => moves += 1,
+            Command::Say(_) => says += 1,
+            #[cfg(feature = "cond_comp")]
+            Command::Wait(_) => {}
+            Command::Connect(_) => panic!("`Connect` has the attribute `#[arbitrary(skip)]`"),
+        }
+    }
+
+    assert!(moves > says);
+    assert!(says > 0);
+
+    // Extracted variant types are generated directly.
+    let bytes = [1, 2];
+    assert_eq!(Move::arbitrary(&mut Unstructured::new(&bytes)), Ok(Move { x: 1, y: 2 }));
+}
+
+#[test]
+fn test_arbitrary_generic_enum() {
+    let mut payloads = 0;
+
+    for seed in 0..=255 {
+        let bytes = unstructured(seed, 8);
+        if let Event::Payload(_) = Event::<u32>::arbitrary(&mut Unstructured::new(&bytes)).unwrap()
+        {
+            payloads += 1;
+        }
+    }
+
+    assert!(payloads > 0 && payloads < 256);
+}
+
+#[test]
+fn test_arbitrary_tables() {
+    let bytes = [1, 0, 2, 0, 3, 0];
+    let budget = Budget::<u16>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+    assert_eq!(budget.gold, 1);
+    assert_eq!(budget.wood, 2);
+    #[cfg(feature = "cond_comp")]
+    assert_eq!(budget.stone, 3);
+
+    let bytes = [7, 9];
+    let limits = Limits::<u8>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+    assert_eq!(limits.values, [7, 9]);
+}
@@ -64,6 +64,7 @@ pub enum ConfigurableSettings {
 }
 
 #[discriminant_generic_table]
+#[discriminant_set]
 pub enum ConfigurableSettingsWithDiscriminants {
     BaseSettings,
     #[cfg(feature = "cond_comp_graphics")]
@@ -450,4 +451,36 @@ mod table_tests {
             }
         }
     }
+
+    #[test]
+    fn test_configurable_settings_set_creation() {
+        let all = ConfigurableSettingsWithDiscriminantsDiscriminantSet::all();
+        assert!(all.contains(ConfigurableSettingsWithDiscriminants::BaseSettings));
+
+        #[allow(unused_mut)]
+        let mut expected_len = 1;
+
+        #[cfg(feature = "cond_comp_graphics")]
+        {
+            expected_len += 1;
+            assert!(all.contains(ConfigurableSettingsWithDiscriminants::GraphicsSettings));
+        }
+
+        #[cfg(feature = "cond_comp_audio")]
+        {
+            expected_len += 1;
+            assert!(all.contains(ConfigurableSettingsWithDiscriminants::AudioSettings));
+        }
+
+        #[cfg(all(feature = "cond_comp_networking", not(target_arch = "wasm32")))]
+        {
+            expected_len += 1;
+            assert!(all.contains(ConfigurableSettingsWithDiscriminants::NetworkSettings));
+        }
+
+        // Bits are packed, skipping variants removed by `#[cfg]`.
+        assert_eq!(all.len(), expected_len);
+        assert_eq!(all.bits().count_ones() as usize, expected_len);
+        assert_eq!(all.bits(), (1 << expected_len) - 1);
+    }
 }
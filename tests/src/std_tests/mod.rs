@@ -1,9 +1,15 @@
 use super::*;
 
 mod advanced_enum_test;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
 mod conditional_compilation;
 mod delegated_enum;
+#[cfg(feature = "proptest")]
+mod proptest_strategies;
 mod support_marked_receiver;
 mod variant_generic_tables;
 mod variant_type_tables;
+#[cfg(feature = "alloc")]
+mod variant_vecs;
 mod weird;
@@ -0,0 +1,101 @@
+#![allow(unused)]
+use spire_enum::proptest::{
+    arbitrary::any,
+    strategy::{Strategy, ValueTree},
+    test_runner::TestRunner,
+};
+
+use super::*;
+
+#[delegated_enum(extract_variants(derive(Debug, Clone, PartialEq)), proptest)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    #[weight(3)]
+    Move {
+        x: i8,
+        y: i8,
+    },
+    Say(String, bool),
+    #[cfg(feature = "cond_comp")]
+    Wait(u16),
+    #[arbitrary(skip)]
+    Connect(std::rc::Rc<str>),
+}
+
+#[variant_type_table(derive(Debug), proptest)]
+pub enum Shape {
+    Circle(u8),
+    Rect { w: u16, h: u16 },
+}
+
+#[variant_generic_table(ty_name = Budget, mod_name = budget, derive(Debug), proptest)]
+pub enum Resource {
+    Gold(u8),
+    Wood(u16),
+    #[cfg(feature = "cond_comp")]
+    Stone(u32),
+}
+
+#[test]
+fn test_proptest_enum() {
+    let mut runner = TestRunner::deterministic();
+    let strategy = any::<Command>();
+
+    let mut moves = 0;
+    let mut says = 0;
+
+    for _ in 0..200 {
+        match strategy.new_tree(&mut runner).unwrap().current() {
+            Command::Move(_) => moves += 1,
+            Command::Say(_) => says += 1,
+            #[cfg(feature = "cond_comp")]
+            Command::Wait(_) => {}
+            Command::Connect(_) => panic!("`Connect` has the attribute `#[arbitrary(skip)]`"),
+        }
+    }
+
+    assert!(moves > says);
+    assert!(says > 0);
+}
+
+#[test]
+fn test_proptest_shrinks_variant_types() {
+    let mut runner = TestRunner::deterministic();
+
+    // Finds the minimal failing input: shrinking the fields of the extracted type.
+    let result = runner.run(&any::<Move>(), |Move { x, y }| {
+        spire_enum::proptest::prop_assert!(x < 10 || y < 10);
+        Ok(())
+    });
+
+    match result {
+        Err(spire_enum::proptest::test_runner::TestError::Fail(_, value)) => {
+            assert_eq!(value, Move { x: 10, y: 10 })
+        }
+        other => panic!("expected a failure, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_proptest_tables() {
+    let mut runner = TestRunner::deterministic();
+
+    let result = runner.run(&any::<Budget<u32>>(), |budget| {
+        spire_enum::proptest::prop_assert!(budget.gold < 100 || budget.wood < 100);
+        Ok(())
+    });
+
+    match result {
+        Err(spire_enum::proptest::test_runner::TestError::Fail(_, budget)) => {
+            assert_eq!((budget.gold, budget.wood), (100, 100));
+        }
+        other => panic!("expected a failure, got {other:?}"),
+    }
+
+    let table = any::<ShapeVariantTypeTable>()
+        .new_tree(&mut runner)
+        .unwrap()
+        .current();
+    let _: &u8 = table.get::<u8>();
+    let _: &(u16, u16) = table.get::<(u16, u16)>();
+}
@@ -0,0 +1,117 @@
+#![allow(unused)]
+use super::*;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spark {
+    heat: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Smoke {
+    density: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ember;
+
+pub trait Update {
+    fn update(&mut self);
+}
+
+impl Update for Spark {
+    fn update(&mut self) { self.heat = self.heat.saturating_sub(1); }
+}
+
+impl Update for Smoke {
+    fn update(&mut self) { self.density += 1; }
+}
+
+impl Update for Ember {
+    fn update(&mut self) {}
+}
+
+#[delegated_enum(impl_conversions)]
+#[variant_vecs(derive(Debug, Clone, PartialEq))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Particle {
+    Spark(Spark),
+    Smoke(Smoke),
+    Ember(Ember),
+}
+
+#[test]
+fn test_variant_vecs() {
+    let mut particles = ParticleVariantVecs::new();
+    assert!(particles.is_empty());
+
+    particles.push(Spark { heat: 2 }.into());
+    particles.push(Smoke { density: 5 }.into());
+    particles.push(Spark { heat: 1 }.into());
+    particles.push(Ember.into());
+
+    assert_eq!(particles.len(), 4);
+    assert_eq!(particles.len_of::<Spark>(), 2);
+    assert_eq!(particles.len_of::<Smoke>(), 1);
+    assert_eq!(particles.get::<Ember>(), &vec![Ember]);
+
+    // The collection expression is only evaluated once.
+    let mut evaluated = 0;
+    for_each_delegated_particle_variant_vecs!(*{ evaluated += 1; &mut particles } => |particle| particle.update());
+    assert_eq!(evaluated, 1);
+    assert_eq!(
+        particles.iter::<Spark>().collect::<Vec<_>>(),
+        [&Spark { heat: 1 }, &Spark { heat: 0 }]
+    );
+    assert_eq!(particles.iter::<Smoke>().next(), Some(&Smoke { density: 6 }));
+
+    particles
+        .iter_mut::<Smoke>()
+        .for_each(|smoke| smoke.density = 0);
+    particles.retain::<Spark>(|spark| spark.heat > 0);
+    assert_eq!(particles.len_of::<Spark>(), 1);
+
+    // Values are yielded grouped by variant, in declaration order.
+    let drained = particles.drain().collect::<Vec<_>>();
+    assert_eq!(
+        drained,
+        [
+            Particle::Spark(Spark { heat: 1 }),
+            Particle::Smoke(Smoke { density: 0 }),
+            Particle::Ember(Ember),
+        ]
+    );
+    assert!(particles.is_empty());
+}
+
+#[test]
+fn test_variant_vecs_collect() {
+    let mut particles = [
+        Smoke { density: 1 }.into(),
+        Ember.into(),
+        Smoke { density: 2 }.into(),
+    ]
+    .into_iter()
+    .collect::<ParticleVariantVecs>();
+    assert_eq!(particles.len_of::<Smoke>(), 2);
+
+    particles.extend([Particle::from(Spark { heat: 3 })]);
+    assert_eq!(particles.get_mut::<Spark>().pop(), Some(Spark { heat: 3 }));
+
+    particles.clear();
+    assert_eq!(particles, ParticleVariantVecs::default());
+}
+
+#[variant_vecs]
+pub enum Payload<'a, T: Clone> {
+    Borrowed(&'a str),
+    Owned(Vec<T>),
+}
+
+#[test]
+fn test_generic_variant_vecs() {
+    let mut payloads = PayloadVariantVecs::<'_, u8>::new();
+    payloads.push(Payload::Owned(vec![1, 2]));
+    payloads.push(Payload::Borrowed("a"));
+    assert_eq!(payloads.iter::<Vec<u8>>().flatten().sum::<u8>(), 3);
+    assert_eq!(payloads.len_of::<&str>(), 1);
+}